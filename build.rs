@@ -8,6 +8,7 @@ fn main() {
         "qml/ClideTreeView.qml",
         "qml/Components/ClideAboutWindow.qml",
         "qml/Components/ClideBreadCrumbs.qml",
+        "qml/Components/ClideDiffView.qml",
         "qml/Components/ClideEditor.qml",
         "qml/Components/ClideHandle.qml",
         "qml/Components/ClideLogger.qml",
@@ -28,6 +29,10 @@ fn main() {
     .qt_module("Svg")
     .qt_module("Xml")
     .qrc("./resources.qrc")
-    .files(["src/gui/colors.rs", "src/gui/filesystem.rs"])
+    .files([
+        "src/gui/colors.rs",
+        "src/gui/filesystem.rs",
+        "src/gui/recent.rs",
+    ])
     .build();
 }
@@ -15,6 +15,14 @@ SplitView {
     // Path to the directory of the project opened in clide.
     required property string projectDir
 
+    // Open a file in the editor and record it in the recent files list.
+    function openFile(path) {
+        Logger.trace("Opening file in editor: " + path);
+        RecentFiles.addFile(path);
+        clideEditorView.diffPath = "";
+        clideEditorView.filePath = path;
+    }
+
     anchors.fill: parent
 
     // Customized handle to drag between the Navigation and the Editor.
@@ -28,10 +36,15 @@ SplitView {
         SplitView.preferredWidth: 200
         projectDir: root.projectDir
 
+        // Compare files with the file open in the editor.
+        onCompareClicked: path => {
+            Logger.trace("Comparing editor file from ClideExplorerView signal: " + path);
+            clideEditorView.diffPath = path;
+        }
         // Open files when clicked in the explorer.
         onFileClicked: path => {
             Logger.trace("Setting editor path from ClideExplorerView signal: " + path)
-            clideEditorView.filePath = path;
+            root.openFile(path);
         }
     }
     ClideEditorView {
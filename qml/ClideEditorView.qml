@@ -15,6 +15,8 @@ Rectangle {
     // The path to the file to show in the text editor.
     // This is updated by a signal caught within ClideApplicationView.
     required property string filePath
+    // The path to a file compared with the open file, shown in place of the editor while it is set.
+    property string diffPath: ""
 
     clip: true
     color: "transparent"
@@ -42,6 +44,15 @@ Rectangle {
 
         ClideEditor {
             SplitView.preferredHeight: 650
+            visible: root.diffPath === ""
+        }
+        ClideDiffView {
+            SplitView.preferredHeight: 650
+            newPath: root.diffPath
+            oldPath: root.filePath
+            visible: root.diffPath !== ""
+
+            onClosed: root.diffPath = ""
         }
         ClideLogger {
         }
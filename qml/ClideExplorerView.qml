@@ -14,6 +14,7 @@ Rectangle {
 
     required property string projectDir
 
+    signal compareClicked(string path)
     signal fileClicked(string path)
 
     clip: true
@@ -51,7 +52,8 @@ Rectangle {
             originalRootDirectory: root.projectDir
             rootDirectory: root.projectDir
 
-            // Pass the signal to the parent component using another signal.
+            // Pass the signals to the parent component using other signals.
+            onCompareClicked: path => root.compareClicked(path)
             onFileClicked: path => root.fileClicked(path)
             onRootDirectoryChanged: {
                 Logger.log("Setting root directory: " + clideTreeView.rootDirectory);
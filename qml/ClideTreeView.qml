@@ -17,6 +17,7 @@ TreeView {
     property string rootDirectory
     property int rootIndent: 25
 
+    signal compareClicked(string filePath)
     signal fileClicked(string filePath)
 
     boundsBehavior: Flickable.StopAtBounds
@@ -143,6 +144,17 @@ TreeView {
                     }
                 }
             }
+            ClideMenuItem {
+                action: Action {
+                    enabled: !treeDelegate.hasChildren
+                    text: qsTr("Compare with open file")
+
+                    onTriggered: {
+                        Logger.debug("Comparing with open file: " + treeDelegate.filePath);
+                        root.compareClicked(treeDelegate.filePath);
+                    }
+                }
+            }
             ClideMenuItem {
                 action: Action {
                     text: qsTr("Reset root")
@@ -0,0 +1,179 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+import QtQuick
+import QtQuick.Controls
+import QtQuick.Layouts
+
+import clide.module 1.0
+import Logger 1.0
+
+Rectangle {
+    id: root
+
+    // The index of the selected change in the list of hunks.
+    property int hunk: 0
+    // The rows where each change starts.
+    readonly property var hunkRows: rows.reduce((starts, row, index) => row.hunk ? starts.concat(index) : starts, [])
+    required property string newPath
+    required property string oldPath
+    // The rows of the diff, each with the old and new line side by side or a single line inline.
+    readonly property var rows: {
+        if (root.oldPath === "" || root.newPath === "")
+            return [];
+        const json = FileSystem.diffFiles(root.oldPath, root.newPath, root.sideBySide);
+        return json.length > 0 ? JSON.parse(json) : [];
+    }
+    property bool sideBySide: true
+
+    signal closed
+
+    function fileName(path) {
+        return path.toString().split("/").pop();
+    }
+
+    // Select a change and scroll to it.
+    function gotoHunk(index) {
+        if (root.hunkRows.length === 0)
+            return;
+        root.hunk = Math.max(0, Math.min(index, root.hunkRows.length - 1));
+        diffList.positionViewAtIndex(root.hunkRows[root.hunk], ListView.Center);
+    }
+
+    color: RustColors.editor_background
+
+    onRowsChanged: gotoHunk(0)
+
+    ColumnLayout {
+        anchors.fill: parent
+        spacing: 0
+
+        RowLayout {
+            Layout.fillWidth: true
+            Layout.margins: 4
+
+            Label {
+                Layout.fillWidth: true
+                color: RustColors.editor_text
+                elide: Text.ElideMiddle
+                text: root.fileName(root.oldPath) + " ↔ " + root.fileName(root.newPath)
+            }
+            Label {
+                color: RustColors.linenumber
+                text: {
+                    switch (root.hunkRows.length) {
+                    case 0:
+                        return qsTr("No changes");
+                    case 1:
+                        return qsTr("1 change");
+                    default:
+                        return qsTr("Change %1 of %2").arg(root.hunk + 1).arg(root.hunkRows.length);
+                    }
+                }
+            }
+            Button {
+                text: qsTr("Previous change")
+
+                onClicked: root.gotoHunk(root.hunk - 1)
+            }
+            Button {
+                text: qsTr("Next change")
+
+                onClicked: root.gotoHunk(root.hunk + 1)
+            }
+            Button {
+                text: root.sideBySide ? qsTr("Inline") : qsTr("Side by side")
+
+                onClicked: root.sideBySide = !root.sideBySide
+            }
+            Button {
+                text: qsTr("Close")
+
+                onClicked: root.closed()
+            }
+        }
+        ListView {
+            id: diffList
+
+            Layout.fillHeight: true
+            Layout.fillWidth: true
+            boundsBehavior: Flickable.StopAtBounds
+            clip: true
+            model: root.rows
+
+            ScrollBar.vertical: ClideScrollBar {
+            }
+            delegate: Row {
+                id: diffRow
+
+                required property int index
+                required property var modelData
+
+                width: diffList.width
+
+                Repeater {
+                    model: diffRow.modelData.cells
+
+                    // A line of the old or new file, or a filler where the other side has a line with no match.
+                    delegate: Rectangle {
+                        id: cell
+
+                        // The side of a side by side diff, with the old file on the left.
+                        required property int index
+                        required property var modelData
+
+                        color: {
+                            if (!cell.modelData)
+                                return Qt.darker(RustColors.editor_background, 1.3);
+                            switch (cell.modelData.change) {
+                            case "delete":
+                                return "#3f1f22";
+                            case "insert":
+                                return "#1f3a26";
+                            default:
+                                return "transparent";
+                            }
+                        }
+                        height: lineText.implicitHeight
+                        width: diffRow.width / diffRow.modelData.cells.length
+
+                        Row {
+                            anchors.fill: parent
+                            spacing: 6
+
+                            // Inline diffs show the old and new line numbers.
+                            Label {
+                                color: RustColors.linenumber
+                                font: lineText.font
+                                horizontalAlignment: Text.AlignRight
+                                text: {
+                                    if (!cell.modelData)
+                                        return "";
+                                    const number = row => row === null ? "" : row;
+                                    if (root.sideBySide)
+                                        return number(cell.index === 0 ? cell.modelData.old : cell.modelData.new);
+                                    return number(cell.modelData.old) + " " + number(cell.modelData.new);
+                                }
+                                width: fontMetrics.averageCharacterWidth * (root.sideBySide ? 5 : 10)
+                            }
+                            Text {
+                                id: lineText
+
+                                color: RustColors.editor_text
+                                font.family: "monospace"
+                                text: cell.modelData ? cell.modelData.html : ""
+                                textFormat: Text.RichText
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    FontMetrics {
+        id: fontMetrics
+
+        font.family: "monospace"
+    }
+}
@@ -10,10 +10,39 @@ import clide.module 1.0
 import Logger 1.0
 
 Rectangle {
+    id: editor
+
+    // Images are shown instead of the text editor, since they can't be edited.
+    readonly property bool isImage: FileSystem.isImage(root.filePath)
+
     color: RustColors.editor_background
 
+    ColumnLayout {
+        anchors.fill: parent
+        visible: editor.isImage
+
+        Image {
+            Layout.fillHeight: true
+            Layout.fillWidth: true
+            asynchronous: true
+            fillMode: Image.PreserveAspectFit
+            horizontalAlignment: Image.AlignHCenter
+            source: editor.isImage ? "file://" + root.filePath : ""
+            // SVG images are drawn at the size of the editor so they stay sharp.
+            sourceSize: source.toString().toLowerCase().endsWith(".svg") ? Qt.size(width, height) : undefined
+            verticalAlignment: Image.AlignVCenter
+        }
+        Label {
+            Layout.fillWidth: true
+            color: RustColors.linenumber
+            horizontalAlignment: Text.AlignHCenter
+            padding: 4
+            text: editor.isImage ? FileSystem.imageInfo(root.filePath) : ""
+        }
+    }
     RowLayout {
         anchors.fill: parent
+        visible: !editor.isImage
 
         // We use a flickable to synchronize the position of the editor and
         // the line numbers. This is necessary because the line numbers can
@@ -112,5 +141,39 @@ Rectangle {
                 font: textArea.font
             }
         }
+        // A rendered preview shown beside markdown files.
+        // It is scrolled to the same fraction of its height as the editor.
+        Flickable {
+            id: previewFlickable
+
+            readonly property real editorScroll: editorFlickable.contentY / Math.max(1, editorFlickable.contentHeight - editorFlickable.height)
+
+            Layout.fillHeight: true
+            Layout.fillWidth: true
+            boundsBehavior: Flickable.StopAtBounds
+            contentY: editorScroll * Math.max(0, contentHeight - height)
+            interactive: false
+            visible: root.filePath.toString().toLowerCase().endsWith(".md")
+
+            ScrollBar.vertical: ClideScrollBar {
+            }
+            TextArea.flickable: TextArea {
+                id: preview
+
+                antialiasing: true
+                readOnly: true
+                selectByMouse: true
+                selectedTextColor: RustColors.editor_highlighted_text
+                selectionColor: RustColors.editor_highlight
+                // The editor holds highlighted HTML, so its plain text is the markdown source.
+                text: previewFlickable.visible ? FileSystem.renderMarkdown(textArea.getText(0, textArea.length)) : ""
+                textFormat: TextEdit.RichText
+                wrapMode: TextArea.Wrap
+
+                onLinkActivated: function (link) {
+                    Qt.openUrlExternally(link);
+                }
+            }
+        }
     }
 }
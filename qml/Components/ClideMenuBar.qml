@@ -8,6 +8,11 @@ import QtQuick.Controls
 import clide.module 1.0
 
 MenuBar {
+    id: root
+
+    signal recentFileSelected(string path)
+    signal recentProjectSelected(string path)
+
     // Background for this MenuBar.
     background: Rectangle {
         color: RustColors.menubar
@@ -34,6 +39,47 @@ MenuBar {
 
             onTriggered: FileSystem.setDirectory(FileSystem.filePath)
         }
+        ClideMenu {
+            id: recentFilesMenu
+
+            title: qsTr("Open &Recent File")
+
+            // Entries are provided by the Rust QML singleton RecentFiles in recent.rs
+            Instantiator {
+                model: RecentFiles.files
+
+                delegate: ClideMenuItem {
+                    required property string modelData
+
+                    text: modelData
+
+                    onTriggered: root.recentFileSelected(modelData)
+                }
+
+                onObjectAdded: (index, object) => recentFilesMenu.insertItem(index, object)
+                onObjectRemoved: (index, object) => recentFilesMenu.removeItem(object)
+            }
+        }
+        ClideMenu {
+            id: recentProjectsMenu
+
+            title: qsTr("Open Recent &Project")
+
+            Instantiator {
+                model: RecentFiles.projects
+
+                delegate: ClideMenuItem {
+                    required property string modelData
+
+                    text: modelData
+
+                    onTriggered: root.recentProjectSelected(modelData)
+                }
+
+                onObjectAdded: (index, object) => recentProjectsMenu.insertItem(index, object)
+                onObjectRemoved: (index, object) => recentProjectsMenu.removeItem(object)
+            }
+        }
         ClideMenuItem {
             action: Action {
                 id: actionSave
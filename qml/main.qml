@@ -8,6 +8,7 @@ import QtQuick.Layouts
 import QtQuick.Dialogs
 
 import clide.module 1.0
+import Logger 1.0
 
 ApplicationWindow {
     id: appWindow
@@ -20,6 +21,12 @@ ApplicationWindow {
     width: 1200
 
     menuBar: ClideMenuBar {
+        onRecentFileSelected: path => appView.openFile(path)
+        onRecentProjectSelected: path => {
+            Logger.info("Opening recent project: " + path);
+            RecentFiles.addProject(path);
+            appWindow.appContextPath = path;
+        }
     }
 
     Rectangle {
@@ -0,0 +1,66 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use anyhow::{Context, Result};
+use log::{trace, warn};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::PathBuf;
+
+pub const ID: &str = "config";
+
+/// The directory where clide stores settings and session state for the current user.
+/// On Linux this is typically `~/.config/clide`.
+pub fn config_dir() -> Result<PathBuf> {
+    let mut dir = dirs::config_dir().context("Failed to find the user configuration directory")?;
+    dir.push("clide");
+    Ok(dir)
+}
+
+/// The directory where clide stores data that is not configuration, such as undo history.
+/// On Linux this is typically `~/.local/share/clide`.
+pub fn data_dir() -> Result<PathBuf> {
+    let mut dir = dirs::data_dir().context("Failed to find the user data directory")?;
+    dir.push("clide");
+    Ok(dir)
+}
+
+/// Loads a TOML file from the clide configuration directory.
+/// If the file does not exist or fails to parse, the default value is returned so clide can
+/// always start with a usable configuration.
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> T {
+    let path = match config_dir() {
+        Ok(dir) => dir.join(file_name),
+        Err(e) => {
+            warn!(target:ID, "Using default {file_name}: {e}");
+            return T::default();
+        }
+    };
+    if !path.exists() {
+        trace!(target:ID, "No {path:?} found; Using defaults");
+        return T::default();
+    }
+    match fs::read_to_string(&path)
+        .context(format!("Failed to read {path:?}"))
+        .and_then(|contents| toml::from_str(&contents).context(format!("Failed to parse {path:?}")))
+    {
+        Ok(value) => value,
+        Err(e) => {
+            warn!(target:ID, "Using default {file_name}: {e:#}");
+            T::default()
+        }
+    }
+}
+
+/// Writes a value as TOML to a file in the clide configuration directory.
+pub fn save<T: Serialize>(file_name: &str, value: &T) -> Result<()> {
+    let dir = config_dir()?;
+    fs::create_dir_all(&dir).context(format!("Failed to create directory {dir:?}"))?;
+    let path = dir.join(file_name);
+    trace!(target:ID, "Saving {path:?}");
+    let contents =
+        toml::to_string_pretty(value).context(format!("Failed to serialize {path:?}"))?;
+    fs::write(&path, contents).context(format!("Failed to write {path:?}"))
+}
@@ -0,0 +1,290 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use anyhow::{Context, Result, bail};
+use log::{trace, warn};
+use serde_json::{Value, json};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::thread;
+
+/// Reads one message from a debug adapter. Messages are JSON preceded by a `Content-Length`
+/// header, like those of the language server protocol.
+/// Returns `None` once the adapter closes its output between messages, and an error for a
+/// message that is cut off or has an invalid `Content-Length` or body.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut length = None;
+    let mut has_headers = false;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return match has_headers {
+                true => Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "Debug adapter output ended in the headers of a message",
+                )),
+                false => Ok(None),
+            };
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            // A blank line ends the headers, unless it comes before any header.
+            match (length, has_headers) {
+                (Some(_), _) => break,
+                (None, false) => continue,
+                (None, true) => return Err(invalid("Message has no Content-Length".to_string())),
+            }
+        }
+        has_headers = true;
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("Content-Length")
+        {
+            let value = value.trim();
+            length = match value.parse::<usize>() {
+                Ok(length) => Some(length),
+                Err(e) => return Err(invalid(format!("Invalid Content-Length {value:?}: {e}"))),
+            };
+        }
+    }
+    let mut body = vec![0; length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| invalid(e.to_string()))
+}
+
+/// Writes one message to a debug adapter, with its `Content-Length` header.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()
+}
+
+/// A debug adapter running in a child process, such as `lldb-dap` or `gdb --interpreter=dap`,
+/// that is sent requests on its stdin.
+///
+/// Its messages are read on a background thread and returned by `try_recv`, so the UI never
+/// waits on the adapter. Anything the adapter writes to stderr is logged.
+pub struct DapClient {
+    /// The adapter process, which is None for adapters connected with `connect`.
+    child: Option<Child>,
+    writer: Box<dyn Write + Send>,
+    receiver: Receiver<Value>,
+    /// The sequence number of the last message sent.
+    seq: i64,
+}
+
+impl DapClient {
+    pub const ID: &str = "DapClient";
+
+    /// Starts a debug adapter from a command and its arguments.
+    pub fn spawn(command: &[String], cwd: &Path) -> Result<Self> {
+        let Some((program, args)) = command.split_first() else {
+            bail!("No debug adapter command is set");
+        };
+        trace!(target:Self::ID, "Starting debug adapter {command:?}");
+        let mut child = Command::new(program)
+            .args(args)
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start debug adapter {program:?}"))?;
+        let stdin = child
+            .stdin
+            .take()
+            .context("Failed to open debug adapter stdin")?;
+        let stdout = child
+            .stdout
+            .take()
+            .context("Failed to open debug adapter stdout")?;
+        let stderr = child
+            .stderr
+            .take()
+            .context("Failed to open debug adapter stderr")?;
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                warn!(target:Self::ID, "{line}");
+            }
+        });
+        let mut client = Self::connect(stdout, stdin);
+        client.child = Some(child);
+        Ok(client)
+    }
+
+    /// Talks to a debug adapter that reads requests from `writer` and writes its messages to
+    /// `reader`, such as the stdin and stdout of an adapter process.
+    pub fn connect(
+        reader: impl Read + Send + 'static,
+        writer: impl Write + Send + 'static,
+    ) -> Self {
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(reader);
+            loop {
+                match read_message(&mut reader) {
+                    // The receiver is dropped when the debug session ends.
+                    Ok(Some(message)) => {
+                        if sender.send(message).is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => return,
+                    Err(e) => {
+                        warn!(target:Self::ID, "Failed to read debug adapter message: {e}");
+                        return;
+                    }
+                }
+            }
+        });
+        Self {
+            child: None,
+            writer: Box::new(writer),
+            receiver,
+            seq: 0,
+        }
+    }
+
+    fn send(&mut self, mut message: Value) -> Result<i64> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        trace!(target:Self::ID, "Sending {message}");
+        write_message(&mut self.writer, &message).context("Failed to write to debug adapter")?;
+        Ok(self.seq)
+    }
+
+    /// Sends a request, returning its sequence number to match it with its response.
+    pub fn request(&mut self, command: &str, arguments: Value) -> Result<i64> {
+        self.send(json!({
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        }))
+    }
+
+    /// Responds to a request sent by the adapter, such as `runInTerminal`.
+    pub fn respond(&mut self, request: &Value, success: bool, message: &str) -> Result<()> {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": success,
+            "message": message,
+        }))
+        .map(|_| ())
+    }
+
+    /// The next message from the adapter if one has arrived. Returns an error once the adapter
+    /// has exited and every message it sent has been received.
+    pub fn try_recv(&mut self) -> Result<Option<Value>> {
+        match self.receiver.try_recv() {
+            Ok(message) => {
+                trace!(target:Self::ID, "Received {message}");
+                Ok(Some(message))
+            }
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => bail!("The debug adapter exited"),
+        }
+    }
+}
+
+impl Drop for DapClient {
+    fn drop(&mut self) {
+        // The adapter usually exits after a disconnect request, but may not if it is unresponsive.
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn read_all(input: &str) -> Vec<io::Result<Option<Value>>> {
+        let mut reader = Cursor::new(input.as_bytes().to_vec());
+        let mut messages = Vec::new();
+        loop {
+            let message = read_message(&mut reader);
+            let done = !matches!(message, Ok(Some(_)));
+            messages.push(message);
+            if done {
+                return messages;
+            }
+        }
+    }
+
+    #[test]
+    fn write_message_frames_body_by_byte_length() {
+        let mut output = Vec::new();
+        write_message(&mut output, &json!({ "text": "é" })).unwrap();
+        let body = r#"{"text":"é"}"#;
+        let expected = format!("Content-Length: {}\r\n\r\n{body}", body.len());
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+        assert_eq!(body.len(), body.chars().count() + 1);
+    }
+
+    #[test]
+    fn read_message_reads_written_messages() {
+        let messages = [json!({ "seq": 1, "text": "é" }), json!({ "seq": 2 })];
+        let mut output = Vec::new();
+        for message in &messages {
+            write_message(&mut output, message).unwrap();
+        }
+        let mut reader = Cursor::new(output);
+        for message in &messages {
+            assert_eq!(read_message(&mut reader).unwrap().as_ref(), Some(message));
+        }
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn read_message_accepts_other_headers_and_line_endings() {
+        let input = concat!(
+            "\r\nContent-Type: application/json\r\ncontent-length:  2 \r\n\r\n{}",
+            "\n\nContent-Length: 5\n\n[1,2]",
+        );
+        let messages = read_all(input);
+        assert_eq!(messages[0].as_ref().unwrap(), &Some(json!({})));
+        assert_eq!(messages[1].as_ref().unwrap(), &Some(json!([1, 2])));
+        assert_eq!(messages[2].as_ref().unwrap(), &None);
+    }
+
+    #[test]
+    fn read_message_ends_at_end_of_output() {
+        assert_eq!(read_all("").pop().unwrap().unwrap(), None);
+        assert_eq!(read_all("\r\n\r\n").pop().unwrap().unwrap(), None);
+    }
+
+    #[test]
+    fn read_message_rejects_truncated_messages() {
+        for input in [
+            "Content-Length: 10\r\n",
+            "Content-Length: 10\r\n\r\n{}",
+            "Content-Le",
+        ] {
+            let error = read_all(input).pop().unwrap().unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn read_message_rejects_malformed_headers() {
+        for input in [
+            "Content-Length: ten\r\n\r\n{}",
+            "Content-Length: -2\r\n\r\n{}",
+            "Content-Type: application/json\r\n\r\n{}",
+            "Content-Length: 3\r\n\r\n{]}",
+        ] {
+            let error = read_all(input).pop().unwrap().unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{input:?}");
+        }
+    }
+}
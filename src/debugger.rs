@@ -0,0 +1,850 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::dap::DapClient;
+use crate::settings::Settings;
+use anyhow::{Context, Result, bail};
+use log::{error, info, trace, warn};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// A command for the debugger, from the debug panes, the menu or a key binding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugCommand {
+    /// Launch the program set in the `[debugger]` settings.
+    Start,
+    /// Run the stopped program until it reaches a breakpoint.
+    Continue,
+    Pause,
+    /// Run to the next line, stepping over function calls.
+    StepOver,
+    /// Run to the next line, stepping into function calls.
+    StepIn,
+    /// Run until the current function returns.
+    StepOut,
+    /// End the session, terminating the program.
+    Stop,
+    /// Show the variables of a frame in the call stack, and its line in the editor.
+    SelectFrame(usize),
+    /// Expand or collapse a variable with children.
+    ToggleVariable(usize),
+    AddWatch(String),
+    RemoveWatch(usize),
+    /// Evaluate an expression typed in the debug console.
+    Evaluate(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugState {
+    /// The adapter was started, and the program is being launched.
+    Starting,
+    Running,
+    Stopped,
+}
+
+/// A function call in the call stack of the stopped thread.
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    id: i64,
+    pub name: String,
+    /// The source file, unless the frame is in code without debug information.
+    pub path: Option<PathBuf>,
+    pub row: usize,
+}
+
+/// A scope or variable in the stopped frame. The children of an expanded entry follow it.
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    pub type_name: String,
+    /// Identifies the children of the variable to the adapter, or 0 if it has none.
+    reference: i64,
+    pub depth: usize,
+    pub expanded: bool,
+}
+
+impl Variable {
+    pub fn has_children(&self) -> bool {
+        self.reference > 0
+    }
+}
+
+/// An expression evaluated each time the program stops.
+#[derive(Debug, Clone)]
+pub struct Watch {
+    pub expression: String,
+    /// The result from the last time the program stopped, if it has stopped since it was added.
+    pub value: Option<String>,
+    pub error: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsoleKind {
+    /// Output of the program or the adapter.
+    Output,
+    /// Errors, including output of the program to stderr.
+    Error,
+    /// An expression typed in the console.
+    Input,
+    /// The result of an expression.
+    Result,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsoleLine {
+    pub kind: ConsoleKind,
+    pub text: String,
+}
+
+/// What a request sent to the adapter was for, used to handle its response.
+enum Pending {
+    Initialize,
+    Launch,
+    SetBreakpoints(PathBuf),
+    Threads,
+    StackTrace,
+    /// The scopes of a frame, by its id.
+    Scopes(i64),
+    /// The children of a variable, by its reference.
+    Variables(i64),
+    /// The value of a watch expression, by its index.
+    Watch(usize),
+    Evaluate,
+    Disconnect,
+    Other,
+}
+
+/// A running debug adapter and the program it is debugging.
+struct Session {
+    client: DapClient,
+    state: DebugState,
+    /// Requests waiting for a response, by their sequence number.
+    pending: HashMap<i64, Pending>,
+    /// The arguments of the launch request, sent once the adapter is initialized.
+    launch: Option<Value>,
+    /// Set once the adapter is ready to be given breakpoints.
+    initialized: bool,
+    /// The thread that last stopped, which is stepped and continued.
+    thread_id: Option<i64>,
+    /// Set after a disconnect request, while waiting for the adapter to end the program.
+    stopping: bool,
+}
+
+/// The debugger for the TUI, which launches programs with a debug adapter using the Debug Adapter
+/// Protocol. Breakpoints are kept between sessions.
+///
+/// Messages from the adapter are handled by `poll`, which is called each frame, and the state of
+/// the stopped program is kept here for the debug panes to show.
+#[derive(Default)]
+pub struct Debugger {
+    session: Option<Session>,
+    /// The rows with a breakpoint in each file.
+    breakpoints: BTreeMap<PathBuf, BTreeSet<usize>>,
+    frames: Vec<StackFrame>,
+    selected_frame: usize,
+    variables: Vec<Variable>,
+    watches: Vec<Watch>,
+    console: Vec<ConsoleLine>,
+    /// The line to show in the editor, set when the program stops or another frame is selected.
+    location: Option<(PathBuf, usize)>,
+}
+
+impl Debugger {
+    pub const ID: &str = "Debugger";
+
+    /// The oldest lines of the debug console are removed past this many lines.
+    const MAX_CONSOLE_LINES: usize = 5000;
+
+    /// The number of frames of the call stack requested when the program stops.
+    const MAX_FRAMES: usize = 100;
+
+    pub fn state(&self) -> Option<DebugState> {
+        self.session.as_ref().map(|session| session.state)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.session.is_some()
+    }
+
+    pub fn frames(&self) -> &[StackFrame] {
+        &self.frames
+    }
+
+    pub fn selected_frame(&self) -> usize {
+        self.selected_frame
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    pub fn watches(&self) -> &[Watch] {
+        &self.watches
+    }
+
+    pub fn console(&self) -> &[ConsoleLine] {
+        &self.console
+    }
+
+    /// The rows with a breakpoint in a file.
+    pub fn breakpoints(&self, path: &Path) -> BTreeSet<usize> {
+        self.breakpoints.get(path).cloned().unwrap_or_default()
+    }
+
+    /// The row the program is stopped at in a file, in the selected frame.
+    pub fn current_row(&self, path: &Path) -> Option<usize> {
+        if self.state() != Some(DebugState::Stopped) {
+            return None;
+        }
+        let frame = self.frames.get(self.selected_frame)?;
+        (frame.path.as_deref() == Some(path)).then_some(frame.row)
+    }
+
+    fn print(&mut self, kind: ConsoleKind, text: &str) {
+        self.console.extend(text.lines().map(|line| ConsoleLine {
+            kind,
+            text: line.to_string(),
+        }));
+        let excess = self.console.len().saturating_sub(Self::MAX_CONSOLE_LINES);
+        self.console.drain(..excess);
+    }
+
+    /// Adds or removes a breakpoint, updating the breakpoints of the file in a running session.
+    pub fn toggle_breakpoint(&mut self, path: PathBuf, row: usize) -> Result<()> {
+        let rows = self.breakpoints.entry(path.clone()).or_default();
+        if !rows.remove(&row) {
+            rows.insert(row);
+        }
+        trace!(target:Self::ID, "Breakpoints in {path:?}: {rows:?}");
+        if rows.is_empty() {
+            self.breakpoints.remove(&path);
+        }
+        if self.session.as_ref().is_some_and(|s| s.initialized) {
+            self.send_breakpoints(path)?;
+        }
+        Ok(())
+    }
+
+    fn request(&mut self, command: &str, arguments: Value, pending: Pending) -> Result<()> {
+        let session = self
+            .session
+            .as_mut()
+            .context("No program is being debugged")?;
+        let seq = session.client.request(command, arguments)?;
+        session.pending.insert(seq, pending);
+        Ok(())
+    }
+
+    fn send_breakpoints(&mut self, path: PathBuf) -> Result<()> {
+        let lines: Vec<Value> = self
+            .breakpoints(&path)
+            .iter()
+            .map(|row| json!({ "line": row + 1 }))
+            .collect();
+        let arguments = json!({
+            "source": { "path": path },
+            "breakpoints": lines,
+        });
+        self.request("setBreakpoints", arguments, Pending::SetBreakpoints(path))
+    }
+
+    /// Runs a debugger command. Starting a session needs the project root, which is the working
+    /// directory of the adapter and the program, and which relative program paths start at.
+    pub fn run(&mut self, command: DebugCommand, root: &Path) -> Result<()> {
+        trace!(target:Self::ID, "Running {command:?}");
+        match command {
+            DebugCommand::Start => self.start(root),
+            DebugCommand::Continue => self.resume("continue"),
+            DebugCommand::StepOver => self.resume("next"),
+            DebugCommand::StepIn => self.resume("stepIn"),
+            DebugCommand::StepOut => self.resume("stepOut"),
+            DebugCommand::Pause => {
+                if self.state() != Some(DebugState::Running) {
+                    bail!("The program is not running");
+                }
+                // Adapters stop the whole program, so any thread can be given before one stops.
+                let thread_id = self.thread_id().unwrap_or_default();
+                self.request("pause", json!({ "threadId": thread_id }), Pending::Other)
+            }
+            DebugCommand::Stop => self.stop(),
+            DebugCommand::SelectFrame(index) => self.select_frame(index),
+            DebugCommand::ToggleVariable(index) => self.toggle_variable(index),
+            DebugCommand::AddWatch(expression) => {
+                self.watches.push(Watch {
+                    expression,
+                    value: None,
+                    error: false,
+                });
+                if self.state() == Some(DebugState::Stopped) {
+                    self.evaluate_watch(self.watches.len() - 1)?;
+                }
+                Ok(())
+            }
+            DebugCommand::RemoveWatch(index) => {
+                if index < self.watches.len() {
+                    self.watches.remove(index);
+                    // Responses for the watches after it would be given to the wrong watch.
+                    if let Some(session) = self.session.as_mut() {
+                        session
+                            .pending
+                            .retain(|_, pending| !matches!(pending, Pending::Watch(_)));
+                    }
+                    if self.state() == Some(DebugState::Stopped) {
+                        self.evaluate_watches()?;
+                    }
+                }
+                Ok(())
+            }
+            DebugCommand::Evaluate(expression) => {
+                self.print(ConsoleKind::Input, &format!("> {expression}"));
+                let mut arguments = json!({ "expression": expression, "context": "repl" });
+                if let Some(frame) = self.frames.get(self.selected_frame) {
+                    arguments["frameId"] = json!(frame.id);
+                }
+                self.request("evaluate", arguments, Pending::Evaluate)
+            }
+        }
+    }
+
+    fn start(&mut self, root: &Path) -> Result<()> {
+        if self.session.is_some() {
+            bail!("A program is already being debugged");
+        }
+        let settings = Settings::load().debugger;
+        let program = settings
+            .program
+            .as_deref()
+            .context("Set the program to debug with `program` in the [debugger] settings")?;
+        let program = root.join(program);
+        let mut launch = json!({
+            "program": program,
+            "args": settings.args,
+            "cwd": root,
+            "stopOnEntry": settings.stop_on_entry,
+            "stopAtBeginningOfMainSubprogram": settings.stop_on_entry,
+        });
+        for (key, value) in settings.launch {
+            launch[key] = value;
+        }
+
+        let client = DapClient::spawn(&settings.adapter, root)?;
+        info!(target:Self::ID, "Debugging {program:?} with {:?}", settings.adapter);
+        self.print(
+            ConsoleKind::Output,
+            &format!(
+                "Debugging {} with {}",
+                program.display(),
+                settings.adapter.join(" ")
+            ),
+        );
+        let adapter_id = Path::new(&settings.adapter[0])
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        self.begin_session(client, launch, adapter_id)
+    }
+
+    /// Starts a session with a connected adapter by initializing it. The program is launched
+    /// once the adapter responds.
+    fn begin_session(
+        &mut self,
+        client: DapClient,
+        launch: Value,
+        adapter_id: Option<String>,
+    ) -> Result<()> {
+        self.session = Some(Session {
+            client,
+            state: DebugState::Starting,
+            pending: HashMap::new(),
+            launch: Some(launch),
+            initialized: false,
+            thread_id: None,
+            stopping: false,
+        });
+        self.clear_stopped_state();
+        let arguments = json!({
+            "clientID": "clide",
+            "clientName": "clide",
+            "adapterID": adapter_id,
+            "pathFormat": "path",
+            "linesStartAt1": true,
+            "columnsStartAt1": true,
+            "supportsVariableType": true,
+        });
+        self.request("initialize", arguments, Pending::Initialize)
+    }
+
+    fn thread_id(&self) -> Option<i64> {
+        self.session.as_ref().and_then(|session| session.thread_id)
+    }
+
+    /// Continues or steps the stopped thread.
+    fn resume(&mut self, command: &str) -> Result<()> {
+        if self.state() != Some(DebugState::Stopped) {
+            bail!("The program is not stopped");
+        }
+        let thread_id = self.thread_id().context("No thread has stopped")?;
+        self.request(command, json!({ "threadId": thread_id }), Pending::Other)?;
+        self.set_running();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        let Some(session) = self.session.as_mut() else {
+            bail!("No program is being debugged");
+        };
+        if session.stopping {
+            // The adapter didn't end the program after the first request, so it is killed.
+            warn!(target:Self::ID, "Debug adapter did not respond to disconnect, killing it");
+            self.end_session();
+            return Ok(());
+        }
+        session.stopping = true;
+        self.request(
+            "disconnect",
+            json!({ "terminateDebuggee": true }),
+            Pending::Disconnect,
+        )
+    }
+
+    fn set_running(&mut self) {
+        if let Some(session) = self.session.as_mut() {
+            session.state = DebugState::Running;
+        }
+        self.clear_stopped_state();
+    }
+
+    /// Forgets the call stack and variables from the last time the program stopped.
+    fn clear_stopped_state(&mut self) {
+        self.frames.clear();
+        self.selected_frame = 0;
+        self.variables.clear();
+    }
+
+    fn end_session(&mut self) {
+        if self.session.take().is_some() {
+            info!(target:Self::ID, "Debug session ended");
+            self.print(ConsoleKind::Output, "Debug session ended");
+        }
+        self.clear_stopped_state();
+        for watch in &mut self.watches {
+            watch.value = None;
+            watch.error = false;
+        }
+    }
+
+    fn select_frame(&mut self, index: usize) -> Result<()> {
+        let Some(frame) = self.frames.get(index) else {
+            return Ok(());
+        };
+        let frame_id = frame.id;
+        if let Some(path) = frame.path.clone() {
+            self.location = Some((path, frame.row));
+        }
+        self.selected_frame = index;
+        self.variables.clear();
+        self.request(
+            "scopes",
+            json!({ "frameId": frame_id }),
+            Pending::Scopes(frame_id),
+        )?;
+        self.evaluate_watches()
+    }
+
+    fn toggle_variable(&mut self, index: usize) -> Result<()> {
+        let Some(variable) = self.variables.get_mut(index) else {
+            return Ok(());
+        };
+        if variable.expanded {
+            variable.expanded = false;
+            let depth = variable.depth;
+            let children = self.variables[index + 1..]
+                .iter()
+                .take_while(|child| child.depth > depth)
+                .count();
+            self.variables.drain(index + 1..index + 1 + children);
+            return Ok(());
+        }
+        if !variable.has_children() {
+            return Ok(());
+        }
+        let reference = variable.reference;
+        self.request(
+            "variables",
+            json!({ "variablesReference": reference }),
+            Pending::Variables(reference),
+        )
+    }
+
+    fn evaluate_watch(&mut self, index: usize) -> Result<()> {
+        let Some(frame) = self.frames.get(self.selected_frame) else {
+            return Ok(());
+        };
+        let arguments = json!({
+            "expression": self.watches[index].expression,
+            "frameId": frame.id,
+            "context": "watch",
+        });
+        self.request("evaluate", arguments, Pending::Watch(index))
+    }
+
+    fn evaluate_watches(&mut self) -> Result<()> {
+        for index in 0..self.watches.len() {
+            self.evaluate_watch(index)?;
+        }
+        Ok(())
+    }
+
+    /// Handles the messages received from the adapter since the last call.
+    /// Returns the file and row to show in the editor when the program has stopped or another
+    /// frame was selected.
+    pub fn poll(&mut self) -> Option<(PathBuf, usize)> {
+        while let Some(session) = self.session.as_mut() {
+            match session.client.try_recv() {
+                Ok(Some(message)) => {
+                    if let Err(e) = self.handle_message(message) {
+                        error!(target:Self::ID, "Failed to handle debug adapter message: {e:#}");
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    if !session.stopping {
+                        self.print(ConsoleKind::Error, &format!("{e:#}"));
+                    }
+                    self.end_session();
+                }
+            }
+        }
+        self.location.take()
+    }
+
+    fn handle_message(&mut self, message: Value) -> Result<()> {
+        match message["type"].as_str() {
+            Some("response") => self.handle_response(message),
+            Some("event") => self.handle_event(message),
+            Some("request") => {
+                // Reverse requests, such as running the program in a terminal, aren't supported.
+                let session = self.session.as_mut().context("No debug session")?;
+                session
+                    .client
+                    .respond(&message, false, "Not supported by clide")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn handle_response(&mut self, message: Value) -> Result<()> {
+        let Some(seq) = message["request_seq"].as_i64() else {
+            bail!("Response has no request_seq: {message}");
+        };
+        let Some(pending) = self
+            .session
+            .as_mut()
+            .and_then(|session| session.pending.remove(&seq))
+        else {
+            return Ok(());
+        };
+        let body = &message["body"];
+        if message["success"].as_bool() != Some(true) {
+            let error = body["error"]["format"]
+                .as_str()
+                .or(message["message"].as_str())
+                .unwrap_or("Request failed")
+                .to_string();
+            match pending {
+                Pending::Watch(index) => {
+                    if let Some(watch) = self.watches.get_mut(index) {
+                        watch.value = Some(error);
+                        watch.error = true;
+                    }
+                }
+                Pending::Initialize | Pending::Launch => {
+                    self.print(ConsoleKind::Error, &format!("Failed to launch: {error}"));
+                    self.end_session();
+                }
+                Pending::Disconnect => self.end_session(),
+                _ => self.print(ConsoleKind::Error, &error),
+            }
+            return Ok(());
+        }
+        match pending {
+            Pending::Initialize => {
+                let launch = self
+                    .session
+                    .as_mut()
+                    .and_then(|session| session.launch.take())
+                    .unwrap_or_default();
+                self.request("launch", launch, Pending::Launch)?;
+            }
+            Pending::SetBreakpoints(path) => {
+                let unverified = body["breakpoints"]
+                    .as_array()
+                    .map_or(0, |b| b.iter().filter(|b| b["verified"] == false).count());
+                if unverified > 0 {
+                    warn!(target:Self::ID, "{unverified} breakpoints in {path:?} could not be set yet");
+                }
+            }
+            Pending::Threads => {
+                let thread_id = body["threads"][0]["id"].as_i64();
+                if let Some(session) = self.session.as_mut() {
+                    session.thread_id = thread_id;
+                }
+                self.request_stack_trace()?;
+            }
+            Pending::StackTrace => {
+                if self.state() != Some(DebugState::Stopped) {
+                    return Ok(());
+                }
+                self.frames = body["stackFrames"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|frame| StackFrame {
+                        id: frame["id"].as_i64().unwrap_or_default(),
+                        name: frame["name"].as_str().unwrap_or_default().to_string(),
+                        path: frame["source"]["path"].as_str().map(PathBuf::from),
+                        row: (frame["line"].as_u64().unwrap_or(1) as usize).saturating_sub(1),
+                    })
+                    .collect();
+                // Frames without a source, such as in system libraries, can't be shown.
+                let first = self.frames.iter().position(|f| f.path.is_some());
+                self.select_frame(first.unwrap_or_default())?;
+            }
+            Pending::Scopes(frame_id) => {
+                let selected = self.frames.get(self.selected_frame).map(|f| f.id);
+                if selected != Some(frame_id) {
+                    return Ok(());
+                }
+                self.variables = body["scopes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|scope| Variable {
+                        name: scope["name"].as_str().unwrap_or_default().to_string(),
+                        value: String::new(),
+                        type_name: String::new(),
+                        reference: scope["variablesReference"].as_i64().unwrap_or_default(),
+                        depth: 0,
+                        expanded: false,
+                    })
+                    .collect();
+                // Expensive scopes, such as globals, are only fetched when expanded.
+                let first = body["scopes"].as_array().and_then(|scopes| {
+                    scopes
+                        .iter()
+                        .position(|scope| scope["expensive"].as_bool() != Some(true))
+                });
+                if let Some(index) = first {
+                    self.toggle_variable(index)?;
+                }
+            }
+            Pending::Variables(reference) => {
+                let Some(index) = self
+                    .variables
+                    .iter()
+                    .position(|v| v.reference == reference && !v.expanded)
+                else {
+                    return Ok(());
+                };
+                let depth = self.variables[index].depth + 1;
+                let children: Vec<Variable> = body["variables"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|variable| Variable {
+                        name: variable["name"].as_str().unwrap_or_default().to_string(),
+                        value: variable["value"].as_str().unwrap_or_default().to_string(),
+                        type_name: variable["type"].as_str().unwrap_or_default().to_string(),
+                        reference: variable["variablesReference"].as_i64().unwrap_or_default(),
+                        depth,
+                        expanded: false,
+                    })
+                    .collect();
+                self.variables[index].expanded = true;
+                self.variables.splice(index + 1..index + 1, children);
+            }
+            Pending::Watch(index) => {
+                if let Some(watch) = self.watches.get_mut(index) {
+                    watch.value = body["result"].as_str().map(str::to_string);
+                    watch.error = false;
+                }
+            }
+            Pending::Evaluate => {
+                let result = body["result"].as_str().unwrap_or_default().to_string();
+                self.print(ConsoleKind::Result, &result);
+            }
+            Pending::Disconnect => self.end_session(),
+            Pending::Launch | Pending::Other => {}
+        }
+        Ok(())
+    }
+
+    fn request_stack_trace(&mut self) -> Result<()> {
+        let Some(thread_id) = self.thread_id() else {
+            return Ok(());
+        };
+        let arguments = json!({
+            "threadId": thread_id,
+            "startFrame": 0,
+            "levels": Self::MAX_FRAMES,
+        });
+        self.request("stackTrace", arguments, Pending::StackTrace)
+    }
+
+    fn handle_event(&mut self, message: Value) -> Result<()> {
+        let body = &message["body"];
+        match message["event"].as_str().unwrap_or_default() {
+            "initialized" => {
+                if let Some(session) = self.session.as_mut() {
+                    session.initialized = true;
+                }
+                let paths: Vec<PathBuf> = self.breakpoints.keys().cloned().collect();
+                for path in paths {
+                    self.send_breakpoints(path)?;
+                }
+                self.request("configurationDone", json!({}), Pending::Other)?;
+                if let Some(session) = self.session.as_mut()
+                    && session.state == DebugState::Starting
+                {
+                    session.state = DebugState::Running;
+                }
+            }
+            "stopped" => {
+                let reason = body["reason"].as_str().unwrap_or("unknown");
+                info!(target:Self::ID, "Program stopped: {reason}");
+                self.clear_stopped_state();
+                let Some(session) = self.session.as_mut() else {
+                    return Ok(());
+                };
+                session.state = DebugState::Stopped;
+                match body["threadId"].as_i64() {
+                    Some(thread_id) => {
+                        session.thread_id = Some(thread_id);
+                        self.request_stack_trace()?;
+                    }
+                    None => self.request("threads", json!({}), Pending::Threads)?,
+                }
+            }
+            "continued" => self.set_running(),
+            "output" => {
+                let kind = match body["category"].as_str() {
+                    Some("telemetry") => return Ok(()),
+                    Some("stderr") => ConsoleKind::Error,
+                    _ => ConsoleKind::Output,
+                };
+                let output = body["output"].as_str().unwrap_or_default().to_string();
+                self.print(kind, &output);
+            }
+            "exited" => {
+                let code = body["exitCode"].as_i64().unwrap_or_default();
+                self.print(
+                    ConsoleKind::Output,
+                    &format!("Program exited with code {code}"),
+                );
+            }
+            "terminated" => {
+                // The adapter stays running until it is disconnected.
+                if self
+                    .session
+                    .as_ref()
+                    .is_some_and(|session| !session.stopping)
+                {
+                    self.stop()?;
+                }
+            }
+            event => trace!(target:Self::ID, "Ignoring {event} event"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dap::{read_message, write_message};
+    use std::io::{self, BufReader};
+    use std::sync::mpsc::{Receiver, channel};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Starts a debug adapter on another thread that answers every request, and sends the
+    /// `initialized` event after the launch request. Each request it receives is sent to the
+    /// receiver.
+    fn stub_adapter() -> (DapClient, Receiver<Value>) {
+        let (client_reader, mut adapter_writer) = io::pipe().unwrap();
+        let (adapter_reader, client_writer) = io::pipe().unwrap();
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(adapter_reader);
+            let mut seq = 0;
+            let mut send = |mut message: Value| {
+                seq += 1;
+                message["seq"] = json!(seq);
+                write_message(&mut adapter_writer, &message).unwrap();
+            };
+            // Ends when the client is dropped and closes its end of the pipe.
+            while let Ok(Some(request)) = read_message(&mut reader) {
+                send(json!({
+                    "type": "response",
+                    "request_seq": request["seq"],
+                    "command": request["command"],
+                    "success": true,
+                    "body": {},
+                }));
+                if request["command"] == "launch" {
+                    send(json!({ "type": "event", "event": "initialized" }));
+                }
+                if sender.send(request).is_err() {
+                    return;
+                }
+            }
+        });
+        (DapClient::connect(client_reader, client_writer), receiver)
+    }
+
+    #[test]
+    fn start_sends_breakpoints_after_initialized() {
+        let path = PathBuf::from("/project/src/main.rs");
+        let mut debugger = Debugger::default();
+        debugger.toggle_breakpoint(path.clone(), 4).unwrap();
+        let (client, requests) = stub_adapter();
+        debugger
+            .begin_session(client, json!({ "program": "/project/app" }), None)
+            .unwrap();
+        assert_eq!(debugger.state(), Some(DebugState::Starting));
+
+        let mut received: Vec<Value> = Vec::new();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while received
+            .last()
+            .is_none_or(|r| r["command"] != "configurationDone")
+        {
+            assert!(Instant::now() < deadline, "Timed out after {received:?}");
+            debugger.poll();
+            received.extend(requests.try_iter());
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let commands: Vec<&str> = received
+            .iter()
+            .map(|r| r["command"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            commands,
+            [
+                "initialize",
+                "launch",
+                "setBreakpoints",
+                "configurationDone"
+            ]
+        );
+        assert_eq!(received[1]["arguments"]["program"], "/project/app");
+        assert_eq!(
+            received[2]["arguments"],
+            json!({ "source": { "path": path }, "breakpoints": [{ "line": 5 }] })
+        );
+        assert_eq!(debugger.state(), Some(DebugState::Running));
+    }
+}
@@ -0,0 +1,214 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use serde_json::{Value, json};
+use similar::{Algorithm, ChangeTag, TextDiff};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Equal,
+    /// The line is only in the old text.
+    Delete,
+    /// The line is only in the new text.
+    Insert,
+}
+
+impl Change {
+    fn name(&self) -> &'static str {
+        match self {
+            Change::Equal => "equal",
+            Change::Delete => "delete",
+            Change::Insert => "insert",
+        }
+    }
+}
+
+/// A line of the old or new text and how it changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    pub change: Change,
+    /// The row of the line in the old text, unless it was inserted.
+    pub old_row: Option<usize>,
+    /// The row of the line in the new text, unless it was deleted.
+    pub new_row: Option<usize>,
+    /// The text of the line in pieces, with true for the pieces that changed within a line that
+    /// was edited rather than entirely deleted or inserted.
+    pub segments: Vec<(bool, String)>,
+}
+
+impl DiffLine {
+    pub fn is_change(&self) -> bool {
+        self.change != Change::Equal
+    }
+}
+
+/// The lines of two texts, in the order they are shown in an inline diff.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Diff {
+    pub lines: Vec<DiffLine>,
+}
+
+impl Diff {
+    pub fn new(old: &str, new: &str) -> Self {
+        let diff = TextDiff::configure()
+            .algorithm(Algorithm::Patience)
+            .diff_lines(old, new);
+        let mut lines = Vec::new();
+        for op in diff.ops() {
+            for change in diff.iter_inline_changes(op) {
+                let mut segments: Vec<(bool, String)> = change
+                    .iter_strings_lossy()
+                    .map(|(emphasized, text)| (emphasized, text.to_string()))
+                    .collect();
+                // Line endings are part of each line, but aren't shown.
+                if let Some((_, text)) = segments.last_mut() {
+                    let trimmed = text.trim_end_matches(['\n', '\r']).len();
+                    text.truncate(trimmed);
+                }
+                let change_kind = match change.tag() {
+                    ChangeTag::Equal => Change::Equal,
+                    ChangeTag::Delete => Change::Delete,
+                    ChangeTag::Insert => Change::Insert,
+                };
+                lines.push(DiffLine {
+                    change: change_kind,
+                    old_row: change.old_index(),
+                    new_row: change.new_index(),
+                    segments,
+                });
+            }
+        }
+        Self { lines }
+    }
+
+    /// The lines shown on each row of a side by side diff, as indices into `lines` for the old
+    /// and new side. Deleted and inserted lines in the same change are shown beside each other.
+    pub fn side_by_side(&self) -> Vec<[Option<usize>; 2]> {
+        let mut rows = Vec::new();
+        let mut deleted = Vec::new();
+        let mut inserted = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            match line.change {
+                Change::Delete => deleted.push(i),
+                Change::Insert => inserted.push(i),
+                Change::Equal => {
+                    Self::pair(&mut deleted, &mut inserted, &mut rows);
+                    rows.push([Some(i), Some(i)]);
+                }
+            }
+        }
+        Self::pair(&mut deleted, &mut inserted, &mut rows);
+        rows
+    }
+
+    fn pair(
+        deleted: &mut Vec<usize>,
+        inserted: &mut Vec<usize>,
+        rows: &mut Vec<[Option<usize>; 2]>,
+    ) {
+        for i in 0..deleted.len().max(inserted.len()) {
+            rows.push([deleted.get(i).copied(), inserted.get(i).copied()]);
+        }
+        deleted.clear();
+        inserted.clear();
+    }
+
+    /// The number of changed regions in the texts.
+    pub fn hunk_count(&self) -> usize {
+        hunk_starts(self.lines.iter().map(DiffLine::is_change)).len()
+    }
+
+    /// The rows of the diff for the GUI, with the old and new line of each row for a side by
+    /// side diff, or a single line for an inline diff. Each line is given as HTML.
+    pub fn to_json(&self, side_by_side: bool) -> Value {
+        let rows: Vec<[Option<usize>; 2]> = match side_by_side {
+            true => self.side_by_side(),
+            false => (0..self.lines.len()).map(|i| [Some(i), None]).collect(),
+        };
+        let changed = rows
+            .iter()
+            .map(|row| row.iter().flatten().any(|i| self.lines[*i].is_change()));
+        let starts = hunk_starts(changed);
+        let rows: Vec<Value> = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let cells: Vec<Value> = match side_by_side {
+                    true => row
+                        .iter()
+                        .map(|line| match line {
+                            Some(line) => self.line_json(*line),
+                            None => Value::Null,
+                        })
+                        .collect(),
+                    false => row
+                        .iter()
+                        .flatten()
+                        .map(|line| self.line_json(*line))
+                        .collect(),
+                };
+                json!({
+                    "hunk": starts.binary_search(&i).is_ok(),
+                    "cells": cells,
+                })
+            })
+            .collect();
+        Value::Array(rows)
+    }
+
+    fn line_json(&self, index: usize) -> Value {
+        let line = &self.lines[index];
+        let color = match line.change {
+            Change::Delete => "#8f3a3a",
+            _ => "#2f7a45",
+        };
+        let html: String = line
+            .segments
+            .iter()
+            .map(|(emphasized, text)| match emphasized {
+                true => format!(
+                    "<span style=\"background-color:{color}\">{}</span>",
+                    escape_html(text)
+                ),
+                false => escape_html(text),
+            })
+            .collect();
+        json!({
+            "change": line.change.name(),
+            "old": line.old_row.map(|row| row + 1),
+            "new": line.new_row.map(|row| row + 1),
+            "html": html,
+        })
+    }
+}
+
+/// The index of the first row of each run of changed rows.
+pub fn hunk_starts(changed: impl Iterator<Item = bool>) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut previous = false;
+    for (i, changed) in changed.enumerate() {
+        if changed && !previous {
+            starts.push(i);
+        }
+        previous = changed;
+    }
+    starts
+}
+
+/// Escapes text for Qt rich text, keeping its indentation.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            ' ' => escaped.push_str("&nbsp;"),
+            '\t' => escaped.push_str("&nbsp;&nbsp;&nbsp;&nbsp;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
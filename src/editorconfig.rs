@@ -0,0 +1,372 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::glob;
+use crate::settings::Indent;
+use anyhow::{Context, Result, bail};
+use log::{trace, warn};
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const ID: &str = "editorconfig";
+
+const FILE_NAME: &str = ".editorconfig";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tab,
+    Space,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentSize {
+    Columns(usize),
+    /// Indent by the width of one tab.
+    Tab,
+}
+
+/// The characters that end each line of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EndOfLine {
+    #[default]
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl EndOfLine {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EndOfLine::Lf => "\n",
+            EndOfLine::CrLf => "\r\n",
+            EndOfLine::Cr => "\r",
+        }
+    }
+
+    /// The line ending used by the first line of some text.
+    fn detect(text: &str) -> Self {
+        match text.find(['\r', '\n']) {
+            Some(i) if text[i..].starts_with("\r\n") => EndOfLine::CrLf,
+            Some(i) if text[i..].starts_with('\r') => EndOfLine::Cr,
+            _ => EndOfLine::Lf,
+        }
+    }
+}
+
+/// The encoding of a file on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    #[default]
+    Utf8,
+    Utf8Bom,
+    Latin1,
+    Utf16Be,
+    Utf16Le,
+}
+
+impl Charset {
+    /// The charset of a file with a byte order mark, or UTF-8 for files without one.
+    fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            [0xef, 0xbb, 0xbf, ..] => Charset::Utf8Bom,
+            [0xfe, 0xff, ..] => Charset::Utf16Be,
+            [0xff, 0xfe, ..] => Charset::Utf16Le,
+            _ => Charset::Utf8,
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<String> {
+        let text = match self {
+            Charset::Utf8 | Charset::Utf8Bom => String::from_utf8(bytes.to_vec())?,
+            Charset::Latin1 => bytes.iter().map(|b| *b as char).collect(),
+            Charset::Utf16Be | Charset::Utf16Le => {
+                if !bytes.len().is_multiple_of(2) {
+                    bail!("Odd number of bytes in {self:?} text");
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| match self {
+                        Charset::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+                        _ => u16::from_le_bytes([pair[0], pair[1]]),
+                    })
+                    .collect();
+                String::from_utf16(&units)?
+            }
+        };
+        Ok(text
+            .strip_prefix('\u{feff}')
+            .map(String::from)
+            .unwrap_or(text))
+    }
+
+    /// Encodes text, starting with a byte order mark for charsets that use one.
+    /// Characters that can't be written in Latin-1 are replaced with `?`.
+    fn encode(&self, text: &str) -> Vec<u8> {
+        match self {
+            Charset::Utf8 => text.as_bytes().to_vec(),
+            Charset::Utf8Bom => [&[0xef, 0xbb, 0xbf], text.as_bytes()].concat(),
+            Charset::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c).unwrap_or(b'?'))
+                .collect(),
+            Charset::Utf16Be => std::iter::once(0xfeff)
+                .chain(text.encode_utf16())
+                .flat_map(u16::to_be_bytes)
+                .collect(),
+            Charset::Utf16Le => std::iter::once(0xfeff)
+                .chain(text.encode_utf16())
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+        }
+    }
+}
+
+/// The properties from `.editorconfig` files that apply to one file. Properties that are not set
+/// by any `.editorconfig` file are None.
+/// See https://editorconfig.org for the file format.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EditorConfig {
+    pub indent_style: Option<IndentStyle>,
+    pub indent_size: Option<IndentSize>,
+    pub tab_width: Option<usize>,
+    pub end_of_line: Option<EndOfLine>,
+    pub charset: Option<Charset>,
+    pub trim_trailing_whitespace: Option<bool>,
+    pub insert_final_newline: Option<bool>,
+}
+
+impl EditorConfig {
+    /// Reads the `.editorconfig` files in each directory above a file, stopping at the first file
+    /// with `root = true`. Files closer to the path take precedence.
+    pub fn for_file(path: &Path) -> Self {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let mut files: Vec<(PathBuf, ConfigFile)> = Vec::new();
+        for dir in path.ancestors().skip(1) {
+            let config_path = dir.join(FILE_NAME);
+            if !config_path.is_file() {
+                continue;
+            }
+            let file = match fs::read_to_string(&config_path) {
+                Ok(contents) => ConfigFile::parse(&contents),
+                Err(e) => {
+                    warn!(target:ID, "Failed to read {config_path:?}: {e}");
+                    continue;
+                }
+            };
+            trace!(target:ID, "Found {config_path:?}");
+            let root = file.root;
+            files.push((dir.to_path_buf(), file));
+            if root {
+                break;
+            }
+        }
+
+        let mut config = Self::default();
+        for (dir, file) in files.iter().rev() {
+            let Ok(relative) = path.strip_prefix(dir) else {
+                continue;
+            };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            for section in file.sections.iter().filter(|s| s.glob.is_match(&relative)) {
+                for (key, value) in &section.properties {
+                    config.set(key, value);
+                }
+            }
+        }
+        config
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        // Values are case insensitive. Any value that isn't valid for a property, such as `unset`,
+        // leaves it unset.
+        let value = value.to_lowercase();
+        let bool_value = match value.as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        };
+        let size_value = value.parse::<usize>().ok().filter(|size| *size > 0);
+        match key {
+            "indent_style" => {
+                self.indent_style = match value.as_str() {
+                    "tab" => Some(IndentStyle::Tab),
+                    "space" => Some(IndentStyle::Space),
+                    _ => None,
+                }
+            }
+            "indent_size" => {
+                self.indent_size = match value.as_str() {
+                    "tab" => Some(IndentSize::Tab),
+                    _ => size_value.map(IndentSize::Columns),
+                }
+            }
+            "tab_width" => self.tab_width = size_value,
+            "end_of_line" => {
+                self.end_of_line = match value.as_str() {
+                    "lf" => Some(EndOfLine::Lf),
+                    "crlf" => Some(EndOfLine::CrLf),
+                    "cr" => Some(EndOfLine::Cr),
+                    _ => None,
+                }
+            }
+            "charset" => {
+                self.charset = match value.as_str() {
+                    "utf-8" => Some(Charset::Utf8),
+                    "utf-8-bom" => Some(Charset::Utf8Bom),
+                    "latin1" => Some(Charset::Latin1),
+                    "utf-16be" => Some(Charset::Utf16Be),
+                    "utf-16le" => Some(Charset::Utf16Le),
+                    _ => None,
+                }
+            }
+            "trim_trailing_whitespace" => self.trim_trailing_whitespace = bool_value,
+            "insert_final_newline" => self.insert_final_newline = bool_value,
+            _ => trace!(target:ID, "Ignoring unsupported property {key}"),
+        }
+    }
+
+    /// The indentation to use, starting from the indentation from clide's settings.
+    pub fn indent(&self, indent: Indent) -> Indent {
+        let tabs = match self.indent_style {
+            Some(style) => style == IndentStyle::Tab,
+            None => indent.tabs,
+        };
+        let indent_size = match self.indent_size {
+            Some(IndentSize::Columns(size)) => Some(size),
+            Some(IndentSize::Tab) | None => None,
+        };
+        // Tabs are shown as wide as one level of indentation, so tab_width only applies to files
+        // indented with tabs.
+        let width = match tabs {
+            true => self.tab_width.or(indent_size),
+            false => indent_size.or(self.tab_width),
+        };
+        Indent {
+            tabs,
+            width: width.unwrap_or(indent.width),
+        }
+    }
+}
+
+/// A section of an `.editorconfig` file, with the glob matching the paths it applies to.
+struct Section {
+    glob: Regex,
+    properties: Vec<(String, String)>,
+}
+
+struct ConfigFile {
+    root: bool,
+    sections: Vec<Section>,
+}
+
+impl ConfigFile {
+    fn parse(contents: &str) -> Self {
+        let mut root = false;
+        let mut sections: Vec<Section> = Vec::new();
+        // False while reading properties of a section with a glob that failed to compile.
+        let mut in_section = false;
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+            if let Some(pattern) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_section = match Regex::new(&glob::to_regex(pattern)) {
+                    Ok(glob) => {
+                        sections.push(Section {
+                            glob,
+                            properties: Vec::new(),
+                        });
+                        true
+                    }
+                    Err(e) => {
+                        warn!(target:ID, "Ignoring section [{pattern}]: {e}");
+                        false
+                    }
+                };
+                continue;
+            }
+            let Some((key, value)) = line.split_once(['=', ':']) else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim().to_string();
+            match sections.last_mut() {
+                Some(section) if in_section => section.properties.push((key, value)),
+                Some(_) => {}
+                // Properties before the first section apply to the file itself.
+                None => root |= key == "root" && value.eq_ignore_ascii_case("true"),
+            }
+        }
+        Self { root, sections }
+    }
+}
+
+/// How the text of a file is stored on disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileFormat {
+    pub end_of_line: EndOfLine,
+    pub charset: Charset,
+    /// End the last line of the file with a line ending.
+    pub final_newline: bool,
+    pub trim_trailing_whitespace: bool,
+}
+
+impl Default for FileFormat {
+    fn default() -> Self {
+        Self {
+            end_of_line: EndOfLine::default(),
+            charset: Charset::default(),
+            final_newline: true,
+            trim_trailing_whitespace: false,
+        }
+    }
+}
+
+impl FileFormat {
+    /// Decodes the contents of a file into text with lines separated by `\n`, without the final
+    /// line ending. Anything not set by the EditorConfig is detected from the contents, so the
+    /// file is saved the same way it was read.
+    pub fn decode(bytes: &[u8], config: &EditorConfig) -> Result<(Self, String)> {
+        let charset = config.charset.unwrap_or_else(|| Charset::detect(bytes));
+        let text = charset
+            .decode(bytes)
+            .context(format!("Failed to decode file as {charset:?}"))?;
+        let mut format = Self {
+            end_of_line: EndOfLine::detect(&text),
+            charset,
+            final_newline: text.ends_with(['\n', '\r']) || text.is_empty(),
+            trim_trailing_whitespace: false,
+        };
+        format.configure(config);
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let text = text.strip_suffix('\n').unwrap_or(&text).to_string();
+        Ok((format, text))
+    }
+
+    /// Applies the properties set by an EditorConfig, keeping the rest unchanged.
+    pub fn configure(&mut self, config: &EditorConfig) {
+        if let Some(end_of_line) = config.end_of_line {
+            self.end_of_line = end_of_line;
+        }
+        if let Some(charset) = config.charset {
+            self.charset = charset;
+        }
+        if let Some(final_newline) = config.insert_final_newline {
+            self.final_newline = final_newline;
+        }
+        if let Some(trim) = config.trim_trailing_whitespace {
+            self.trim_trailing_whitespace = trim;
+        }
+    }
+
+    /// Encodes text with lines separated by `\n` to be written to the file.
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let mut text = text.replace('\n', self.end_of_line.as_str());
+        if self.final_newline && !text.is_empty() {
+            text.push_str(self.end_of_line.as_str());
+        }
+        self.charset.encode(&text)
+    }
+}
@@ -0,0 +1,107 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use anyhow::{Context, Result, anyhow, bail};
+use log::{error, trace};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const ID: &str = "formatter";
+
+/// How long a formatter may run before it is killed, so a formatter that hangs can't block saving.
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// How often to check whether the formatter has exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Formats text with an external command, such as `rustfmt` or `prettier`.
+/// The text is written to the command's stdin and the formatted text is read from its stdout.
+/// Any `{path}` argument is replaced with the path of the file being formatted, for formatters that
+/// choose their style from the file name.
+pub fn format(command: &[String], path: &Path, text: &str) -> Result<String> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("No formatter command set");
+    };
+    let path = path.to_string_lossy();
+    let args: Vec<String> = args.iter().map(|a| a.replace("{path}", &path)).collect();
+    trace!(target:ID, "Formatting {path} with {program} {args:?}");
+    let mut child = Command::new(program)
+        .args(&args)
+        .current_dir(
+            Path::new(path.as_ref())
+                .parent()
+                .filter(|dir| dir.is_dir())
+                .unwrap_or(Path::new(".")),
+        )
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(format!("Failed to run formatter {program}"))?;
+
+    // Write the text from another thread, so a formatter that starts printing before it has read
+    // all of its input can't fill the stdout pipe and block both processes.
+    let mut stdin = child
+        .stdin
+        .take()
+        .context("Failed to open formatter stdin")?;
+    let input = text.to_string();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let stdout = read_pipe(
+        child
+            .stdout
+            .take()
+            .context("Failed to open formatter stdout")?,
+    );
+    let stderr = read_pipe(
+        child
+            .stderr
+            .take()
+            .context("Failed to open formatter stderr")?,
+    );
+
+    let deadline = Instant::now() + TIMEOUT;
+    let status = loop {
+        match child
+            .try_wait()
+            .context(format!("Failed to wait for formatter {program}"))?
+        {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                // Killing the formatter closes its pipes, which ends the reader threads.
+                let killed = child.kill().and_then(|_| child.wait());
+                error!(target:ID, "Killed formatter {program} after {TIMEOUT:?}: {killed:?}");
+                bail!("Formatter {program} timed out after {TIMEOUT:?}");
+            }
+            None => thread::sleep(POLL_INTERVAL),
+        }
+    };
+    let join_error = || anyhow!("Formatter {program} pipe thread panicked");
+    let written = writer.join().map_err(|_| join_error())?;
+    let stdout = stdout.join().map_err(|_| join_error())?;
+    let stderr = stderr.join().map_err(|_| join_error())?;
+
+    // A formatter that exits early closes stdin, so report its own error before any write error.
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr.unwrap_or_default()).to_string();
+        bail!(
+            "Formatter {program} failed with {status}: {}",
+            stderr.trim()
+        );
+    }
+    written.context(format!("Failed to write to formatter {program}"))?;
+    let stdout = stdout.context(format!("Failed to read output of formatter {program}"))?;
+    String::from_utf8(stdout).context(format!("Formatter {program} printed invalid UTF-8"))
+}
+
+/// Reads everything from a pipe on another thread, so the formatter can't block writing to it
+/// while it is being waited on.
+fn read_pipe(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<std::io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        pipe.read_to_end(&mut bytes).map(|_| bytes)
+    })
+}
@@ -0,0 +1,130 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+/// Translates a glob into a regex matching paths, using the glob syntax of EditorConfig: `*`,
+/// `**`, `?`, `[abc]`, `[!abc]`, `{a,b}` and numeric ranges such as `{1..3}`.
+/// Globs without a `/` match file names in any directory, and other globs match paths from their
+/// start. A leading `/` is ignored.
+pub fn to_regex(pattern: &str) -> String {
+    let (prefix, pattern) = match pattern.strip_prefix('/') {
+        Some(pattern) => ("", pattern),
+        None if pattern.contains('/') => ("", pattern),
+        None => ("(?:.*/)?", pattern),
+    };
+    format!("^{prefix}{}$", translate_glob(pattern))
+}
+
+fn translate_glob(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut regex = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                regex.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                i += 1;
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => match chars[i + 1..].iter().position(|c| *c == ']') {
+                Some(len) => {
+                    let class: String = chars[i + 1..i + 1 + len].iter().collect();
+                    let (negate, class) = match class.strip_prefix('!') {
+                        Some(class) => ("^", class.to_string()),
+                        None => ("", class),
+                    };
+                    let class: String = class
+                        .chars()
+                        .map(|c| match c {
+                            '-' => c.to_string(),
+                            c => regex::escape(&c.to_string()),
+                        })
+                        .collect();
+                    regex.push_str(&format!("[{negate}{class}]"));
+                    i += len + 1;
+                }
+                None => regex.push_str("\\["),
+            },
+            '{' => match closing_brace(&chars[i..]) {
+                Some(len) => {
+                    let inner: String = chars[i + 1..i + len].iter().collect();
+                    regex.push_str(&translate_braces(&inner));
+                    i += len;
+                }
+                None => regex.push_str("\\{"),
+            },
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    regex
+}
+
+/// The index of the brace closing the brace at the start of `chars`.
+fn closing_brace(chars: &[char]) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, c) in chars.iter().enumerate() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Translates the contents of `{...}`, which is either a numeric range such as `1..3` or a list
+/// of alternatives such as `js,ts`. Braces containing neither are matched literally.
+fn translate_braces(inner: &str) -> String {
+    if let Some((start, end)) = inner.split_once("..")
+        && let (Ok(start), Ok(end)) = (start.parse::<i64>(), end.parse::<i64>())
+    {
+        let (start, end) = (start.min(end), start.max(end));
+        // Large ranges match any number, rather than listing every value.
+        if end - start > 1000 {
+            return "-?[0-9]+".to_string();
+        }
+        let numbers: Vec<String> = (start..=end).map(|n| n.to_string()).collect();
+        return format!("(?:{})", numbers.join("|"));
+    }
+    let mut alternatives = Vec::new();
+    let mut depth = 0;
+    let mut escaped = false;
+    let mut current = String::new();
+    for c in inner.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    alternatives.push(current);
+    match alternatives.len() {
+        1 => format!("\\{{{}\\}}", translate_glob(inner)),
+        _ => {
+            let alternatives: Vec<String> =
+                alternatives.iter().map(|a| translate_glob(a)).collect();
+            format!("(?:{})", alternatives.join("|"))
+        }
+    }
+}
@@ -9,6 +9,7 @@ use log::trace;
 
 pub mod colors;
 pub mod filesystem;
+pub mod recent;
 
 pub fn run(app_context: AppContext) -> Result<()> {
     trace!(target:"gui::run()", "Starting the GUI editor at {:?}", app_context.path);
@@ -2,6 +2,11 @@
 //
 // SPDX-License-Identifier: GNU General Public License v3.0 or later
 
+use crate::diff::Diff;
+use crate::highlight;
+use crate::image_file::{self, ImageInfo};
+use crate::markdown;
+use crate::settings::Settings;
 use cxx_qt_lib::{QModelIndex, QString};
 use devicons::FileIcon;
 use dirs;
@@ -9,9 +14,7 @@ use log::warn;
 use std::fs;
 use std::path::Path;
 use syntect::easy::HighlightLines;
-use syntect::highlighting::ThemeSet;
 use syntect::html::{IncludeBackground, append_highlighted_html_for_styled_line};
-use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
 
 #[cxx_qt::bridge]
@@ -48,6 +51,27 @@ pub mod qobject {
         #[cxx_name = "readFile"]
         fn read_file(self: &FileSystem, path: &QString) -> QString;
 
+        #[qinvokable]
+        #[cxx_name = "isImage"]
+        fn is_image(self: &FileSystem, path: &QString) -> bool;
+
+        #[qinvokable]
+        #[cxx_name = "imageInfo"]
+        fn image_info(self: &FileSystem, path: &QString) -> QString;
+
+        #[qinvokable]
+        #[cxx_name = "renderMarkdown"]
+        fn render_markdown(self: &FileSystem, text: &QString) -> QString;
+
+        #[qinvokable]
+        #[cxx_name = "diffFiles"]
+        fn diff_files(
+            self: &FileSystem,
+            old_path: &QString,
+            new_path: &QString,
+            side_by_side: bool,
+        ) -> QString;
+
         #[qinvokable]
         #[cxx_name = "setDirectory"]
         fn set_directory(self: Pin<&mut FileSystem>, path: &QString) -> QModelIndex;
@@ -82,25 +106,26 @@ impl qobject::FileSystem {
             return QString::default();
         }
         let path_str = path.to_string();
+        // Images are shown by the image view in the editor instead.
+        if image_file::is_image(Path::new(&path_str)) {
+            return QString::default();
+        }
         if let Ok(lines) = fs::read_to_string(path_str.as_str()) {
-            let ss = SyntaxSet::load_defaults_nonewlines();
-            let ts = ThemeSet::load_defaults();
-            let theme = &ts.themes["base16-ocean.dark"];
-            let lang = ss
-                .find_syntax_by_extension(
-                    Path::new(path_str.as_str())
-                        .extension()
-                        .map(|s| s.to_str())
-                        .unwrap_or_else(|| Some("md"))
-                        .expect("Failed to get file extension"),
-                )
-                .unwrap_or_else(|| ss.find_syntax_plain_text());
+            let ss = highlight::syntax_set();
+            let theme = highlight::theme("base16-ocean.dark").expect("Failed to find default theme");
+            let file_types = Settings::load().file_types;
+            let lang = highlight::detect_syntax(Path::new(path_str.as_str()), &lines, &file_types);
+            #[cfg(feature = "tree-sitter")]
+            if let Some(mut tree) = crate::syntax_tree::SyntaxTree::new(&lang.name) {
+                tree.update(&lines);
+                return QString::from(tree.html());
+            }
             let mut highlighter = HighlightLines::new(lang, theme);
             // If you care about the background, see `start_highlighted_html_snippet(theme);`.
             let mut output = String::from("<pre>\n");
             for line in LinesWithEndings::from(lines.as_str()) {
                 let regions = highlighter
-                    .highlight_line(line, &ss)
+                    .highlight_line(line, ss)
                     .expect("Failed to highlight");
 
                 append_highlighted_html_for_styled_line(
@@ -118,6 +143,39 @@ impl qobject::FileSystem {
         }
     }
 
+    fn is_image(&self, path: &QString) -> bool {
+        image_file::is_image(Path::new(&path.to_string()))
+    }
+
+    /// The format, dimensions and file size of an image, shown below it in the editor.
+    fn image_info(&self, path: &QString) -> QString {
+        match ImageInfo::read(Path::new(&path.to_string())) {
+            Ok(info) => QString::from(info.summary()),
+            Err(e) => {
+                warn!(target:"FileSystem", "Failed to read image {path:?}: {e:#}");
+                QString::default()
+            }
+        }
+    }
+
+    fn render_markdown(&self, text: &QString) -> QString {
+        QString::from(markdown::to_html(&text.to_string()))
+    }
+
+    /// The rows of a diff between two files as JSON, for the diff view in the editor.
+    fn diff_files(&self, old_path: &QString, new_path: &QString, side_by_side: bool) -> QString {
+        let read = |path: &QString| {
+            fs::read_to_string(path.to_string())
+                .inspect_err(|e| warn!(target:"FileSystem", "Failed to read {path:?}: {e}"))
+        };
+        match (read(old_path), read(new_path)) {
+            (Ok(old), Ok(new)) => {
+                QString::from(Diff::new(&old, &new).to_json(side_by_side).to_string())
+            }
+            _ => QString::default(),
+        }
+    }
+
     // There will never be more than one column.
     fn column_count(&self, _index: &QModelIndex) -> i32 {
         1
@@ -0,0 +1,89 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::recent::Recent;
+use cxx_qt_lib::{QList, QString, QStringList};
+use log::error;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+#[cxx_qt::bridge]
+pub mod qobject {
+    unsafe extern "C++" {
+        include!("cxx-qt-lib/qstring.h");
+        type QString = cxx_qt_lib::QString;
+        include!("cxx-qt-lib/qstringlist.h");
+        type QStringList = cxx_qt_lib::QStringList;
+    }
+
+    unsafe extern "RustQt" {
+        #[qobject]
+        #[qml_element]
+        #[qml_singleton]
+        #[qproperty(QStringList, projects)]
+        #[qproperty(QStringList, files)]
+        type RecentFiles = super::RecentFilesImpl;
+
+        #[qinvokable]
+        #[cxx_name = "addProject"]
+        fn add_project(self: Pin<&mut RecentFiles>, path: &QString);
+
+        #[qinvokable]
+        #[cxx_name = "addFile"]
+        fn add_file(self: Pin<&mut RecentFiles>, path: &QString);
+    }
+}
+
+pub struct RecentFilesImpl {
+    projects: QStringList,
+    files: QStringList,
+    recent: Recent,
+}
+
+impl Default for RecentFilesImpl {
+    fn default() -> Self {
+        let recent = Recent::load();
+        Self {
+            projects: to_string_list(&recent.projects),
+            files: to_string_list(&recent.files),
+            recent,
+        }
+    }
+}
+
+fn to_string_list(paths: &[PathBuf]) -> QStringList {
+    let mut list = QList::<QString>::default();
+    for path in paths {
+        list.append(QString::from(path.to_string_lossy().to_string()));
+    }
+    QStringList::from(&list)
+}
+
+impl qobject::RecentFiles {
+    fn add_project(mut self: Pin<&mut Self>, path: &QString) {
+        let path = path.to_string();
+        self.as_mut()
+            .rust_mut()
+            .recent
+            .add_project(Path::new(&path));
+        self.save();
+    }
+
+    fn add_file(mut self: Pin<&mut Self>, path: &QString) {
+        let path = path.to_string();
+        self.as_mut().rust_mut().recent.add_file(Path::new(&path));
+        self.save();
+    }
+
+    /// Persists the recent lists and notifies QML of the new entries.
+    fn save(mut self: Pin<&mut Self>) {
+        if let Err(e) = self.recent.save() {
+            error!(target:Recent::ID, "Failed to save recent projects and files: {e:#}");
+        }
+        let projects = to_string_list(&self.recent.projects);
+        let files = to_string_list(&self.recent.files);
+        self.as_mut().set_projects(projects);
+        self.as_mut().set_files(files);
+    }
+}
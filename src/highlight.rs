@@ -0,0 +1,187 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::{config, glob};
+use log::{trace, warn};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+
+pub const ID: &str = "highlight";
+
+/// The directory in the clide configuration directory for `.sublime-syntax` files.
+const SYNTAXES_DIR: &str = "syntaxes";
+/// The directory in the clide configuration directory for `.tmTheme` files.
+const THEMES_DIR: &str = "themes";
+
+/// Syntax definitions shared by both front ends, loaded the first time they are used.
+/// They expect each line to include its line ending, as given by `LinesWithEndings`.
+static SYNTAX_SET: LazyLock<Arc<SyntaxSet>> = LazyLock::new(|| Arc::new(load_syntax_set()));
+
+static THEME_SET: LazyLock<Arc<ThemeSet>> = LazyLock::new(|| Arc::new(load_theme_set()));
+
+/// A vim modeline setting the file type, such as `# vim: set ft=python:`.
+static VIM_MODELINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|\s)(?:vi|vim|ex):.*?\b(?:ft|filetype|syntax)=([\w+#.-]+)")
+        .expect("Failed to compile vim modeline regex")
+});
+
+/// An emacs modeline, such as `-*- mode: python -*-` or `-*- python -*-`.
+static EMACS_MODELINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"-\*-\s*(.*?)\s*-\*-").expect("Failed to compile emacs modeline regex")
+});
+
+/// The number of lines at the start and end of a file that are checked for modelines.
+const MODELINE_LINES: usize = 5;
+
+/// The default syntax definitions, along with any from `syntaxes` in the configuration directory.
+pub fn syntax_set() -> &'static SyntaxSet {
+    &SYNTAX_SET
+}
+
+/// The default themes, along with any from `themes` in the configuration directory.
+pub fn theme_set() -> &'static ThemeSet {
+    &THEME_SET
+}
+
+/// A shared reference to the syntax set, for highlighters that keep their own reference.
+pub fn shared_syntax_set() -> Arc<SyntaxSet> {
+    SYNTAX_SET.clone()
+}
+
+/// A shared reference to the theme set, for highlighters that keep their own reference.
+pub fn shared_theme_set() -> Arc<ThemeSet> {
+    THEME_SET.clone()
+}
+
+/// A theme by name, such as `base16-ocean.dark` or the file name of a user theme without its
+/// `.tmTheme` extension.
+pub fn theme(name: &str) -> Option<&'static Theme> {
+    theme_set().themes.get(name)
+}
+
+/// A syntax by its name, such as `Rust`, or by a lowercase name or file extension such as
+/// `python` or `sh`.
+pub fn find_syntax(name: &str) -> Option<&'static SyntaxReference> {
+    let syntax_set = syntax_set();
+    syntax_set
+        .find_syntax_by_name(name)
+        .or_else(|| syntax_set.find_syntax_by_token(name))
+}
+
+/// Finds the syntax for a file, trying in order:
+/// 1. The most specific glob in `file_types` matching the path, from the user's settings.
+/// 2. A vim or emacs modeline near the start or end of the contents.
+/// 3. The full file name, such as `Makefile` or `.bashrc`.
+/// 4. The file extension.
+/// 5. The first line of the contents, such as a shebang.
+///
+/// Files that match none of these are plain text.
+pub fn detect_syntax(
+    path: &Path,
+    contents: &str,
+    file_types: &HashMap<String, String>,
+) -> &'static SyntaxReference {
+    let syntax_set = syntax_set();
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let extension = path.extension().and_then(|e| e.to_str());
+    let first_line = contents.lines().next().unwrap_or_default();
+    file_type(path, file_types)
+        .or_else(|| modeline(contents).and_then(|name| find_syntax(&name)))
+        .or_else(|| syntax_set.find_syntax_by_extension(file_name))
+        .or_else(|| extension.and_then(|e| syntax_set.find_syntax_by_extension(e)))
+        .or_else(|| syntax_set.find_syntax_by_first_line(first_line))
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+}
+
+fn file_type(
+    path: &Path,
+    file_types: &HashMap<String, String>,
+) -> Option<&'static SyntaxReference> {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut matches: Vec<(&String, &String)> = file_types
+        .iter()
+        .filter(|(pattern, _)| match Regex::new(&glob::to_regex(pattern)) {
+            Ok(regex) => regex.is_match(&path),
+            Err(e) => {
+                warn!(target:ID, "Ignoring file type pattern {pattern}: {e}");
+                false
+            }
+        })
+        .collect();
+    // Longer patterns are more specific, such as `Dockerfile.*` over `*.*`.
+    matches.sort_by_key(|(pattern, _)| std::cmp::Reverse(pattern.len()));
+    matches.into_iter().find_map(|(pattern, name)| {
+        let syntax = find_syntax(name);
+        if syntax.is_none() {
+            warn!(target:ID, "Unknown language {name} for file type pattern {pattern}");
+        }
+        syntax
+    })
+}
+
+/// The language set by a modeline in the first or last lines of a file.
+fn modeline(contents: &str) -> Option<String> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let tail = lines
+        .len()
+        .saturating_sub(MODELINE_LINES)
+        .max(MODELINE_LINES);
+    lines
+        .iter()
+        .take(MODELINE_LINES)
+        .chain(lines.iter().skip(tail))
+        .find_map(|line| {
+            if let Some(captures) = VIM_MODELINE.captures(line) {
+                return Some(captures[1].to_string());
+            }
+            let variables = EMACS_MODELINE.captures(line)?.get(1)?.as_str();
+            match variables.contains(':') {
+                true => variables.split(';').find_map(|variable| {
+                    let (name, value) = variable.split_once(':')?;
+                    name.trim()
+                        .eq_ignore_ascii_case("mode")
+                        .then(|| value.trim().to_string())
+                }),
+                false => Some(variables.to_string()),
+            }
+        })
+}
+
+fn user_dir(name: &str) -> Option<PathBuf> {
+    let dir = config::config_dir().ok()?.join(name);
+    dir.is_dir().then_some(dir)
+}
+
+fn load_syntax_set() -> SyntaxSet {
+    trace!(target:ID, "Loading syntax definitions");
+    let defaults = SyntaxSet::load_defaults_newlines();
+    let Some(dir) = user_dir(SYNTAXES_DIR) else {
+        return defaults;
+    };
+    let mut builder = defaults.into_builder();
+    match builder.add_from_folder(&dir, true) {
+        Ok(_) => trace!(target:ID, "Loaded syntax definitions from {dir:?}"),
+        Err(e) => warn!(target:ID, "Failed to load syntax definitions from {dir:?}: {e}"),
+    }
+    builder.build()
+}
+
+fn load_theme_set() -> ThemeSet {
+    trace!(target:ID, "Loading themes");
+    let mut themes = ThemeSet::load_defaults();
+    if let Some(dir) = user_dir(THEMES_DIR) {
+        match themes.add_from_folder(&dir) {
+            Ok(_) => trace!(target:ID, "Loaded themes from {dir:?}"),
+            Err(e) => warn!(target:ID, "Failed to load themes from {dir:?}: {e}"),
+        }
+    }
+    themes
+}
@@ -0,0 +1,126 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use anyhow::{Context, Result, anyhow};
+use image::{ImageFormat, ImageReader, RgbaImage};
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg;
+use std::fs;
+use std::path::Path;
+
+/// The size of an image file, shown with its preview.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageInfo {
+    /// The name of the image format, such as PNG or SVG.
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    /// The size of the file in bytes.
+    pub size: u64,
+}
+
+impl ImageInfo {
+    pub fn read(path: &Path) -> Result<Self> {
+        let size = fs::metadata(path)
+            .with_context(|| format!("Failed to read metadata for {path:?}"))?
+            .len();
+        if is_svg(path) {
+            let tree = parse_svg(path)?;
+            return Ok(Self {
+                format: "SVG".to_string(),
+                width: tree.size().width().round() as u32,
+                height: tree.size().height().round() as u32,
+                size,
+            });
+        }
+        let reader = ImageReader::open(path)?.with_guessed_format()?;
+        let format = reader
+            .format()
+            .ok_or_else(|| anyhow!("Unknown image format for {path:?}"))?;
+        let (width, height) = reader.into_dimensions()?;
+        Ok(Self {
+            format: format.extensions_str()[0].to_uppercase(),
+            width,
+            height,
+            size,
+        })
+    }
+
+    /// The format, dimensions and file size, such as `PNG 640×480 12.5 KiB`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {}×{} {}",
+            self.format,
+            self.width,
+            self.height,
+            file_size(self.size)
+        )
+    }
+}
+
+/// True for files that are shown as an image instead of opened as text.
+pub fn is_image(path: &Path) -> bool {
+    is_svg(path) || ImageFormat::from_path(path).is_ok_and(|format| format.reading_enabled())
+}
+
+fn is_svg(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg") || ext.eq_ignore_ascii_case("svgz"))
+}
+
+fn parse_svg(path: &Path) -> Result<usvg::Tree> {
+    let data = fs::read(path).with_context(|| format!("Failed to read {path:?}"))?;
+    let mut options = usvg::Options {
+        resources_dir: path.parent().map(Path::to_path_buf),
+        ..usvg::Options::default()
+    };
+    options.fontdb_mut().load_system_fonts();
+    usvg::Tree::from_data(&data, &options).with_context(|| format!("Failed to parse {path:?}"))
+}
+
+/// Reads the pixels of an image. SVG images are drawn with their longest side `svg_size` pixels
+/// long, so they stay sharp when drawn larger than their own size.
+pub fn decode(path: &Path, svg_size: u32) -> Result<RgbaImage> {
+    if !is_svg(path) {
+        let image = ImageReader::open(path)?.with_guessed_format()?.decode()?;
+        return Ok(image.to_rgba8());
+    }
+    let tree = parse_svg(path)?;
+    let size = tree.size();
+    let scale = svg_size as f32 / size.width().max(size.height()).max(1.0);
+    let width = (size.width() * scale).ceil().max(1.0) as u32;
+    let height = (size.height() * scale).ceil().max(1.0) as u32;
+    let mut pixmap =
+        Pixmap::new(width, height).ok_or_else(|| anyhow!("Invalid SVG size {width}×{height}"))?;
+    resvg::render(
+        &tree,
+        Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    RgbaImage::from_raw(width, height, pixels).ok_or_else(|| anyhow!("Failed to draw {path:?}"))
+}
+
+/// A file size in the largest unit that keeps it above 1, such as `12.5 KiB`.
+fn file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
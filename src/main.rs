@@ -2,12 +2,30 @@
 //
 // SPDX-License-Identifier: GNU General Public License v3.0 or later
 
+use crate::recent::Recent;
 use anyhow::{Context, Result, anyhow};
 use clap::Parser;
-use log::{info, trace};
+use log::{info, trace, warn};
 use std::process::{Command, Stdio};
 
+pub mod config;
+pub mod dap;
+pub mod debugger;
+pub mod diff;
+pub mod editorconfig;
+pub mod formatter;
+pub mod glob;
 pub mod gui;
+pub mod highlight;
+pub mod image_file;
+pub mod markdown;
+pub mod merge;
+pub mod recent;
+pub mod settings;
+pub mod symbol_index;
+pub mod symbols;
+#[cfg(feature = "tree-sitter")]
+pub mod syntax_tree;
 pub mod tui;
 /// Extendable command-line driven development environment written in Rust using the Qt UI framework.
 /// If no flags are provided, the GUI editor is launched in a separate process.
@@ -19,6 +37,10 @@ struct Cli {
     #[arg(value_parser = clap::value_parser!(std::path::PathBuf))]
     pub path: Option<std::path::PathBuf>,
 
+    /// Reopen the most recently opened project.
+    #[arg(value_name = "last", short, long, conflicts_with = "path")]
+    pub last: bool,
+
     /// Run clide in headless mode.
     #[arg(value_name = "tui", short, long)]
     pub tui: bool,
@@ -50,14 +72,25 @@ pub struct AppContext {
 
 impl AppContext {
     fn new(cli: Cli) -> Result<Self> {
+        let mut recent = Recent::load();
         let path = match &cli.path {
             // If the CLI was provided a directory, convert it to absolute.
             Some(path) => std::path::absolute(path)?,
+            // Reopen the last project if requested.
+            None if cli.last => recent
+                .last_project()
+                .cloned()
+                .context("No recent project found to reopen")?,
             // If no path was provided, use the current directory.
             None => std::env::current_dir().context("Failed to obtain current directory")?,
         };
         info!(target:"main()", "Root path detected: {path:?}");
 
+        recent.add_project(&path);
+        if let Err(e) = recent.save() {
+            warn!(target:"main()", "Failed to record recent project: {e:#}");
+        }
+
         Ok(Self {
             path,
             run_mode: cli.run_mode()?,
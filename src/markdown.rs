@@ -0,0 +1,39 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use pulldown_cmark::{Options, Parser, html};
+
+/// The markdown extensions rendered by the TUI and GUI previews.
+pub fn options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_FOOTNOTES
+}
+
+/// Renders markdown as HTML for the GUI preview.
+pub fn to_html(text: &str) -> String {
+    let mut output = String::new();
+    html::push_html(&mut output, Parser::new_ext(text, options()));
+    output
+}
+
+/// Finds the line of the text containing each byte offset.
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        Self {
+            line_starts: std::iter::once(0)
+                .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+        }
+    }
+
+    pub fn row(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+}
@@ -0,0 +1,121 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use std::ops::Range;
+
+/// The side of a merge conflict kept when it is resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// The lines of the branch being merged into, above `=======`.
+    Ours,
+    /// The lines of the branch being merged, below `=======`.
+    Theirs,
+    /// Our lines followed by their lines.
+    Both,
+}
+
+/// The rows of the markers of a merge conflict left in a file by git.
+/// Conflicts written with `merge.conflictStyle = diff3` also have a marker starting the lines of
+/// the common ancestor, which are shown but never kept when the conflict is resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict {
+    /// The `<<<<<<<` marker.
+    pub start: usize,
+    /// The `|||||||` marker, if the common ancestor is included.
+    pub base: Option<usize>,
+    /// The `=======` marker.
+    pub separator: usize,
+    /// The `>>>>>>>` marker.
+    pub end: usize,
+}
+
+impl Conflict {
+    pub const START: &str = "<<<<<<<";
+    pub const BASE: &str = "|||||||";
+    pub const SEPARATOR: &str = "=======";
+    pub const END: &str = ">>>>>>>";
+
+    /// All rows of the conflict, including the markers.
+    pub fn rows(&self) -> Range<usize> {
+        self.start..self.end + 1
+    }
+
+    pub fn ours(&self) -> Range<usize> {
+        self.start + 1..self.base.unwrap_or(self.separator)
+    }
+
+    pub fn base_rows(&self) -> Option<Range<usize>> {
+        self.base.map(|base| base + 1..self.separator)
+    }
+
+    pub fn theirs(&self) -> Range<usize> {
+        self.separator + 1..self.end
+    }
+
+    /// True for the rows of the markers themselves.
+    pub fn is_marker(&self, row: usize) -> bool {
+        row == self.start || Some(row) == self.base || row == self.separator || row == self.end
+    }
+
+    /// The text after a marker, such as the branch name after `<<<<<<<`.
+    pub fn label(lines: &[String], row: usize) -> &str {
+        lines
+            .get(row)
+            .and_then(|line| line.get(Self::START.len()..))
+            .map_or("", str::trim)
+    }
+
+    /// The lines that replace the conflict when it is resolved.
+    pub fn resolved(&self, lines: &[String], resolution: Resolution) -> Vec<String> {
+        match resolution {
+            Resolution::Ours => lines[self.ours()].to_vec(),
+            Resolution::Theirs => lines[self.theirs()].to_vec(),
+            Resolution::Both => [&lines[self.ours()], &lines[self.theirs()]].concat(),
+        }
+    }
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    // Markers are followed by a space and a label, except for the separator.
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '\r']))
+}
+
+/// Finds the merge conflicts in a file, in order. Markers that don't form a complete conflict are
+/// ignored.
+pub fn conflicts(lines: &[String]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    let mut start = None;
+    let mut base = None;
+    let mut separator = None;
+    for (row, line) in lines.iter().enumerate() {
+        if is_marker(line, Conflict::START) {
+            // A start marker inside another conflict begins a new conflict.
+            (start, base, separator) = (Some(row), None, None);
+        } else if is_marker(line, Conflict::BASE) && start.is_some() && separator.is_none() {
+            base = Some(row);
+        } else if line.trim_end() == Conflict::SEPARATOR && start.is_some() {
+            separator = Some(row);
+        } else if is_marker(line, Conflict::END) {
+            if let (Some(start), Some(separator)) = (start, separator) {
+                conflicts.push(Conflict {
+                    start,
+                    base,
+                    separator,
+                    end: row,
+                });
+            }
+            (start, base, separator) = (None, None, None);
+        }
+    }
+    conflicts
+}
+
+/// Resolves a conflict, returning the lines of the file with the conflict replaced.
+pub fn resolve(lines: &[String], conflict: &Conflict, resolution: Resolution) -> Vec<String> {
+    let mut resolved = lines[..conflict.start].to_vec();
+    resolved.extend(conflict.resolved(lines, resolution));
+    resolved.extend_from_slice(&lines[conflict.end + 1..]);
+    resolved
+}
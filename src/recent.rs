@@ -0,0 +1,57 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::config;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Recently opened projects and files, shared between the TUI and GUI.
+/// The most recent entry is always first in each list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Recent {
+    #[serde(default)]
+    pub projects: Vec<PathBuf>,
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+}
+
+impl Recent {
+    pub const ID: &str = "Recent";
+
+    const FILE_NAME: &str = "recent.toml";
+
+    /// The maximum number of entries kept in each list.
+    const MAX_ENTRIES: usize = 10;
+
+    /// Loads the recent lists, dropping any entries that no longer exist on disk.
+    pub fn load() -> Self {
+        let mut recent: Self = config::load(Self::FILE_NAME);
+        recent.projects.retain(|p| p.is_dir());
+        recent.files.retain(|p| p.is_file());
+        recent
+    }
+
+    pub fn save(&self) -> Result<()> {
+        config::save(Self::FILE_NAME, self)
+    }
+
+    pub fn add_project(&mut self, path: &Path) {
+        Self::push_front(&mut self.projects, path);
+    }
+
+    pub fn add_file(&mut self, path: &Path) {
+        Self::push_front(&mut self.files, path);
+    }
+
+    pub fn last_project(&self) -> Option<&PathBuf> {
+        self.projects.first()
+    }
+
+    fn push_front(list: &mut Vec<PathBuf>, path: &Path) {
+        list.retain(|p| p != path);
+        list.insert(0, path.to_path_buf());
+        list.truncate(Self::MAX_ENTRIES);
+    }
+}
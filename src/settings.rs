@@ -0,0 +1,164 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::config;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The key bindings used for editing text in the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Keymap {
+    /// Modal editing using vim emulation.
+    #[default]
+    Vim,
+    /// Arrow keys, shift-selection and the usual CTRL shortcuts for undo, cut, copy and paste.
+    Standard,
+}
+
+/// How lines are indented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Indent {
+    /// Indent with tab characters instead of spaces.
+    pub tabs: bool,
+    /// The number of columns in one level of indentation, which is also the width tabs are shown.
+    pub width: usize,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Self {
+            tabs: false,
+            width: 4,
+        }
+    }
+}
+
+impl Indent {
+    /// The text inserted to indent from `col` to the next level of indentation.
+    pub fn unit(&self, col: usize) -> String {
+        match self.tabs {
+            true => "\t".to_string(),
+            false => " ".repeat(self.width - col % self.width.max(1)),
+        }
+    }
+}
+
+/// Settings for files of one language, from a `[languages.<name>]` table in `settings.toml`.
+/// The name is the lowercase name of the language shown in the editor title, such as `rust` or
+/// `makefile`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageSettings {
+    pub indent: Option<Indent>,
+    /// A command that formats files when they are saved, such as `["rustfmt", "--edition", "2024"]`.
+    /// It is given the file contents on stdin and prints the formatted contents to stdout.
+    /// An argument of `{path}` is replaced with the path of the file.
+    pub formatter: Option<Vec<String>>,
+}
+
+/// How programs are debugged, from the `[debugger]` table in `settings.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebuggerSettings {
+    /// The command that starts a debug adapter, such as `["lldb-dap"]` or
+    /// `["gdb", "--interpreter=dap"]`. It is run from the project root.
+    pub adapter: Vec<String>,
+    /// The program to debug, relative to the project root.
+    pub program: Option<String>,
+    pub args: Vec<String>,
+    /// Stop at the start of the program instead of running to the first breakpoint.
+    pub stop_on_entry: bool,
+    /// Other arguments of the launch request, for settings specific to an adapter.
+    pub launch: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Default for DebuggerSettings {
+    fn default() -> Self {
+        Self {
+            adapter: vec!["lldb-dap".to_string()],
+            program: None,
+            args: Vec::new(),
+            stop_on_entry: false,
+            launch: serde_json::Map::new(),
+        }
+    }
+}
+
+/// User settings for clide, read from `settings.toml` in the clide configuration directory.
+/// Any setting missing from the file uses its default value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Keep the undo history of each file on disk so it is available in later sessions.
+    pub persist_undo: bool,
+    /// The key bindings for editing text, either `"vim"` or `"standard"`.
+    pub keymap: Keymap,
+    /// Indent new lines to match the line above.
+    pub auto_indent: bool,
+    /// Insert the closing bracket or quote when an opening one is typed.
+    pub auto_close: bool,
+    /// The indentation for languages without their own setting.
+    pub indent: Indent,
+    pub languages: HashMap<String, LanguageSettings>,
+    /// The language for files matching a glob, such as `"*.tpl" = "html"`, from the `[file_types]`
+    /// table. These take precedence over languages found from the file name or contents.
+    pub file_types: HashMap<String, String>,
+    pub debugger: DebuggerSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            persist_undo: false,
+            keymap: Keymap::default(),
+            auto_indent: true,
+            auto_close: true,
+            indent: Indent::default(),
+            languages: HashMap::new(),
+            file_types: HashMap::new(),
+            debugger: DebuggerSettings::default(),
+        }
+    }
+}
+
+impl Settings {
+    const FILE_NAME: &str = "settings.toml";
+
+    pub fn load() -> Self {
+        config::load(Self::FILE_NAME)
+    }
+
+    /// The settings for a language, by the name shown in the editor title.
+    pub fn language(&self, name: &str) -> Option<&LanguageSettings> {
+        self.languages.get(&name.to_lowercase())
+    }
+
+    /// The indentation for a language. Without a setting for the language, languages that
+    /// conventionally indent differently from the default use their own conventions.
+    pub fn indent_for(&self, language: &str) -> Indent {
+        if let Some(indent) = self.language(language).and_then(|l| l.indent) {
+            return indent;
+        }
+        match language.to_lowercase().as_str() {
+            "makefile" | "go" => Indent {
+                tabs: true,
+                width: self.indent.width,
+            },
+            "yaml" | "json" | "ruby" | "html" | "css" | "javascript" | "typescript" => Indent {
+                tabs: false,
+                width: 2,
+            },
+            _ => self.indent,
+        }
+    }
+
+    /// The command that formats files of a language when they are saved, if one is set.
+    pub fn formatter_for(&self, language: &str) -> Option<&[String]> {
+        self.language(language)
+            .and_then(|l| l.formatter.as_deref())
+            .filter(|command| !command.is_empty())
+    }
+}
@@ -0,0 +1,212 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::highlight;
+use crate::settings::Settings;
+use crate::symbols::{self, Symbol};
+use log::{info, trace};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::thread;
+
+/// The symbols defined in each file under a project root.
+///
+/// The files are read on a background thread, which sends the symbols of each file back as it
+/// goes. They are added to the index by `poll`, so the index can be searched while it is built.
+pub struct SymbolIndex {
+    root: PathBuf,
+    files: BTreeMap<PathBuf, Vec<Symbol>>,
+    /// Receives the symbols of each file from the thread building the index, until it finishes.
+    receiver: Option<Receiver<(PathBuf, Vec<Symbol>)>>,
+}
+
+impl SymbolIndex {
+    pub const ID: &str = "SymbolIndex";
+
+    /// Directories that are skipped while indexing, in addition to hidden directories.
+    const SKIPPED_DIRS: [&str; 2] = ["target", "node_modules"];
+
+    /// Files larger than this are assumed to be generated, and are not indexed.
+    const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+    /// Starts indexing the files under a project root in the background.
+    pub fn build(root: &Path) -> Self {
+        let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        info!(target:Self::ID, "Indexing symbols under {root:?}");
+        let (sender, receiver) = channel();
+        let thread_root = root.clone();
+        thread::spawn(move || {
+            let file_types = Settings::load().file_types;
+            let mut paths = Vec::new();
+            Self::find_files(&thread_root, &mut paths);
+            for path in paths {
+                let Ok(contents) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let syntax = highlight::detect_syntax(&path, &contents, &file_types);
+                let symbols = Self::file_symbols(&syntax.name, &contents);
+                // The receiver is dropped when another project is opened.
+                if !symbols.is_empty() && sender.send((path, symbols)).is_err() {
+                    return;
+                }
+            }
+        });
+        Self {
+            root,
+            files: BTreeMap::new(),
+            receiver: Some(receiver),
+        }
+    }
+
+    /// Collects the paths of the files under a directory that should be indexed.
+    fn find_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !name.starts_with('.') && !Self::SKIPPED_DIRS.contains(&name.as_str()) {
+                    Self::find_files(&entry.path(), paths);
+                }
+            } else if file_type.is_file()
+                && entry
+                    .metadata()
+                    .is_ok_and(|m| m.len() <= Self::MAX_FILE_SIZE)
+            {
+                paths.push(entry.path());
+            }
+        }
+    }
+
+    fn file_symbols(language: &str, contents: &str) -> Vec<Symbol> {
+        #[cfg(feature = "tree-sitter")]
+        if let Some(mut tree) = crate::syntax_tree::SyntaxTree::new(language) {
+            tree.update(contents);
+            let symbols = tree.symbols();
+            if !symbols.is_empty() {
+                return symbols;
+            }
+        }
+        symbols::extract(language, contents)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Adds the symbols sent by the indexing thread since the last poll.
+    pub fn poll(&mut self) {
+        let Some(receiver) = &self.receiver else {
+            return;
+        };
+        loop {
+            match receiver.try_recv() {
+                Ok((path, symbols)) => {
+                    self.files.insert(path, symbols);
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    info!(target:Self::ID, "Indexed {} symbols under {:?}", self.symbol_count(), self.root);
+                    self.receiver = None;
+                    break;
+                }
+            }
+        }
+    }
+
+    pub fn is_building(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// The number of symbols in the index.
+    pub fn symbol_count(&self) -> usize {
+        self.files.values().map(Vec::len).sum()
+    }
+
+    /// Replaces the symbols of a file, such as after it is saved.
+    pub fn update_file(&mut self, path: &Path, symbols: Vec<Symbol>) {
+        // The root is canonical, so the path must be too for files opened by a relative path or
+        // through a symlink.
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        if !path.starts_with(&self.root) {
+            return;
+        }
+        trace!(target:Self::ID, "Updating the symbols of {path:?}");
+        self.files.insert(path, symbols);
+    }
+
+    /// The symbols with names matching a query, best matches first.
+    /// The characters of the query must appear in order in the name, ignoring case.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(PathBuf, Symbol)> {
+        // The name of each symbol is scored once, since most names are defined in several places.
+        let mut scores: HashMap<&str, Option<i32>> = HashMap::new();
+        let mut matches: Vec<(i32, &Path, &Symbol)> = self
+            .files
+            .iter()
+            .flat_map(|(path, symbols)| symbols.iter().map(move |s| (path.as_path(), s)))
+            .filter_map(|(path, symbol)| {
+                let score = *scores
+                    .entry(&symbol.name)
+                    .or_insert_with(|| fuzzy_score(query, &symbol.name));
+                Some((score?, path, symbol))
+            })
+            .collect();
+        matches.sort_by(|(a, a_path, a_symbol), (b, b_path, b_symbol)| {
+            b.cmp(a)
+                .then(a_symbol.name.len().cmp(&b_symbol.name.len()))
+                .then(a_path.cmp(b_path))
+                .then(a_symbol.row.cmp(&b_symbol.row))
+        });
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, path, symbol)| (path.to_path_buf(), symbol.clone()))
+            .collect()
+    }
+}
+
+/// Scores how well a name matches a query, or None if the characters of the query don't appear
+/// in order in the name. Matches at the start of words and runs of consecutive matches score
+/// higher, so `ws` prefers `WorkspaceSymbol` over `lowest`.
+fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let name: Vec<char> = name.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        let found = (position..name.len())
+            .find(|i| name[*i].to_lowercase().eq(query_char.to_lowercase()))?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 4;
+        }
+        let word_start = match found.checked_sub(1).map(|i| name[i]) {
+            None => true,
+            Some(before) => {
+                !before.is_alphanumeric() || (before.is_lowercase() && name[found].is_uppercase())
+            }
+        };
+        if word_start {
+            score += 6;
+        }
+        // Characters skipped before the first match count against it.
+        if previous.is_none() {
+            score -= found.min(10) as i32;
+        }
+        if name[found] == query_char {
+            score += 1;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
@@ -0,0 +1,327 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// The kind of a symbol, shown before its name in the outline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Struct,
+    Enum,
+    Trait,
+    Interface,
+    Impl,
+    Class,
+    Module,
+    Type,
+    Constant,
+    Macro,
+    Heading,
+}
+
+impl SymbolKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SymbolKind::Function => "fn",
+            SymbolKind::Struct => "struct",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Interface => "interface",
+            SymbolKind::Impl => "impl",
+            SymbolKind::Class => "class",
+            SymbolKind::Module => "mod",
+            SymbolKind::Type => "type",
+            SymbolKind::Constant => "const",
+            SymbolKind::Macro => "macro",
+            SymbolKind::Heading => "#",
+        }
+    }
+}
+
+/// A function, type or heading defined in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The position of the start of the line defining the symbol, or of its name.
+    pub row: usize,
+    pub col: usize,
+    /// The number of symbols this symbol is nested in.
+    pub depth: usize,
+}
+
+/// Patterns matching a line that defines a symbol, with the name of the symbol in the `name` group.
+/// Each line uses the first pattern that matches it.
+type Patterns = Vec<(SymbolKind, Regex)>;
+
+/// The visibility and qualifiers that can come before a Rust item.
+const RUST_PREFIX: &str =
+    r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|default|extern\s+"[^"]*")\s+)*"#;
+
+/// The modifiers that can come before a Java or C# declaration.
+const JAVA_PREFIX: &str = r"^\s*(?:@\w+\s+)*(?:(?:public|private|protected|internal|static|final|abstract|sealed|partial)\s+)*";
+
+static LANGUAGES: LazyLock<Vec<(&'static [&'static str], Patterns)>> = LazyLock::new(|| {
+    let patterns = |patterns: &[(SymbolKind, &str)]| -> Patterns {
+        patterns
+            .iter()
+            .map(|(kind, pattern)| (*kind, Regex::new(pattern).expect("Invalid symbol pattern")))
+            .collect()
+    };
+    vec![
+        (
+            &["Rust"],
+            patterns(&[
+                (
+                    SymbolKind::Function,
+                    &format!(r"{RUST_PREFIX}fn\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Struct,
+                    &format!(r"{RUST_PREFIX}struct\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Enum,
+                    &format!(r"{RUST_PREFIX}enum\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Struct,
+                    &format!(r"{RUST_PREFIX}union\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Trait,
+                    &format!(r"{RUST_PREFIX}trait\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Module,
+                    &format!(r"{RUST_PREFIX}mod\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Type,
+                    &format!(r"{RUST_PREFIX}type\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Constant,
+                    &format!(
+                        r"{RUST_PREFIX}(?:static\s+(?:mut\s+)?)?(?P<name>[A-Z][A-Z0-9_]*)\s*:"
+                    ),
+                ),
+                (SymbolKind::Macro, r"^\s*macro_rules!\s*(?P<name>\w+)"),
+                (
+                    SymbolKind::Impl,
+                    r"^\s*(?:unsafe\s+)?impl(?:<[^>]*>)?\s+(?P<name>[^{]*?)\s*(?:where\b.*)?\{?\s*$",
+                ),
+            ]),
+        ),
+        (
+            &["Python"],
+            patterns(&[
+                (
+                    SymbolKind::Function,
+                    r"^\s*(?:async\s+)?def\s+(?P<name>\w+)",
+                ),
+                (SymbolKind::Class, r"^\s*class\s+(?P<name>\w+)"),
+            ]),
+        ),
+        (
+            &["Go"],
+            patterns(&[
+                (
+                    SymbolKind::Function,
+                    r"^func\s+(?:\([^)]*\)\s*)?(?P<name>\w+)",
+                ),
+                (SymbolKind::Struct, r"^type\s+(?P<name>\w+)\s+struct\b"),
+                (
+                    SymbolKind::Interface,
+                    r"^type\s+(?P<name>\w+)\s+interface\b",
+                ),
+                (SymbolKind::Type, r"^type\s+(?P<name>\w+)"),
+            ]),
+        ),
+        (
+            &[
+                "JavaScript",
+                "TypeScript",
+                "TypeScriptReact",
+                "JavaScript (Babel)",
+            ],
+            patterns(&[
+                (
+                    SymbolKind::Function,
+                    r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\*?\s+(?P<name>\w+)",
+                ),
+                (
+                    SymbolKind::Class,
+                    r"^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+(?P<name>\w+)",
+                ),
+                (
+                    SymbolKind::Interface,
+                    r"^\s*(?:export\s+)?interface\s+(?P<name>\w+)",
+                ),
+                (
+                    SymbolKind::Type,
+                    r"^\s*(?:export\s+)?type\s+(?P<name>\w+)\s*=",
+                ),
+            ]),
+        ),
+        (
+            &["Java", "C#"],
+            patterns(&[
+                (
+                    SymbolKind::Class,
+                    &format!(r"{JAVA_PREFIX}(?:class|record)\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Interface,
+                    &format!(r"{JAVA_PREFIX}interface\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Enum,
+                    &format!(r"{JAVA_PREFIX}enum\s+(?P<name>\w+)"),
+                ),
+                (
+                    SymbolKind::Function,
+                    &format!(r"{JAVA_PREFIX}[\w<>\[\],.?]+\s+(?P<name>\w+)\s*\([^;]*$"),
+                ),
+            ]),
+        ),
+        (
+            &["C", "C++", "Objective-C"],
+            patterns(&[
+                (SymbolKind::Module, r"^\s*namespace\s+(?P<name>[\w:]+)"),
+                (
+                    SymbolKind::Class,
+                    r"^\s*(?:template\s*<.*>\s*)?class\s+(?P<name>\w+)[^;]*$",
+                ),
+                (
+                    SymbolKind::Struct,
+                    r"^\s*(?:typedef\s+)?struct\s+(?P<name>\w+)[^;]*$",
+                ),
+                (
+                    SymbolKind::Enum,
+                    r"^\s*(?:typedef\s+)?enum\s+(?:class\s+)?(?P<name>\w+)[^;]*$",
+                ),
+                (SymbolKind::Macro, r"^\s*#\s*define\s+(?P<name>\w+)"),
+                // Functions are only found at the start of a line, so calls and statements inside
+                // function bodies are skipped.
+                (
+                    SymbolKind::Function,
+                    r"^(?:[A-Za-z_][\w:<>,*&]*\s+)+[*&]*(?P<name>[A-Za-z_~][\w:~]*)\s*\([^;]*$",
+                ),
+            ]),
+        ),
+        (
+            &["Ruby"],
+            patterns(&[
+                (SymbolKind::Function, r"^\s*def\s+(?P<name>[\w.?!=]+)"),
+                (SymbolKind::Class, r"^\s*class\s+(?P<name>[\w:]+)"),
+                (SymbolKind::Module, r"^\s*module\s+(?P<name>[\w:]+)"),
+            ]),
+        ),
+        (
+            &["Bourne Again Shell (bash)", "Shell-Unix-Generic"],
+            patterns(&[(
+                SymbolKind::Function,
+                r"^\s*(?:function\s+(?P<name>[\w-]+)|(?P<plain>[\w-]+)\s*\(\s*\))",
+            )]),
+        ),
+    ]
+});
+
+static MARKDOWN_HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?P<level>#{1,6})\s+(?P<name>.*?)\s*#*\s*$").unwrap());
+
+/// Finds the symbols in a file by matching each line against patterns for its language.
+/// The language is the name of a syntect syntax, such as `Rust`.
+/// Returns an empty list for languages without patterns.
+pub fn extract(language: &str, text: &str) -> Vec<Symbol> {
+    if language == "Markdown" || language == "MultiMarkdown" {
+        return extract_headings(text);
+    }
+    let Some((_, patterns)) = LANGUAGES
+        .iter()
+        .find(|(names, _)| names.contains(&language))
+    else {
+        return Vec::new();
+    };
+    let mut nesting = Nesting::default();
+    let mut symbols = Vec::new();
+    for (row, line) in text.lines().enumerate() {
+        let Some((kind, name)) = patterns.iter().find_map(|(kind, regex)| {
+            let captures = regex.captures(line)?;
+            let name = captures.name("name").or(captures.name("plain"))?;
+            Some((*kind, name))
+        }) else {
+            continue;
+        };
+        if name.as_str().is_empty() || is_keyword(name.as_str()) {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        symbols.push(Symbol {
+            name: name.as_str().to_string(),
+            kind,
+            row,
+            col: name.start(),
+            depth: nesting.depth(indent),
+        });
+    }
+    symbols
+}
+
+/// The headings of a markdown file, skipping lines in fenced code blocks.
+fn extract_headings(text: &str) -> Vec<Symbol> {
+    let mut nesting = Nesting::default();
+    let mut in_code = false;
+    let mut symbols = Vec::new();
+    for (row, line) in text.lines().enumerate() {
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let Some(captures) = MARKDOWN_HEADING.captures(line) else {
+            continue;
+        };
+        let name = &captures["name"];
+        symbols.push(Symbol {
+            name: name.to_string(),
+            kind: SymbolKind::Heading,
+            row,
+            col: 0,
+            depth: nesting.depth(captures["level"].len()),
+        });
+    }
+    symbols
+}
+
+/// Words that can start a line like a function definition in C-like languages, such as
+/// `else if (x)` or `return foo(x)`.
+fn is_keyword(name: &str) -> bool {
+    matches!(
+        name,
+        "if" | "for" | "while" | "switch" | "return" | "sizeof" | "catch"
+    )
+}
+
+/// Tracks the levels of the symbols enclosing each line, so symbols are nested under any symbol
+/// before them with a smaller indentation or heading level.
+#[derive(Default)]
+struct Nesting {
+    levels: Vec<usize>,
+}
+
+impl Nesting {
+    fn depth(&mut self, level: usize) -> usize {
+        while self.levels.last().is_some_and(|last| *last >= level) {
+            self.levels.pop();
+        }
+        self.levels.push(level);
+        self.levels.len() - 1
+    }
+}
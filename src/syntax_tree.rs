@@ -0,0 +1,317 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::symbols::{Symbol, SymbolKind};
+use log::{error, trace};
+use std::ops::Range;
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{InputEdit, Language, Node, Parser, Point, Query, QueryCursor, Tree};
+
+pub const ID: &str = "SyntaxTree";
+
+/// A color as red, green and blue components.
+pub type Rgb = (u8, u8, u8);
+
+/// A tree-sitter parse of a buffer, used for highlighting and for selecting syntax nodes.
+///
+/// The tree is updated with the whole text of the buffer after each change. The change is found by
+/// comparing the new text with the text last parsed, so only the part of the tree around the edit
+/// is parsed again.
+pub struct SyntaxTree {
+    parser: Parser,
+    query: Query,
+    tree: Option<Tree>,
+    text: String,
+    /// The byte offset of the start of each line in the text.
+    line_starts: Vec<usize>,
+}
+
+impl SyntaxTree {
+    /// Creates a parser for a language by the name of its syntect syntax, such as `Rust`.
+    /// Returns None for languages without a tree-sitter grammar.
+    pub fn new(language: &str) -> Option<Self> {
+        let (grammar, highlights): (Language, &str) = match language {
+            "Rust" => (
+                Language::new(tree_sitter_rust::LANGUAGE),
+                tree_sitter_rust::HIGHLIGHTS_QUERY,
+            ),
+            "Python" => (
+                Language::new(tree_sitter_python::LANGUAGE),
+                tree_sitter_python::HIGHLIGHTS_QUERY,
+            ),
+            "JSON" => (
+                Language::new(tree_sitter_json::LANGUAGE),
+                tree_sitter_json::HIGHLIGHTS_QUERY,
+            ),
+            _ => return None,
+        };
+        let mut parser = Parser::new();
+        if let Err(e) = parser.set_language(&grammar) {
+            error!(target:ID, "Failed to load the {language} grammar: {e}");
+            return None;
+        }
+        let query = match Query::new(&grammar, highlights) {
+            Ok(query) => query,
+            Err(e) => {
+                error!(target:ID, "Failed to load the {language} highlights: {e}");
+                return None;
+            }
+        };
+        trace!(target:ID, "Using tree-sitter for {language}");
+        Some(Self {
+            parser,
+            query,
+            tree: None,
+            text: String::new(),
+            line_starts: vec![0],
+        })
+    }
+
+    /// Parses the text again if it changed since it was last parsed.
+    pub fn update(&mut self, text: &str) {
+        if self.tree.is_some() && text == self.text {
+            return;
+        }
+        if let Some(tree) = &mut self.tree {
+            tree.edit(&input_edit(&self.text, text));
+        }
+        self.tree = self.parser.parse(text, self.tree.as_ref());
+        self.text = text.to_string();
+        self.line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+    }
+
+    /// The colors for the highlighted parts of a byte range of the text. Outer nodes come before the
+    /// nodes inside them, so applying the colors in order leaves the innermost color on top.
+    pub fn highlights(&self, range: Range<usize>) -> Vec<(Range<usize>, Rgb)> {
+        let Some(tree) = &self.tree else {
+            return Vec::new();
+        };
+        let names = self.query.capture_names();
+        let mut cursor = QueryCursor::new();
+        cursor.set_byte_range(range);
+        let mut captures = cursor.captures(&self.query, tree.root_node(), self.text.as_bytes());
+        let mut highlights: Vec<(Range<usize>, Rgb)> = Vec::new();
+        while let Some((found, index)) = captures.next() {
+            let capture = found.captures[*index];
+            let range = capture.node.byte_range();
+            // The first pattern capturing a node takes precedence, as in tree-sitter-highlight.
+            if highlights.last().is_some_and(|(last, _)| *last == range) {
+                continue;
+            }
+            if let Some(color) = color(names[capture.index as usize]) {
+                highlights.push((range, color));
+            }
+        }
+        highlights
+    }
+
+    /// The text as HTML, with each highlighted part in a colored span.
+    pub fn html(&self) -> String {
+        // The color of each byte, with inner nodes replacing the colors of outer nodes.
+        let mut colors: Vec<Option<Rgb>> = vec![None; self.text.len()];
+        for (range, color) in self.highlights(0..self.text.len()) {
+            colors[range].fill(Some(color));
+        }
+        let mut html = String::from("<pre>\n");
+        let mut start = 0;
+        while start < self.text.len() {
+            let color = colors[start];
+            let end = (start..self.text.len())
+                .find(|i| colors[*i] != color && self.text.is_char_boundary(*i))
+                .unwrap_or(self.text.len());
+            let escaped = escape_html(&self.text[start..end]);
+            match color {
+                Some((r, g, b)) => html.push_str(&format!(
+                    "<span style=\"color:#{r:02x}{g:02x}{b:02x};\">{escaped}</span>"
+                )),
+                None => html.push_str(&escaped),
+            }
+            start = end;
+        }
+        html.push_str("</pre>\n");
+        html
+    }
+
+    /// The byte range of the smallest named node that contains a byte range and is larger than it.
+    pub fn expand(&self, range: Range<usize>) -> Option<Range<usize>> {
+        let mut node = self.node_at(range.clone())?;
+        while node.byte_range() == range || !node.is_named() {
+            node = node.parent()?;
+        }
+        Some(node.byte_range())
+    }
+
+    /// The start of the named node that is the parent of the smallest named node at a byte offset.
+    pub fn parent(&self, offset: usize) -> Option<usize> {
+        let node = self.node_at(offset..offset)?;
+        let mut parent = node.parent()?;
+        while !parent.is_named() || parent.start_byte() == node.start_byte() {
+            parent = parent.parent()?;
+        }
+        Some(parent.start_byte())
+    }
+
+    /// The functions, types and modules defined in the text, nested by the nodes containing them.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        let mut symbols = Vec::new();
+        if let Some(tree) = &self.tree {
+            self.collect_symbols(tree.root_node(), 0, &mut symbols);
+        }
+        symbols
+    }
+
+    fn collect_symbols(&self, node: Node<'_>, depth: usize, symbols: &mut Vec<Symbol>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            let symbol = symbol_kind(child.kind()).and_then(|kind| {
+                let name = match kind {
+                    SymbolKind::Impl => {
+                        let text = |field| {
+                            Some(&self.text[child.child_by_field_name(field)?.byte_range()])
+                        };
+                        match (text("trait"), text("type")) {
+                            (Some(t), Some(ty)) => format!("{t} for {ty}"),
+                            (None, Some(ty)) => ty.to_string(),
+                            _ => return None,
+                        }
+                    }
+                    _ => self.text[child.child_by_field_name("name")?.byte_range()].to_string(),
+                };
+                let start = child.start_position();
+                Some(Symbol {
+                    name,
+                    kind,
+                    row: start.row,
+                    col: self.text[self.line_starts[start.row]..child.start_byte()]
+                        .chars()
+                        .count(),
+                    depth,
+                })
+            });
+            match symbol {
+                Some(symbol) => {
+                    symbols.push(symbol);
+                    self.collect_symbols(child, depth + 1, symbols);
+                }
+                None => self.collect_symbols(child, depth, symbols),
+            }
+        }
+    }
+
+    fn node_at(&self, range: Range<usize>) -> Option<Node<'_>> {
+        let root = self.tree.as_ref()?.root_node();
+        let mut node = root.descendant_for_byte_range(range.start, range.end)?;
+        while !node.is_named() {
+            node = node.parent()?;
+        }
+        Some(node)
+    }
+
+    /// The byte offset of a row and character column in the text.
+    pub fn offset(&self, row: usize, col: usize) -> usize {
+        let Some(line_start) = self.line_starts.get(row).copied() else {
+            return self.text.len();
+        };
+        let line_end = self
+            .line_starts
+            .get(row + 1)
+            .map_or(self.text.len(), |next| next - 1);
+        let line = &self.text[line_start..line_end];
+        line_start
+            + line
+                .char_indices()
+                .nth(col)
+                .map_or(line.len(), |(index, _)| index)
+    }
+
+    /// The row and character column of a byte offset in the text.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.text.len());
+        let row = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let line_start = self.line_starts[row];
+        (row, self.text[line_start..offset].chars().count())
+    }
+}
+
+/// Describes the edit that changed `old` into `new`, from the first to the last byte that differ.
+fn input_edit(old: &str, new: &str) -> InputEdit {
+    let (old_bytes, new_bytes) = (old.as_bytes(), new.as_bytes());
+    let prefix = old_bytes
+        .iter()
+        .zip(new_bytes)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_bytes[prefix..]
+        .iter()
+        .rev()
+        .zip(new_bytes[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_end = old_bytes.len() - suffix;
+    let new_end = new_bytes.len() - suffix;
+    InputEdit {
+        start_byte: prefix,
+        old_end_byte: old_end,
+        new_end_byte: new_end,
+        start_position: point(old_bytes, prefix),
+        old_end_position: point(old_bytes, old_end),
+        new_end_position: point(new_bytes, new_end),
+    }
+}
+
+fn point(text: &[u8], offset: usize) -> Point {
+    let before = &text[..offset];
+    let row = before.iter().filter(|b| **b == b'\n').count();
+    let line_start = before
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |i| i + 1);
+    Point::new(row, offset - line_start)
+}
+
+/// The kind of symbol defined by a Rust or Python node.
+fn symbol_kind(node_kind: &str) -> Option<SymbolKind> {
+    let kind = match node_kind {
+        "function_item" | "function_signature_item" | "function_definition" => SymbolKind::Function,
+        "struct_item" | "union_item" => SymbolKind::Struct,
+        "enum_item" => SymbolKind::Enum,
+        "trait_item" => SymbolKind::Trait,
+        "impl_item" => SymbolKind::Impl,
+        "mod_item" => SymbolKind::Module,
+        "type_item" => SymbolKind::Type,
+        "const_item" | "static_item" => SymbolKind::Constant,
+        "macro_definition" => SymbolKind::Macro,
+        "class_definition" => SymbolKind::Class,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// The color for a highlight capture name, such as `keyword` or `function.method`, using the
+/// dracula palette to match the syntect highlighting.
+fn color(capture: &str) -> Option<Rgb> {
+    let color = match capture {
+        "variable.parameter" => (0xff, 0xb8, 0x6c),
+        "variable.builtin" => (0xbd, 0x93, 0xf9),
+        _ => match capture.split('.').next().unwrap_or_default() {
+            "comment" => (0x62, 0x72, 0xa4),
+            "string" | "character" => (0xf1, 0xfa, 0x8c),
+            "keyword" | "operator" | "include" | "conditional" | "repeat" => (0xff, 0x79, 0xc6),
+            "function" | "constructor" | "method" => (0x50, 0xfa, 0x7b),
+            "type" | "property" | "label" => (0x8b, 0xe9, 0xfd),
+            "constant" | "number" | "boolean" | "escape" => (0xbd, 0x93, 0xf9),
+            "attribute" => (0xff, 0xb8, 0x6c),
+            _ => return None,
+        },
+    };
+    Some(color)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
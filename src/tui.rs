@@ -4,12 +4,34 @@
 
 mod about;
 mod app;
+mod call_stack;
+mod clipboard;
+mod command_line;
 mod component;
+mod debug_console;
+mod debug_panel;
+mod diff_view;
 mod editor;
+mod editor_layout;
+mod editor_overlay;
 mod editor_tab;
 mod explorer;
+mod folds;
+mod history;
+mod image_view;
 mod logger;
+mod markdown_preview;
 mod menu_bar;
+mod merge_view;
+mod multi_cursor;
+mod outline;
+mod panel_layout;
+mod search_bar;
+mod symbol_search;
+mod text;
+mod undo_tree;
+mod variables;
+mod watches;
 
 use crate::AppContext;
 use anyhow::{Context, Result};
@@ -7,15 +7,18 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap};
 
-pub struct About {}
+pub struct About {
+    /// The keys of the current editor, listed below the description.
+    keys: &'static [&'static str],
+}
 
 impl About {
     #[allow(unused)]
     pub const ID: &str = "About";
 
-    pub fn new() -> Self {
+    pub fn new(keys: &'static [&'static str]) -> Self {
         // trace!(target:Self::id(), "Building {}", Self::id());
-        Self {}
+        Self { keys }
     }
 }
 
@@ -27,8 +30,8 @@ impl Widget for About {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Fill(1), // Image Layout
-                Constraint::Fill(2), // Description
+                Constraint::Length(3), // Image Layout
+                Constraint::Fill(1),   // Description and keys
             ])
             .split(area);
         let kilroy = [
@@ -65,6 +68,8 @@ impl Widget for About {
         ];
         let about_lines: Vec<Line> = about_text
             .iter()
+            .chain(["", "Editor keys:"].iter())
+            .chain(self.keys)
             .map(|l| Line::from(Span::raw(*l)))
             .collect();
 
@@ -2,25 +2,37 @@
 //
 // SPDX-License-Identifier: GNU General Public License v3.0 or later
 
+use crate::debugger::{DebugCommand, Debugger};
+use crate::editorconfig::{EditorConfig, FileFormat};
+use crate::recent::Recent;
+use crate::settings::Keymap;
+use crate::symbol_index::SymbolIndex;
 use crate::tui::about::About;
-use crate::tui::app::AppComponent::{AppEditor, AppExplorer, AppLogger};
+use crate::tui::app::AppComponent::{AppDebugger, AppEditor, AppExplorer, AppLogger, AppOutline};
 use crate::tui::component::{Action, Component, Focus, FocusState, Visibility, VisibleState};
-use crate::tui::editor_tab::EditorTab;
+use crate::tui::debug_panel::DebugPanel;
+use crate::tui::diff_view::DiffView;
+use crate::tui::editor::Editor;
+use crate::tui::editor_layout::EditorLayout;
 use crate::tui::explorer::Explorer;
 use crate::tui::logger::Logger;
 use crate::tui::menu_bar::MenuBar;
+use crate::tui::outline::Outline;
+use crate::tui::panel_layout::{Divider, ExplorerSide, PanelLayout};
+use crate::tui::symbol_search::SymbolSearch;
 use AppComponent::AppMenuBar;
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use log::{error, info, trace};
 use ratatui::DefaultTerminal;
 use ratatui::buffer::Buffer;
 use ratatui::crossterm::event;
 use ratatui::crossterm::event::{
-    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
 };
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Widget};
+use ratatui::layout::{Constraint, Direction, Layout, Position, Rect};
+use ratatui::prelude::{Color, Style, Widget};
 use ratatui::widgets::{Paragraph, Wrap};
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
@@ -30,33 +42,338 @@ pub enum AppComponent {
     AppExplorer,
     AppLogger,
     AppMenuBar,
+    AppOutline,
+    AppDebugger,
 }
 
 pub struct App<'a> {
-    editor_tab: EditorTab,
+    editor_layout: EditorLayout,
     explorer: Explorer<'a>,
     logger: Logger,
     menu_bar: MenuBar,
+    outline: Outline,
+    symbol_search: SymbolSearch,
+    /// The symbols defined in the project, for the symbol search.
+    symbol_index: SymbolIndex,
+    diff_view: DiffView,
+    debugger: Debugger,
+    debug_panel: DebugPanel,
     last_active: AppComponent,
     about: bool,
+    recent: Recent,
+    layout: PanelLayout,
+    /// The panel border currently being dragged with the mouse, if any.
+    dragging: Option<Divider>,
+    /// Areas from the last render, used to find panel borders and components under the mouse.
+    area: Rect,
+    /// The column holding the Explorer and Outline.
+    side_area: Rect,
+    explorer_area: Rect,
+    outline_area: Rect,
+    logger_area: Rect,
+    editor_area: Rect,
+    debug_area: Rect,
 }
 
 impl<'a> App<'a> {
     pub const ID: &'static str = "App";
 
+    /// The number of columns or percent of height a panel is resized by for each key press.
+    const RESIZE_STEP: i16 = 2;
+
+    /// The percent of the height of the Explorer and editor taken by the debug panel.
+    const DEBUG_PANEL_HEIGHT: u16 = 40;
+
     pub fn new(root_path: PathBuf) -> Result<Self> {
         trace!(target:Self::ID, "Building {}", Self::ID);
+        let recent = Recent::load();
+        let mut menu_bar = MenuBar::new();
+        menu_bar.set_recent(&recent);
         let app = Self {
-            editor_tab: EditorTab::new(),
+            editor_layout: EditorLayout::new(),
             explorer: Explorer::new(&root_path)?,
             logger: Logger::new(),
-            menu_bar: MenuBar::new(),
+            menu_bar,
+            outline: Outline::new(),
+            symbol_search: SymbolSearch::default(),
+            symbol_index: SymbolIndex::build(&root_path),
+            diff_view: DiffView::default(),
+            debugger: Debugger::default(),
+            debug_panel: DebugPanel::new(),
             last_active: AppEditor,
             about: false,
+            recent,
+            layout: PanelLayout::load(),
+            dragging: None,
+            area: Rect::default(),
+            side_area: Rect::default(),
+            explorer_area: Rect::default(),
+            outline_area: Rect::default(),
+            logger_area: Rect::default(),
+            editor_area: Rect::default(),
+            debug_area: Rect::default(),
         };
         Ok(app)
     }
 
+    /// Opens a file in a new editor tab and records it in the recent files list.
+    fn open_file(&mut self, path: &Path) -> Result<()> {
+        self.editor_layout.open_tab(path)?;
+        self.recent.add_file(path);
+        self.save_recent();
+        Ok(())
+    }
+
+    /// Replaces the Explorer with a new one rooted at the given project directory.
+    fn open_project(&mut self, path: &Path) -> Result<()> {
+        info!(target:Self::ID, "Opening project {path:?}");
+        let mut explorer = Explorer::new(&path.to_path_buf())?;
+        if self.last_active == AppExplorer {
+            explorer.component_state.set_focus(Focus::Active);
+        }
+        self.explorer = explorer;
+        self.symbol_index = SymbolIndex::build(path);
+        self.recent.add_project(path);
+        self.save_recent();
+        Ok(())
+    }
+
+    /// The contents of a file on disk, decoded as they are when the file is opened so the line
+    /// endings and encoding match its buffer.
+    fn saved_contents(path: &Path) -> Result<String> {
+        let bytes = fs::read(path).with_context(|| format!("Failed to read {path:?}"))?;
+        let (_, contents) = FileFormat::decode(&bytes, &EditorConfig::for_file(path))?;
+        Ok(contents)
+    }
+
+    /// The contents of a file, from its buffer if it is open in an editor.
+    fn file_contents(&self, path: &Path) -> Result<String> {
+        match self.editor_layout.find_editor(path) {
+            Some(editor) => Ok(editor.state.lines.to_string()),
+            None => Self::saved_contents(path),
+        }
+    }
+
+    /// The path of a file relative to the project root, for titles.
+    fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.explorer.root_path)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    /// Saves the current editor and updates the symbols of its file in the index, returning false
+    /// if it couldn't be saved. Errors are logged and shown in the editor.
+    fn save_current_editor(&mut self) -> bool {
+        let Some(editor) = self.editor_layout.current_editor_mut() else {
+            error!(target:Self::ID, "Failed to get current editor while saving");
+            return false;
+        };
+        if let Err(e) = editor.save() {
+            error!(target:Self::ID, "Failed to save editor contents: {e:#}");
+            editor.show_message(format!("{e:#}"));
+            return false;
+        }
+        if let Some(path) = editor.file_path.clone() {
+            let symbols = editor.symbols();
+            self.symbol_index.update_file(&path, symbols);
+        }
+        true
+    }
+
+    /// Compares two files on disk.
+    fn compare_files(&mut self, old: &Path, new: &Path) -> Result<()> {
+        let (old_contents, new_contents) = (Self::saved_contents(old)?, Self::saved_contents(new)?);
+        self.diff_view.open(
+            self.display_path(old),
+            &old_contents,
+            self.display_path(new),
+            &new_contents,
+        );
+        Ok(())
+    }
+
+    /// Compares the current buffer with a file, or with its own file on disk.
+    fn compare_buffer(&mut self, path: Option<PathBuf>) -> Result<()> {
+        let Some(editor) = self.editor_layout.current_editor() else {
+            bail!("No file is open to compare");
+        };
+        let Some(buffer_path) = editor.file_path.clone() else {
+            bail!("The current editor has no file to compare");
+        };
+        let buffer = editor.state.lines.to_string();
+        let name = self.display_path(&buffer_path);
+        match path {
+            None => {
+                let saved = Self::saved_contents(&buffer_path)?;
+                self.diff_view
+                    .open(format!("{name} (saved)"), &saved, name, &buffer);
+            }
+            Some(path) => {
+                // Relative paths, such as those given to the `:diff` command, start at the project root.
+                let path = self.explorer.root_path.join(path);
+                let other = self.file_contents(&path)?;
+                self.diff_view
+                    .open(name, &buffer, self.display_path(&path), &other);
+            }
+        }
+        Ok(())
+    }
+
+    /// Opens a file and moves the cursor to a position in it.
+    fn open_file_at(&mut self, path: &Path, row: usize, col: usize) -> Result<Action> {
+        self.open_file(path)?;
+        if let Some(editor) = self.editor_layout.current_editor_mut() {
+            editor.goto(row, col);
+        }
+        self.change_focus(AppEditor);
+        Ok(Action::Handled)
+    }
+
+    /// The debugger command for a function key. These work whichever component is focused.
+    fn debug_key(key: KeyEvent) -> Option<DebugCommand> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::F(5) if shift => Some(DebugCommand::Stop),
+            KeyCode::F(5) => Some(DebugCommand::Continue),
+            KeyCode::F(6) => Some(DebugCommand::Pause),
+            KeyCode::F(10) => Some(DebugCommand::StepOver),
+            KeyCode::F(11) if shift => Some(DebugCommand::StepOut),
+            KeyCode::F(11) => Some(DebugCommand::StepIn),
+            _ => None,
+        }
+    }
+
+    /// Runs a debugger command. Continuing without a program being debugged starts it.
+    fn debug(&mut self, command: DebugCommand) -> Result<Action> {
+        let command = match command {
+            DebugCommand::Continue if !self.debugger.is_running() => DebugCommand::Start,
+            command => command,
+        };
+        if command == DebugCommand::Start {
+            self.debug_panel
+                .component_state
+                .set_visible(Visibility::Visible);
+        }
+        // The adapter and program run from the project root, so relative paths start there.
+        let root = fs::canonicalize(&self.explorer.root_path)
+            .unwrap_or_else(|_| self.explorer.root_path.clone());
+        if let Err(e) = self.debugger.run(command, &root) {
+            error!(target:Debugger::ID, "{e:#}");
+        }
+        Ok(Action::Handled)
+    }
+
+    /// Handles messages from the debug adapter, showing the line the program stopped at and
+    /// giving each editor its breakpoints.
+    fn update_debugger(&mut self) {
+        if let Some((path, row)) = self.debugger.poll() {
+            match self.editor_layout.open_tab(&path) {
+                Ok(()) => {
+                    if let Some(editor) = self.editor_layout.current_editor_mut() {
+                        editor.goto(row, 0);
+                    }
+                }
+                Err(e) => error!(target:Debugger::ID, "Failed to open {path:?}: {e:#}"),
+            }
+        }
+        for editor in self.editor_layout.editors_mut() {
+            // Breakpoints are kept by their full path, which is also how adapters give paths.
+            let Some(path) = editor.canonical_path() else {
+                continue;
+            };
+            let breakpoints = self.debugger.breakpoints(path);
+            let row = self.debugger.current_row(path);
+            editor.set_debug_marks(breakpoints, row);
+        }
+    }
+
+    /// Finds the panel border at a position on the screen.
+    fn divider_at(&self, column: u16, row: u16) -> Option<Divider> {
+        let side = self.side_area;
+        if side.width > 0 && row >= side.y && row < side.bottom() {
+            let edge = match self.layout.explorer_side {
+                ExplorerSide::Left => side.right().saturating_sub(1),
+                ExplorerSide::Right => side.x,
+            };
+            if column == edge {
+                return Some(Divider::Explorer);
+            }
+        }
+        let logger = self.logger_area;
+        if logger.height > 0 && row == logger.y && column >= logger.x && column < logger.right() {
+            return Some(Divider::Logger);
+        }
+        None
+    }
+
+    /// Resizes panels by dragging their borders with the left mouse button.
+    /// Returns true if the mouse event was used for resizing.
+    fn handle_divider_drag(&mut self, mouse: MouseEvent) -> bool {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.dragging = self.divider_at(mouse.column, mouse.row);
+                self.dragging.is_some()
+            }
+            MouseEventKind::Drag(MouseButton::Left) => match self.dragging {
+                Some(Divider::Explorer) => {
+                    let width = match self.layout.explorer_side {
+                        ExplorerSide::Left => (mouse.column + 1).saturating_sub(self.side_area.x),
+                        ExplorerSide::Right => self.side_area.right().saturating_sub(mouse.column),
+                    };
+                    self.layout.set_explorer_width(width);
+                    true
+                }
+                Some(Divider::Logger) => {
+                    let rows = self.logger_area.bottom().saturating_sub(mouse.row);
+                    let height = (rows as u32 * 100 / self.area.height.max(1) as u32) as u16;
+                    self.layout.set_logger_height(height);
+                    true
+                }
+                None => false,
+            },
+            MouseEventKind::Up(MouseButton::Left) => match self.dragging.take() {
+                Some(divider) => {
+                    trace!(target:Self::ID, "Finished resizing {divider:?}");
+                    self.layout.save();
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Finds the component under the mouse.
+    /// The menu bar is checked first since its drop-down menus are drawn over other components.
+    fn component_at(&self, position: Position) -> Option<AppComponent> {
+        if self.menu_bar.contains(position) {
+            Some(AppMenuBar)
+        } else if self.explorer_area.contains(position) {
+            Some(AppExplorer)
+        } else if self.outline_area.contains(position) {
+            Some(AppOutline)
+        } else if self.logger_area.contains(position) {
+            Some(AppLogger)
+        } else if self.debug_area.contains(position) {
+            Some(AppDebugger)
+        } else if self.editor_area.contains(position) {
+            Some(AppEditor)
+        } else {
+            None
+        }
+    }
+
+    fn save_recent(&mut self) {
+        if let Err(e) = self.recent.save() {
+            error!(target:Self::ID, "Failed to save recent projects and files: {e:#}");
+        }
+        self.menu_bar.set_recent(&self.recent);
+    }
+
     /// Logic that should be executed once on application startup.
     pub fn start(&mut self) -> Result<()> {
         trace!(target:Self::ID, "Starting App");
@@ -67,6 +384,10 @@ impl<'a> App<'a> {
         self.start()?;
         trace!(target:Self::ID, "Entering App run loop");
         loop {
+            // Results from background work are taken before drawing, so rendering only shows state.
+            self.symbol_index.poll();
+            // The debugger is updated before the editors are drawn, so they show its breakpoints.
+            self.update_debugger();
             terminal.draw(|f| {
                 f.render_widget(&mut self, f.area());
             })?;
@@ -85,12 +406,14 @@ impl<'a> App<'a> {
     }
 
     fn draw_bottom_status(&self, area: Rect, buf: &mut Buffer) {
-        // Determine help text from the most recently focused component.
+        // Determine help text from the symbol search or the most recently focused component.
         let help = match self.last_active {
-            AppEditor => match self.editor_tab.current_editor() {
+            _ if self.symbol_search.is_visible() => SymbolSearch::HELP.to_string(),
+            _ if self.diff_view.is_visible() => DiffView::HELP.to_string(),
+            AppEditor => match self.editor_layout.current_editor() {
                 Some(editor) => editor.component_state.help_text.clone(),
                 None => {
-                    if !self.editor_tab.is_empty() {
+                    if !self.editor_layout.is_empty() {
                         error!(target:Self::ID, "Failed to get Editor while drawing bottom status bar");
                     }
                     "Failed to get current Editor while getting widget help text".to_string()
@@ -99,11 +422,32 @@ impl<'a> App<'a> {
             AppExplorer => self.explorer.component_state.help_text.clone(),
             AppLogger => self.logger.component_state.help_text.clone(),
             AppMenuBar => self.menu_bar.component_state.help_text.clone(),
+            AppOutline => self.outline.component_state.help_text.clone(),
+            AppDebugger => self.debug_panel.component_state.help_text.clone(),
+        };
+        // The editing mode of the current editor is shown on the left of the status bar.
+        let area = match self.editor_layout.current_editor() {
+            Some(editor) => {
+                let mode = format!(" {} ", editor.mode_name());
+                let [mode_area, area] = Layout::horizontal([
+                    Constraint::Length(mode.len() as u16),
+                    Constraint::Fill(1),
+                ])
+                .areas(area);
+                Paragraph::new(mode)
+                    .style(Style::default().fg(Color::Black).bg(editor.mode_color()))
+                    .render(mode_area, buf);
+                area
+            }
+            None => area,
         };
         Paragraph::new(
             concat!(
                 "ALT+Q: Focus project explorer | ALT+W: Focus editor | ALT+E: Focus logger |",
-                " ALT+R: Focus menu bar | CTRL+C: Quit\n"
+                " ALT+R: Focus menu bar | ALT+T: Focus outline | ALT+D: Focus debugger |",
+                " ALT+([/]): Resize explorer |",
+                " ALT+({/}): Resize logger | CTRL+T: Go to symbol |",
+                " CTRL+Q: Quit\n"
             )
             .to_string()
                 + help.as_str(),
@@ -114,13 +458,23 @@ impl<'a> App<'a> {
         .render(area, buf);
     }
 
+    fn editor_uses_standard_keymap(&self) -> bool {
+        self.last_active == AppEditor
+            && self
+                .editor_layout
+                .current_editor()
+                .is_some_and(|editor| editor.keymap() == Keymap::Standard)
+    }
+
     fn clear_focus(&mut self) {
         info!(target:Self::ID, "Clearing all widget focus");
         self.explorer.component_state.set_focus(Focus::Inactive);
         self.explorer.component_state.set_focus(Focus::Inactive);
         self.logger.component_state.set_focus(Focus::Inactive);
         self.menu_bar.component_state.set_focus(Focus::Inactive);
-        match self.editor_tab.current_editor_mut() {
+        self.outline.component_state.set_focus(Focus::Inactive);
+        self.debug_panel.component_state.set_focus(Focus::Inactive);
+        match self.editor_layout.current_editor_mut() {
             None => {
                 error!(target:Self::ID, "Failed to get current Editor while clearing focus")
             }
@@ -132,7 +486,7 @@ impl<'a> App<'a> {
         info!(target:Self::ID, "Changing widget focus to {:?}", focus);
         self.clear_focus();
         match focus {
-            AppEditor => match self.editor_tab.current_editor_mut() {
+            AppEditor => match self.editor_layout.current_editor_mut() {
                 None => {
                     error!(target:Self::ID, "Failed to get current Editor while changing focus")
                 }
@@ -141,9 +495,41 @@ impl<'a> App<'a> {
             AppExplorer => self.explorer.component_state.set_focus(Focus::Active),
             AppLogger => self.logger.component_state.set_focus(Focus::Active),
             AppMenuBar => self.menu_bar.component_state.set_focus(Focus::Active),
+            AppOutline => self.outline.component_state.set_focus(Focus::Active),
+            AppDebugger => {
+                self.debug_panel
+                    .component_state
+                    .set_visible(Visibility::Visible);
+                self.debug_panel.component_state.set_focus(Focus::Active);
+            }
         }
         self.last_active = focus;
     }
+
+    /// Draws the Explorer above the Outline, or either one alone in the whole column.
+    fn render_side_panels(&mut self, area: Rect, buf: &mut Buffer) {
+        let (explorer_area, outline_area) = match (
+            self.explorer.component_state.vis,
+            self.outline.component_state.vis,
+        ) {
+            (Visibility::Visible, Visibility::Visible) => {
+                let [explorer_area, outline_area] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Percentage(40)]).areas(area);
+                (explorer_area, outline_area)
+            }
+            (Visibility::Visible, Visibility::Hidden) => (area, Rect::default()),
+            (Visibility::Hidden, _) => (Rect::default(), area),
+        };
+        if !explorer_area.is_empty() {
+            self.explorer.render(explorer_area, buf);
+        }
+        if !outline_area.is_empty() {
+            self.outline.update(self.editor_layout.current_editor_mut());
+            self.outline.render(outline_area, buf);
+        }
+        self.explorer_area = explorer_area;
+        self.outline_area = outline_area;
+    }
 }
 
 impl<'a> Widget for &mut App<'a> {
@@ -154,10 +540,10 @@ impl<'a> Widget for &mut App<'a> {
         let vertical_constraints = match self.logger.component_state.vis {
             Visibility::Visible => {
                 vec![
-                    Constraint::Length(3),      // top status bar
-                    Constraint::Percentage(70), // horizontal layout
-                    Constraint::Fill(1),        // terminal
-                    Constraint::Length(3),      // bottom status bar
+                    Constraint::Length(3),                             // top status bar
+                    Constraint::Fill(1),                               // horizontal layout
+                    Constraint::Percentage(self.layout.logger_height), // terminal
+                    Constraint::Length(3),                             // bottom status bar
                 ]
             }
             Visibility::Hidden => {
@@ -173,17 +559,42 @@ impl<'a> Widget for &mut App<'a> {
             .constraints(vertical_constraints)
             .split(area);
 
-        let horizontal_constraints = match self.explorer.component_state.vis {
-            Visibility::Visible => {
+        let explorer_width = self.layout.explorer_width;
+        let side_visible = self.explorer.component_state.vis == Visibility::Visible
+            || self.outline.component_state.vis == Visibility::Visible;
+        let horizontal_constraints = match side_visible {
+            true => match self.layout.explorer_side {
+                ExplorerSide::Left => vec![
+                    Constraint::Max(explorer_width), // File explorer with a user-defined max width.
+                    Constraint::Fill(1),             // Editor fills the remaining space.
+                ],
+                ExplorerSide::Right => vec![
+                    Constraint::Fill(1), // Editor fills the remaining space.
+                    Constraint::Max(explorer_width),
+                ],
+            },
+            false => {
                 vec![
-                    Constraint::Max(30), // File explorer with a max width of 30 characters.
                     Constraint::Fill(1), // Editor fills the remaining space.
                 ]
             }
+        };
+
+        // The debug panel is drawn below the Explorer and editor.
+        let main_area = match self.debug_panel.component_state.vis {
+            Visibility::Visible => {
+                let [main_area, debug_area] = Layout::vertical([
+                    Constraint::Fill(1),
+                    Constraint::Percentage(App::DEBUG_PANEL_HEIGHT),
+                ])
+                .areas(vertical[1]);
+                self.debug_panel.render(debug_area, buf, &self.debugger);
+                self.debug_area = debug_area;
+                main_area
+            }
             Visibility::Hidden => {
-                vec![
-                    Constraint::Fill(1), // Editor fills the remaining space.
-                ]
+                self.debug_area = Rect::default();
+                vertical[1]
             }
         };
 
@@ -191,30 +602,25 @@ impl<'a> Widget for &mut App<'a> {
         let horizontal = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(horizontal_constraints)
-            .split(vertical[1]);
-        match self.explorer.component_state.vis {
-            Visibility::Visible => {
-                let editor_layout = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([
-                        Constraint::Length(1), // Editor tabs.
-                        Constraint::Fill(1),   // Editor contents.
-                    ])
-                    .split(horizontal[1]);
-                self.editor_tab
-                    .render(editor_layout[0], editor_layout[1], buf);
-                self.explorer.render(horizontal[0], buf);
+            .split(main_area);
+        self.area = area;
+        match side_visible {
+            true => {
+                let (side_area, editor_area) = match self.layout.explorer_side {
+                    ExplorerSide::Left => (horizontal[0], horizontal[1]),
+                    ExplorerSide::Right => (horizontal[1], horizontal[0]),
+                };
+                self.editor_layout.render(editor_area, buf);
+                self.render_side_panels(side_area, buf);
+                self.side_area = side_area;
+                self.editor_area = editor_area;
             }
-            Visibility::Hidden => {
-                let editor_layout = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([
-                        Constraint::Length(1), // Editor tabs.
-                        Constraint::Fill(1),   // Editor contents.
-                    ])
-                    .split(horizontal[0]);
-                self.editor_tab
-                    .render(editor_layout[0], editor_layout[1], buf);
+            false => {
+                self.editor_layout.render(horizontal[0], buf);
+                self.side_area = Rect::default();
+                self.explorer_area = Rect::default();
+                self.outline_area = Rect::default();
+                self.editor_area = horizontal[0];
             }
         }
 
@@ -222,11 +628,13 @@ impl<'a> Widget for &mut App<'a> {
             // Index 1 of vertical is rendered with the horizontal layout above.
             Visibility::Visible => {
                 self.logger.render(vertical[2], buf);
+                self.logger_area = vertical[2];
                 self.draw_bottom_status(vertical[3], buf);
                 // The title bar is rendered last to overlay any popups created for drop-down menus.
                 self.menu_bar.render(vertical[0], buf);
             }
             Visibility::Hidden => {
+                self.logger_area = Rect::default();
                 self.draw_bottom_status(vertical[2], buf);
                 // The title bar is rendered last to overlay any popups created for drop-down menus.
                 self.menu_bar.render(vertical[0], buf);
@@ -234,8 +642,22 @@ impl<'a> Widget for &mut App<'a> {
         }
 
         if self.about {
-            let about_area = area.centered(Constraint::Percentage(40), Constraint::Percentage(50));
-            About::new().render(about_area, buf);
+            let about_area = area.centered(Constraint::Percentage(80), Constraint::Percentage(90));
+            let keys = self
+                .editor_layout
+                .current_editor()
+                .map_or(Editor::KEYS, Editor::keys);
+            About::new(keys).render(about_area, buf);
+        }
+
+        if self.diff_view.is_visible() {
+            self.diff_view.render(self.editor_area, buf);
+        }
+
+        if self.symbol_search.is_visible() {
+            let search_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(60));
+            self.symbol_search
+                .render(search_area, buf, &self.symbol_index);
         }
     }
 }
@@ -243,6 +665,41 @@ impl<'a> Widget for &mut App<'a> {
 impl<'a> Component for App<'a> {
     /// Handles events for the App and delegates to attached Components.
     fn handle_event(&mut self, event: Event) -> Result<Action> {
+        // The symbol search takes all input while it is open.
+        if self.symbol_search.is_visible() {
+            let action = match event {
+                Event::Key(key) => self.symbol_search.handle_key_events(key)?,
+                Event::Mouse(mouse) => self.symbol_search.handle_mouse_events(mouse)?,
+                _ => Action::Noop,
+            };
+            return match action {
+                Action::OpenFileAt(path, row, col) => self.open_file_at(&path, row, col),
+                action => Ok(action),
+            };
+        }
+        // The diff takes input while it is open, except for mouse input outside of it.
+        if self.diff_view.is_visible() {
+            let action = match event {
+                Event::Key(key) => Some(self.diff_view.handle_key_events(key)?),
+                Event::Mouse(mouse)
+                    if self
+                        .editor_area
+                        .contains(Position::new(mouse.column, mouse.row)) =>
+                {
+                    Some(self.diff_view.handle_mouse_events(mouse)?)
+                }
+                _ => None,
+            };
+            if let Some(action) = action {
+                return Ok(action);
+            }
+        }
+        // Resizing panels takes priority over mouse input for the components under the cursor.
+        if let Some(mouse) = event.as_mouse_event() {
+            if self.handle_divider_drag(mouse) {
+                return Ok(Action::Handled);
+            }
+        }
         // Handle events in the primary application.
         if let Some(key_event) = event.as_key_event() {
             let res = self
@@ -253,57 +710,49 @@ impl<'a> Component for App<'a> {
                 _ => {}
             }
         }
-        // Handle events for all components.
-        let action = match self.last_active {
-            AppEditor => self.editor_tab.handle_event(event.clone())?,
-            AppExplorer => self.explorer.handle_event(event.clone())?,
-            AppLogger => self.logger.handle_event(event.clone())?,
-            AppMenuBar => self.menu_bar.handle_event(event.clone())?,
+        // Mouse events go to the component under the cursor, and key events to the focused one.
+        let action = match event {
+            Event::Mouse(mouse) => self.handle_mouse_events(mouse)?,
+            _ => match self.last_active {
+                AppEditor => self.editor_layout.handle_event(event)?,
+                AppExplorer => self.explorer.handle_event(event)?,
+                AppLogger => self.logger.handle_event(event)?,
+                AppMenuBar => self.menu_bar.handle_event(event)?,
+                AppOutline => self.outline.handle_event(event)?,
+                AppDebugger => self.debug_panel.handle_event(event)?,
+            },
         };
 
-        // Components should always handle mouse events for click interaction.
-        if let Some(mouse) = event.as_mouse_event() {
-            if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
-                if let Some(editor) = self.editor_tab.current_editor_mut() {
-                    editor.handle_mouse_events(mouse)?;
-                }
-                self.explorer.handle_mouse_events(mouse)?;
-                self.logger.handle_mouse_events(mouse)?;
-            }
-        }
-
         // Handle actions returned from widgets that may need context on other widgets or app state.
         match action {
             Action::Quit | Action::Handled => Ok(action),
-            Action::Save => match self.editor_tab.current_editor_mut() {
-                None => {
-                    error!(target:Self::ID, "Failed to get current editor while handling App Action::Save");
-                    Ok(Action::Noop)
-                }
-                Some(editor) => match editor.save() {
-                    Ok(_) => Ok(Action::Handled),
-                    Err(e) => {
-                        error!(target:Self::ID, "Failed to save editor contents: {e}");
-                        Ok(Action::Noop)
-                    }
-                },
+            Action::Save => match self.save_current_editor() {
+                true => Ok(Action::Handled),
+                false => Ok(Action::Noop),
             },
+            Action::SaveAndCloseTab => {
+                if self.save_current_editor()
+                    && let Err(e) = self.editor_layout.close_current_tab()
+                {
+                    error!(target:Self::ID, "Failed to close saved tab: {e:#}");
+                }
+                Ok(Action::Handled)
+            }
             Action::OpenTab => {
                 if let Ok(path) = self.explorer.selected() {
-                    let path_buf = Path::new(&path);
-                    self.editor_tab.open_tab(path_buf)?;
+                    self.open_file(Path::new(&path))?;
                     Ok(Action::Handled)
                 } else {
                     Ok(Action::Noop)
                 }
             }
-            Action::CloseTab => match self.editor_tab.close_current_tab() {
+            Action::CloseTab => match self.editor_layout.close_current_tab() {
                 Ok(_) => Ok(Action::Handled),
                 Err(_) => Ok(Action::Noop),
             },
             Action::ReloadFile => {
                 trace!(target:Self::ID, "Reloading file for current editor");
-                if let Some(editor) = self.editor_tab.current_editor_mut() {
+                if let Some(editor) = self.editor_layout.current_editor_mut() {
                     editor
                         .reload_contents()
                         .map(|_| Action::Handled)
@@ -321,14 +770,108 @@ impl<'a> Component for App<'a> {
                 self.explorer.component_state.toggle_visible();
                 Ok(Action::Handled)
             }
+            Action::ShowHideOutline => {
+                self.outline.component_state.toggle_visible();
+                Ok(Action::Handled)
+            }
             Action::ShowHideAbout => {
                 self.about = !self.about;
                 Ok(Action::Handled)
             }
+            Action::CloseAllTabs
+            | Action::CloseOtherTabs
+            | Action::CloseTabsToRight
+            | Action::TogglePinTab
+            | Action::NextTab
+            | Action::PreviousTab
+            | Action::SplitEditor(_) => self.editor_layout.update(action),
+            Action::SwapExplorerSide => {
+                self.layout.toggle_explorer_side();
+                self.layout.save();
+                Ok(Action::Handled)
+            }
+            Action::OpenFile(path) => {
+                // Relative paths, such as those given to the `:e` command, start at the project root.
+                let path = self.explorer.root_path.join(path);
+                self.open_file(&path)?;
+                self.change_focus(AppEditor);
+                Ok(Action::Handled)
+            }
+            Action::OpenProject(path) => {
+                self.open_project(&path)?;
+                self.change_focus(AppExplorer);
+                Ok(Action::Handled)
+            }
+            Action::OpenFileAt(path, row, col) => self.open_file_at(&path, row, col),
+            Action::CompareFiles(old, new) => {
+                if let Err(e) = self.compare_files(&old, &new) {
+                    error!(target:Self::ID, "Failed to compare {old:?} with {new:?}: {e:#}");
+                }
+                Ok(Action::Handled)
+            }
+            Action::CompareBuffer(path) => {
+                if let Err(e) = self.compare_buffer(path) {
+                    error!(target:Self::ID, "Failed to compare the current buffer: {e:#}");
+                }
+                Ok(Action::Handled)
+            }
+            Action::ToggleBreakpoint(path, row) => {
+                if let Err(e) = self.debugger.toggle_breakpoint(path, row) {
+                    error!(target:Debugger::ID, "Failed to send breakpoints: {e:#}");
+                }
+                Ok(Action::Handled)
+            }
+            Action::Debug(command) => self.debug(command),
+            Action::ShowHideDebugger => {
+                self.debug_panel.component_state.toggle_visible();
+                Ok(Action::Handled)
+            }
+            Action::GoToPosition(row, col) => match self.editor_layout.current_editor_mut() {
+                None => {
+                    error!(target:Self::ID, "Failed to get current editor while handling App Action::GoToPosition");
+                    Ok(Action::Noop)
+                }
+                Some(editor) => {
+                    editor.goto(row, col);
+                    self.change_focus(AppEditor);
+                    Ok(Action::Handled)
+                }
+            },
             _ => Ok(Action::Noop),
         }
     }
 
+    /// Clicking a component gives it focus before the event is passed on to it.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        if let MouseEventKind::Down(_) = mouse.kind {
+            // Clicking anywhere while the About page is open hides it.
+            if self.about {
+                self.about = false;
+                return Ok(Action::Handled);
+            }
+        }
+        let Some(target) = self.component_at(Position::new(mouse.column, mouse.row)) else {
+            return Ok(Action::Noop);
+        };
+        if let MouseEventKind::Down(_) = mouse.kind {
+            if target != AppMenuBar {
+                self.menu_bar.close_menus();
+            }
+            if target != self.last_active {
+                self.change_focus(target);
+            }
+        }
+        let event = Event::Mouse(mouse);
+        match target {
+            AppEditor => self.editor_layout.handle_event(event),
+            AppExplorer => self.explorer.handle_event(event),
+            AppLogger => self.logger.handle_event(event),
+            AppMenuBar => self.menu_bar.handle_event(event),
+            AppOutline => self.outline.handle_event(event),
+            AppDebugger => self.debug_panel.handle_event(event),
+        }
+    }
+
     /// Handles key events for the App Component only.
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
         match key.code {
@@ -342,6 +885,27 @@ impl<'a> Component for App<'a> {
             _ => {}
         }
 
+        if let Some(command) = Self::debug_key(key) {
+            return self.debug(command);
+        }
+
+        // Panel resizing. Braces may be reported with or without the SHIFT modifier.
+        if key.modifiers.contains(KeyModifiers::ALT) && key.kind == KeyEventKind::Press {
+            let (explorer, logger) = match key.code {
+                KeyCode::Char('[') => (-Self::RESIZE_STEP, 0),
+                KeyCode::Char(']') => (Self::RESIZE_STEP, 0),
+                KeyCode::Char('{') => (0, -Self::RESIZE_STEP),
+                KeyCode::Char('}') => (0, Self::RESIZE_STEP),
+                _ => (0, 0),
+            };
+            if explorer != 0 || logger != 0 {
+                self.layout.resize_explorer(explorer);
+                self.layout.resize_logger(logger);
+                self.layout.save();
+                return Ok(Action::Handled);
+            }
+        }
+
         match key {
             KeyEvent {
                 code: KeyCode::Char('q'),
@@ -380,11 +944,46 @@ impl<'a> Component for App<'a> {
                 Ok(Action::Handled)
             }
             KeyEvent {
-                code: KeyCode::Char('c'),
+                code: KeyCode::Char('t'),
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                state: _state,
+            } => {
+                self.change_focus(AppOutline);
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('d'),
+                modifiers: KeyModifiers::ALT,
+                kind: KeyEventKind::Press,
+                state: _state,
+            } => {
+                self.change_focus(AppDebugger);
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('t'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                state: _state,
+            } => {
+                self.symbol_search.open();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('q'),
                 modifiers: KeyModifiers::CONTROL,
                 kind: KeyEventKind::Press,
                 state: _state,
             } => Ok(Action::Quit),
+            // CTRL+C copies text in the standard keymap, so it only quits when the editor is
+            // not focused or is using vim key bindings.
+            KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                state: _state,
+            } if !self.editor_uses_standard_keymap() => Ok(Action::Quit),
             _ => Ok(Action::Noop),
         }
     }
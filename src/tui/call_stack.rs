@@ -0,0 +1,125 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::debugger::{DebugCommand, DebugState, Debugger};
+use crate::tui::component::{Action, Component, ComponentState, Focus};
+use crate::tui::debug_panel::DebugPanel;
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+
+/// The frames of the stopped thread. Selecting a frame shows its line in the editor and its
+/// variables.
+pub struct CallStack {
+    list_state: ListState,
+    /// The number of frames and the selected frame from the last frame drawn.
+    frames: usize,
+    selected: usize,
+    area: Rect,
+    pub(crate) component_state: ComponentState,
+}
+
+impl CallStack {
+    pub const ID: &str = "CallStack";
+
+    pub const HELP: &str = "(↑/k)/(↓/j): Select frame | Enter: Show frame in editor";
+
+    pub fn new() -> Self {
+        trace!(target:Self::ID, "Building {}", Self::ID);
+        Self {
+            list_state: ListState::default(),
+            frames: 0,
+            selected: 0,
+            area: Rect::default(),
+            component_state: ComponentState::default(),
+        }
+    }
+
+    fn select(&self, index: usize) -> Action {
+        match index < self.frames {
+            true => Action::Debug(DebugCommand::SelectFrame(index)),
+            false => Action::Handled,
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, debugger: &Debugger) {
+        self.area = area;
+        self.frames = debugger.frames().len();
+        self.selected = debugger.selected_frame();
+        self.list_state
+            .select((self.frames > 0).then_some(self.selected));
+        let title = match debugger.state() {
+            None => "Call stack",
+            Some(DebugState::Starting) => "Call stack (starting)",
+            Some(DebugState::Running) => "Call stack (running)",
+            Some(DebugState::Stopped) => "Call stack (stopped)",
+        };
+        let items: Vec<ListItem> = debugger
+            .frames()
+            .iter()
+            .map(|frame| {
+                let location = match &frame.path {
+                    Some(path) => {
+                        let name = path.file_name().unwrap_or_default().to_string_lossy();
+                        format!("  {name}:{}", frame.row + 1)
+                    }
+                    None => "  (no source)".to_string(),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(frame.name.as_str()),
+                    Span::styled(location, Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+        StatefulWidget::render(
+            List::new(items)
+                .block(DebugPanel::pane_block(
+                    title.to_string(),
+                    &self.component_state,
+                ))
+                .highlight_style(DebugPanel::HIGHLIGHT_STYLE),
+            area,
+            buf,
+            &mut self.list_state,
+        );
+    }
+}
+
+impl Component for CallStack {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => Ok(self.select(self.selected.saturating_sub(1))),
+            KeyCode::Down | KeyCode::Char('j') => Ok(self.select(self.selected + 1)),
+            KeyCode::Home => Ok(self.select(0)),
+            KeyCode::End => Ok(self.select(self.frames.saturating_sub(1))),
+            KeyCode::Enter => Ok(self.select(self.selected)),
+            _ => Ok(Action::Noop),
+        }
+    }
+
+    /// Clicking a frame selects it.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.list_state.scroll_down_by(1),
+            MouseEventKind::ScrollUp => self.list_state.scroll_up_by(1),
+            MouseEventKind::Down(_) => {
+                return match DebugPanel::list_row_at(self.area, &self.list_state, mouse) {
+                    Some(row) => Ok(self.select(row)),
+                    None => Ok(Action::Handled),
+                };
+            }
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    fn is_active(&self) -> bool {
+        self.component_state.focus == Focus::Active
+    }
+}
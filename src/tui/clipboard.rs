@@ -0,0 +1,107 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use edtui::clipboard::ClipboardTrait;
+use log::{debug, error, warn};
+use std::cell::RefCell;
+use std::env;
+use std::io::{Write, stdout};
+use std::sync::Mutex;
+
+/// The most recently copied text, used when the system clipboard can't be read.
+static LAST_COPIED: Mutex<String> = Mutex::new(String::new());
+
+thread_local! {
+    /// The system clipboard shared by every editor, which is opened the first time it is used.
+    static SYSTEM: RefCell<Option<arboard::Clipboard>> = RefCell::new(Clipboard::open_system());
+}
+
+/// Copies and pastes using the system clipboard.
+///
+/// When the system clipboard is unavailable, or clide is running over SSH where the system
+/// clipboard belongs to the remote machine, copied text is also sent to the terminal using an
+/// OSC 52 escape sequence so it reaches the clipboard of the local machine. Terminals rarely allow
+/// reading the clipboard this way, so pasting then uses the text most recently copied in clide.
+#[derive(Default, Clone, Copy)]
+pub struct Clipboard;
+
+impl Clipboard {
+    pub const ID: &str = "Clipboard";
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn open_system() -> Option<arboard::Clipboard> {
+        match arboard::Clipboard::new() {
+            Ok(clipboard) => Some(clipboard),
+            Err(e) => {
+                debug!(target:Self::ID, "System clipboard is unavailable; Using OSC 52: {e}");
+                None
+            }
+        }
+    }
+
+    fn is_ssh() -> bool {
+        env::var_os("SSH_TTY").is_some() || env::var_os("SSH_CONNECTION").is_some()
+    }
+
+    pub fn copy(&mut self, text: &str) {
+        match LAST_COPIED.lock() {
+            Ok(mut last) => *last = text.to_string(),
+            Err(e) => error!(target:Self::ID, "Failed to store copied text: {e}"),
+        }
+        let copied = SYSTEM.with_borrow_mut(|system| match system {
+            Some(clipboard) => match clipboard.set_text(text) {
+                Ok(_) => true,
+                Err(e) => {
+                    warn!(target:Self::ID, "Failed to copy to the system clipboard: {e}");
+                    false
+                }
+            },
+            None => false,
+        });
+        if !copied || Self::is_ssh() {
+            Self::copy_osc52(text);
+        }
+    }
+
+    pub fn paste(&mut self) -> String {
+        if !Self::is_ssh()
+            && let Some(text) =
+                SYSTEM.with_borrow_mut(|system| system.as_mut().and_then(|c| c.get_text().ok()))
+        {
+            return text;
+        }
+        LAST_COPIED
+            .lock()
+            .map(|last| last.clone())
+            .unwrap_or_default()
+    }
+
+    /// Asks the terminal to copy text to the clipboard of the machine it is running on.
+    fn copy_osc52(text: &str) {
+        let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+        let mut stdout = stdout();
+        if let Err(e) = stdout
+            .write_all(sequence.as_bytes())
+            .and_then(|_| stdout.flush())
+        {
+            error!(target:Self::ID, "Failed to copy using OSC 52: {e}");
+        }
+    }
+}
+
+/// Vim yank and put in the EditorView use the same clipboard as clide.
+impl ClipboardTrait for Clipboard {
+    fn set_text(&mut self, text: String) {
+        self.copy(&text);
+    }
+
+    fn get_text(&mut self) -> String {
+        self.paste()
+    }
+}
@@ -0,0 +1,155 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use anyhow::{Result, bail};
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Paragraph, Widget};
+use std::path::PathBuf;
+
+/// The ex-commands supported by the vim command line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Write,
+    /// Close the current tab.
+    Quit,
+    WriteQuit,
+    /// Exit clide.
+    QuitAll,
+    /// Open a file, or reload the current file if no path is given.
+    Edit(Option<PathBuf>),
+    TabNext,
+    TabPrevious,
+    /// Close the current tab, as `:bd` deletes the current buffer in vim.
+    BufferDelete,
+    Split,
+    VerticalSplit,
+    /// Move the cursor to a line number, starting from 1.
+    GotoLine(usize),
+    /// Set the language of the buffer, or detect it again from the file if no language is given.
+    SetLanguage(Option<String>),
+    /// Compare the buffer with a file, or with its file on disk if no path is given.
+    Diff(Option<PathBuf>),
+}
+
+impl Command {
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (name, argument) = match input.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, Some(argument.trim())),
+            None => (input, None),
+        };
+        if let Ok(line) = name.parse::<usize>() {
+            return Ok(Command::GotoLine(line));
+        }
+        let command = match name {
+            "w" | "write" => Command::Write,
+            "q" | "quit" | "q!" | "quit!" | "close" => Command::Quit,
+            "wq" | "x" | "xit" => Command::WriteQuit,
+            "qa" | "qall" | "qa!" | "qall!" | "wqa" | "xa" => Command::QuitAll,
+            "e" | "edit" | "e!" | "edit!" | "tabe" | "tabedit" | "tabnew" => {
+                Command::Edit(argument.filter(|a| !a.is_empty()).map(PathBuf::from))
+            }
+            "tabn" | "tabnext" | "bn" | "bnext" => Command::TabNext,
+            "tabp" | "tabprevious" | "tabN" | "tabNext" | "bp" | "bprevious" => {
+                Command::TabPrevious
+            }
+            "bd" | "bdelete" | "bd!" | "tabc" | "tabclose" => Command::BufferDelete,
+            "diff" | "diffs" | "diffsplit" => {
+                Command::Diff(argument.filter(|a| !a.is_empty()).map(PathBuf::from))
+            }
+            "sp" | "split" => Command::Split,
+            "vs" | "vsp" | "vsplit" => Command::VerticalSplit,
+            "setf" | "setfiletype" => {
+                Command::SetLanguage(argument.filter(|a| !a.is_empty()).map(String::from))
+            }
+            "se" | "set" => match argument.and_then(|a| a.split_once('=')) {
+                Some(("ft" | "filetype" | "syntax", language)) => Command::SetLanguage(
+                    Some(language.trim())
+                        .filter(|l| !l.is_empty())
+                        .map(String::from),
+                ),
+                _ => bail!("Unsupported option: {input}"),
+            },
+            _ => bail!("Not an editor command: {input}"),
+        };
+        Ok(command)
+    }
+}
+
+/// The result of input to the CommandLine that the Editor needs to act on.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandLineEvent {
+    Submit(String),
+    Close,
+    Handled,
+}
+
+/// The `:` command line for vim key bindings, drawn on the last row of an Editor.
+/// After a command runs, the row shows any error until the next key is pressed.
+#[derive(Debug, Default)]
+pub struct CommandLine {
+    input: String,
+    visible: bool,
+    message: Option<String>,
+}
+
+impl CommandLine {
+    pub const ID: &str = "CommandLine";
+
+    pub const HELP: &str = "ENTER: Run command | ESC: Cancel";
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.input.clear();
+        self.message = None;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn set_message(&mut self, message: String) {
+        self.message = Some(message);
+    }
+
+    pub fn clear_message(&mut self) {
+        self.message = None;
+    }
+
+    pub fn handle_key_events(&mut self, key: KeyEvent) -> CommandLineEvent {
+        match key.code {
+            KeyCode::Esc => CommandLineEvent::Close,
+            KeyCode::Enter => CommandLineEvent::Submit(self.input.clone()),
+            // Deleting past the start of the command line closes it, as it does in vim.
+            KeyCode::Backspace if self.input.is_empty() => CommandLineEvent::Close,
+            KeyCode::Backspace => {
+                self.input.pop();
+                CommandLineEvent::Handled
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                CommandLineEvent::Handled
+            }
+            _ => CommandLineEvent::Handled,
+        }
+    }
+}
+
+impl Widget for &CommandLine {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (text, style) = match (&self.message, self.visible) {
+            (_, true) => (format!(":{}", self.input), Style::default()),
+            (Some(message), false) => (message.to_owned(), Style::default().fg(Color::Red)),
+            (None, false) => return,
+        };
+        Paragraph::new(text).style(style).render(area, buf);
+    }
+}
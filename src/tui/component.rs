@@ -4,12 +4,15 @@
 
 #![allow(dead_code, unused_variables)]
 
+use crate::debugger::DebugCommand;
 use crate::tui::component::Focus::Inactive;
 use Focus::Active;
 use anyhow::Result;
 use log::trace;
 use ratatui::crossterm::event::{Event, KeyEvent, MouseEvent};
+use ratatui::layout::Direction;
 use ratatui::style::Color;
+use std::path::PathBuf;
 
 pub enum Action {
     /// Exit the application.
@@ -25,14 +28,69 @@ pub enum Action {
     /// Save the current file.
     Save,
 
+    /// Save the current file, then close its tab if it was saved.
+    SaveAndCloseTab,
+
     /// The input was handled by a Component and should not be passed to the next component.
     Handled,
     OpenTab,
     ReloadFile,
     ShowHideExplorer,
     ShowHideLogger,
+    ShowHideOutline,
     ShowHideAbout,
     CloseTab,
+
+    /// Close every unpinned tab in the focused editor pane.
+    CloseAllTabs,
+
+    /// Close every unpinned tab except the current tab.
+    CloseOtherTabs,
+
+    /// Close every unpinned tab to the right of the current tab.
+    CloseTabsToRight,
+
+    /// Pin or unpin the current tab.
+    TogglePinTab,
+
+    /// Select the next tab in the focused editor pane.
+    NextTab,
+
+    /// Select the previous tab in the focused editor pane.
+    PreviousTab,
+
+    /// Split the focused editor pane. A horizontal split places the new pane to the right.
+    SplitEditor(Direction),
+
+    /// Move the Explorer to the opposite side of the editor.
+    SwapExplorerSide,
+
+    /// Open a file in a new editor tab.
+    OpenFile(PathBuf),
+
+    /// Open a directory as the root of the project Explorer.
+    OpenProject(PathBuf),
+
+    /// Open a file in an editor tab and move the cursor to a row and column.
+    OpenFileAt(PathBuf, usize, usize),
+
+    /// Move the cursor of the current editor to a row and column, and focus the editor.
+    GoToPosition(usize, usize),
+
+    /// Compare two files on disk.
+    CompareFiles(PathBuf, PathBuf),
+
+    /// Compare the current buffer with a file, or with its own file on disk if no path is given.
+    /// Files open in a tab are compared using the contents of their buffer.
+    CompareBuffer(Option<PathBuf>),
+
+    /// Add or remove a breakpoint on a row of a file.
+    ToggleBreakpoint(PathBuf, usize),
+
+    /// Run a command for the debugger, such as stepping or starting the program.
+    Debug(DebugCommand),
+
+    ShowHideDebugger,
 }
 
 pub trait Component {
@@ -61,6 +119,20 @@ pub trait Component {
     }
 }
 
+/// Finds the index of the tab under `column` for a ratatui Tabs widget rendered starting at `x`
+/// with the default one-column padding on each side of a title and a one-column divider.
+pub fn tab_at(title_widths: impl IntoIterator<Item = usize>, x: u16, column: u16) -> Option<usize> {
+    let mut start = x as usize;
+    for (i, width) in title_widths.into_iter().enumerate() {
+        let end = start + width + 2;
+        if (start..end).contains(&(column as usize)) {
+            return Some(i);
+        }
+        start = end + 1;
+    }
+    None
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ComponentState {
     pub(crate) focus: Focus,
@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::debugger::{ConsoleKind, DebugCommand, Debugger};
+use crate::tui::component::{Action, Component, ComponentState, Focus};
+use crate::tui::debug_panel::DebugPanel;
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Widget};
+
+/// The output of the program and the debug adapter, with a prompt for evaluating expressions in
+/// the selected frame.
+pub struct DebugConsole {
+    input: String,
+    /// The expressions entered before, and the one being recalled with the arrow keys.
+    history: Vec<String>,
+    history_index: Option<usize>,
+    /// The number of lines scrolled up from the end of the output.
+    scroll: usize,
+    /// The number of output lines and the height they were drawn in during the last frame.
+    lines: usize,
+    height: usize,
+    pub(crate) component_state: ComponentState,
+}
+
+impl DebugConsole {
+    pub const ID: &str = "DebugConsole";
+
+    pub const HELP: &str = concat!(
+        "Type to evaluate an expression | Enter: Evaluate | (↑/↓): History |",
+        " PageUp/PageDown: Scroll"
+    );
+
+    pub fn new() -> Self {
+        trace!(target:Self::ID, "Building {}", Self::ID);
+        Self {
+            input: String::new(),
+            history: Vec::new(),
+            history_index: None,
+            scroll: 0,
+            lines: 0,
+            height: 0,
+            component_state: ComponentState::default(),
+        }
+    }
+
+    /// Adds pasted text to the prompt, joining lines since expressions are evaluated one line at
+    /// a time.
+    pub fn paste(&mut self, text: &str) {
+        self.input
+            .push_str(&text.lines().collect::<Vec<_>>().join(" "));
+    }
+
+    fn scroll_by(&mut self, lines: isize) {
+        let max = self.lines.saturating_sub(self.height);
+        self.scroll = self.scroll.saturating_add_signed(lines).min(max);
+    }
+
+    fn recall(&mut self, previous: bool) {
+        let index = match (self.history_index, previous) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => (index + 1 < self.history.len()).then_some(index + 1),
+        };
+        self.history_index = index;
+        self.input = index
+            .map(|index| self.history[index].clone())
+            .unwrap_or_default();
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, debugger: &Debugger) {
+        let block = DebugPanel::pane_block("Debug console".to_string(), &self.component_state);
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [output_area, input_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+
+        let console = debugger.console();
+        self.lines = console.len();
+        self.height = output_area.height as usize;
+        self.scroll = self.scroll.min(self.lines.saturating_sub(self.height));
+        let end = self.lines - self.scroll;
+        let start = end.saturating_sub(self.height);
+        let lines: Vec<Line> = console[start..end]
+            .iter()
+            .map(|line| {
+                let style = match line.kind {
+                    ConsoleKind::Output => Style::default(),
+                    ConsoleKind::Error => Style::default().fg(Color::Red),
+                    ConsoleKind::Input => Style::default().fg(Color::DarkGray),
+                    ConsoleKind::Result => Style::default().fg(Color::Cyan),
+                };
+                Line::styled(line.text.as_str(), style)
+            })
+            .collect();
+        Paragraph::new(lines).render(output_area, buf);
+        let cursor = match self.is_active() {
+            true => "█",
+            false => "",
+        };
+        Paragraph::new(format!("> {}{cursor}", self.input))
+            .style(Style::default().fg(Color::Yellow))
+            .render(input_area, buf);
+    }
+}
+
+impl Component for DebugConsole {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Enter => {
+                let expression = self.input.trim().to_string();
+                self.input.clear();
+                self.history_index = None;
+                self.scroll = 0;
+                if expression.is_empty() {
+                    return Ok(Action::Handled);
+                }
+                if self.history.last() != Some(&expression) {
+                    self.history.push(expression.clone());
+                }
+                return Ok(Action::Debug(DebugCommand::Evaluate(expression)));
+            }
+            KeyCode::Esc => self.input.clear(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Up => self.recall(true),
+            KeyCode::Down => self.recall(false),
+            KeyCode::PageUp => self.scroll_by(self.height.max(1) as isize),
+            KeyCode::PageDown => self.scroll_by(-(self.height.max(1) as isize)),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.push(c)
+            }
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_by(1),
+            MouseEventKind::ScrollDown => self.scroll_by(-1),
+            MouseEventKind::Down(_) => {}
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    fn is_active(&self) -> bool {
+        self.component_state.focus == Focus::Active
+    }
+}
@@ -0,0 +1,211 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::debugger::Debugger;
+use crate::tui::call_stack::CallStack;
+use crate::tui::component::{
+    Action, Component, ComponentState, Focus, FocusState, Visibility, VisibleState,
+};
+use crate::tui::debug_console::DebugConsole;
+use crate::tui::variables::Variables;
+use crate::tui::watches::Watches;
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, ListState};
+
+/// The panes of the debug panel, in the order TAB moves between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DebugPane {
+    CallStack,
+    Variables,
+    Watches,
+    Console,
+}
+
+impl DebugPane {
+    const ALL: [DebugPane; 4] = [
+        Self::CallStack,
+        Self::Variables,
+        Self::Watches,
+        Self::Console,
+    ];
+
+    fn next(self, forward: bool) -> Self {
+        let index = Self::ALL.iter().position(|pane| *pane == self).unwrap_or(0);
+        let count = Self::ALL.len();
+        match forward {
+            true => Self::ALL[(index + 1) % count],
+            false => Self::ALL[(index + count - 1) % count],
+        }
+    }
+}
+
+/// The call stack, variables, watch expressions and console of the debugger, shown below the
+/// editor while debugging.
+pub struct DebugPanel {
+    call_stack: CallStack,
+    variables: Variables,
+    watches: Watches,
+    console: DebugConsole,
+    focused: DebugPane,
+    pub(crate) component_state: ComponentState,
+    /// The areas of each pane drawn in the last frame, used to find the pane under the mouse.
+    areas: Vec<(DebugPane, Rect)>,
+}
+
+impl DebugPanel {
+    pub const ID: &str = "DebugPanel";
+
+    const HELP: &str = concat!(
+        "F5: Start / continue | SHIFT+F5: Stop | F6: Pause | F10: Step over | F11: Step in |",
+        " SHIFT+F11: Step out | (TAB/SHIFT+TAB): Next / previous pane | "
+    );
+
+    pub const HIGHLIGHT_STYLE: Style = Style::new()
+        .fg(Color::Black)
+        .bg(Color::Rgb(57, 59, 64))
+        .add_modifier(Modifier::BOLD);
+
+    pub fn new() -> Self {
+        trace!(target:Self::ID, "Building {}", Self::ID);
+        let mut panel = Self {
+            call_stack: CallStack::new(),
+            variables: Variables::new(),
+            watches: Watches::new(),
+            console: DebugConsole::new(),
+            focused: DebugPane::CallStack,
+            component_state: ComponentState::default().with_visible(Visibility::Hidden),
+            areas: Vec::new(),
+        };
+        panel.focus_pane(DebugPane::CallStack);
+        panel
+    }
+
+    /// The bordered block drawn around each pane, titled like the other panels.
+    pub fn pane_block(title: String, component_state: &ComponentState) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(component_state.get_active_color()))
+            .title_style(Style::default().fg(Color::Green))
+            .title_alignment(Alignment::Center)
+    }
+
+    /// Finds the row of a bordered list under the mouse.
+    pub fn list_row_at(area: Rect, state: &ListState, mouse: MouseEvent) -> Option<usize> {
+        let inner_top = area.y + 1;
+        (mouse.row >= inner_top && mouse.row < area.bottom().saturating_sub(1))
+            .then(|| (mouse.row - inner_top) as usize + state.offset())
+    }
+
+    fn focus_pane(&mut self, pane: DebugPane) {
+        self.focused = pane;
+        let help = match pane {
+            DebugPane::CallStack => CallStack::HELP,
+            DebugPane::Variables => Variables::HELP,
+            DebugPane::Watches => Watches::HELP,
+            DebugPane::Console => DebugConsole::HELP,
+        };
+        self.component_state.help_text = format!("{}{help}", Self::HELP);
+    }
+
+    fn pane_state(&mut self, pane: DebugPane) -> &mut ComponentState {
+        match pane {
+            DebugPane::CallStack => &mut self.call_stack.component_state,
+            DebugPane::Variables => &mut self.variables.component_state,
+            DebugPane::Watches => &mut self.watches.component_state,
+            DebugPane::Console => &mut self.console.component_state,
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, debugger: &Debugger) {
+        // Only the focused pane is highlighted, while the panel has focus.
+        let active = self.is_active();
+        for pane in DebugPane::ALL {
+            let focus = match active && pane == self.focused {
+                true => Focus::Active,
+                false => Focus::Inactive,
+            };
+            self.pane_state(pane).set_focus(focus);
+        }
+        let [left, variables_area, console_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [call_stack_area, watches_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(left);
+        self.call_stack.render(call_stack_area, buf, debugger);
+        self.variables.render(variables_area, buf, debugger);
+        self.watches.render(watches_area, buf, debugger);
+        self.console.render(console_area, buf, debugger);
+        self.areas = vec![
+            (DebugPane::CallStack, call_stack_area),
+            (DebugPane::Variables, variables_area),
+            (DebugPane::Watches, watches_area),
+            (DebugPane::Console, console_area),
+        ];
+    }
+}
+
+impl Component for DebugPanel {
+    fn handle_event(&mut self, event: Event) -> Result<Action> {
+        match event {
+            Event::Key(key) => self.handle_key_events(key),
+            Event::Mouse(mouse) => self.handle_mouse_events(mouse),
+            Event::Paste(text) if self.focused == DebugPane::Console => {
+                self.console.paste(&text);
+                Ok(Action::Handled)
+            }
+            _ => Ok(Action::Noop),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Tab => self.focus_pane(self.focused.next(true)),
+            KeyCode::BackTab => self.focus_pane(self.focused.next(false)),
+            _ => {
+                return match self.focused {
+                    DebugPane::CallStack => self.call_stack.handle_key_events(key),
+                    DebugPane::Variables => self.variables.handle_key_events(key),
+                    DebugPane::Watches => self.watches.handle_key_events(key),
+                    DebugPane::Console => self.console.handle_key_events(key),
+                };
+            }
+        }
+        Ok(Action::Handled)
+    }
+
+    /// Clicking a pane focuses it before the event is passed on to it.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        let position = Position::new(mouse.column, mouse.row);
+        let Some(pane) = self
+            .areas
+            .iter()
+            .find(|(_, area)| area.contains(position))
+            .map(|(pane, _)| *pane)
+        else {
+            return Ok(Action::Noop);
+        };
+        if let MouseEventKind::Down(_) = mouse.kind {
+            self.focus_pane(pane);
+        }
+        match pane {
+            DebugPane::CallStack => self.call_stack.handle_mouse_events(mouse),
+            DebugPane::Variables => self.variables.handle_mouse_events(mouse),
+            DebugPane::Watches => self.watches.handle_mouse_events(mouse),
+            DebugPane::Console => self.console.handle_mouse_events(mouse),
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        self.component_state.focus == Focus::Active
+    }
+}
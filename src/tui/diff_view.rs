@@ -0,0 +1,258 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::diff::{self, Change, Diff, DiffLine};
+use crate::tui::component::{Action, Component};
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Widget};
+
+/// A view comparing two texts over the editor area, with the old and new text side by side or
+/// with the changed lines of both inline.
+#[derive(Debug, Default)]
+pub struct DiffView {
+    visible: bool,
+    old_title: String,
+    new_title: String,
+    diff: Diff,
+    inline: bool,
+    /// The first row shown.
+    scroll: usize,
+    /// The index of the selected change in the list of hunks.
+    hunk: usize,
+    /// The number of rows drawn in the last frame, used to scroll by a page.
+    height: usize,
+}
+
+impl DiffView {
+    pub const ID: &str = "DiffView";
+
+    pub const HELP: &str = concat!(
+        "ESC: Close diff | TAB: Side by side / inline | (n/]): Next change | (N/[): Previous change |",
+        " (↑/k)/(↓/j): Scroll | PgUp/PgDn: Scroll page | g/G: Top / bottom"
+    );
+
+    /// The unchanged rows shown above a change selected with next or previous.
+    const CONTEXT: usize = 3;
+
+    const DELETE_STYLE: Style = Style::new().bg(Color::Rgb(0x3f, 0x1f, 0x22));
+    const DELETE_EMPHASIS_STYLE: Style = Style::new().bg(Color::Rgb(0x8f, 0x3a, 0x3a));
+    const INSERT_STYLE: Style = Style::new().bg(Color::Rgb(0x1f, 0x3a, 0x26));
+    const INSERT_EMPHASIS_STYLE: Style = Style::new().bg(Color::Rgb(0x2f, 0x7a, 0x45));
+    const GUTTER_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Compares two texts, scrolling to the first change.
+    pub fn open(&mut self, old_title: String, old: &str, new_title: String, new: &str) {
+        trace!(target:Self::ID, "Comparing {old_title} with {new_title}");
+        self.diff = Diff::new(old, new);
+        self.old_title = old_title;
+        self.new_title = new_title;
+        self.visible = true;
+        self.goto_hunk(0);
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.diff = Diff::default();
+    }
+
+    /// The lines shown on each row, as indices into the lines of the diff. Inline diffs show a
+    /// single line on each row.
+    fn rows(&self) -> Vec<[Option<usize>; 2]> {
+        match self.inline {
+            true => (0..self.diff.lines.len())
+                .map(|i| [Some(i), None])
+                .collect(),
+            false => self.diff.side_by_side(),
+        }
+    }
+
+    fn hunk_starts(&self, rows: &[[Option<usize>; 2]]) -> Vec<usize> {
+        diff::hunk_starts(rows.iter().map(|row| {
+            row.iter()
+                .flatten()
+                .any(|i| self.diff.lines[*i].is_change())
+        }))
+    }
+
+    /// Selects a change and scrolls to it.
+    fn goto_hunk(&mut self, hunk: usize) {
+        let rows = self.rows();
+        let starts = self.hunk_starts(&rows);
+        self.hunk = hunk.min(starts.len().saturating_sub(1));
+        self.scroll = starts
+            .get(self.hunk)
+            .map_or(0, |start| start.saturating_sub(Self::CONTEXT));
+    }
+
+    fn scroll_by(&mut self, rows: isize) {
+        let last = self.rows().len().saturating_sub(self.height.max(1));
+        self.scroll = self.scroll.saturating_add_signed(rows).min(last);
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let changes = match self.diff.hunk_count() {
+            0 => " No changes ".to_string(),
+            1 => " 1 change ".to_string(),
+            count => format!(" Change {} of {count} ", self.hunk + 1),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ↔ {} ", self.old_title, self.new_title))
+            .title_bottom(Line::from(changes).right_aligned())
+            .border_style(Style::default().fg(Color::LightYellow));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        self.height = inner.height as usize;
+
+        let rows = self.rows();
+        let current = self.hunk_starts(&rows).get(self.hunk).copied();
+        let number_width = self.diff.lines.len().to_string().len();
+        let [old_area, divider, new_area] = match self.inline {
+            true => [inner, Rect::default(), Rect::default()],
+            false => Layout::horizontal([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .areas(inner),
+        };
+        for (y, (index, row)) in rows
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(inner.height as usize)
+            .enumerate()
+        {
+            let y = inner.y + y as u16;
+            let marker = match Some(index) == current {
+                true => Span::styled("▶", Style::default().fg(Color::Yellow)),
+                false => Span::raw(" "),
+            };
+            match self.inline {
+                true => {
+                    let line = row[0].map(|i| &self.diff.lines[i]);
+                    let numbers = line.map_or(String::new(), |line| {
+                        format!(
+                            "{:>number_width$} {:>number_width$} ",
+                            line.old_row
+                                .map_or(String::new(), |row| (row + 1).to_string()),
+                            line.new_row
+                                .map_or(String::new(), |row| (row + 1).to_string()),
+                        )
+                    });
+                    let sign = match line.map(|line| line.change) {
+                        Some(Change::Delete) => "-",
+                        Some(Change::Insert) => "+",
+                        _ => " ",
+                    };
+                    Self::render_line(
+                        old_area,
+                        y,
+                        buf,
+                        vec![marker, Span::styled(numbers + sign, Self::GUTTER_STYLE)],
+                        line,
+                    );
+                }
+                false => {
+                    for (side, area) in [(0, old_area), (1, new_area)] {
+                        let line = row[side].map(|i| &self.diff.lines[i]);
+                        let number = line
+                            .and_then(|line| [line.old_row, line.new_row][side])
+                            .map_or(String::new(), |row| (row + 1).to_string());
+                        let gutter = vec![
+                            match side {
+                                0 => marker.clone(),
+                                _ => Span::raw(" "),
+                            },
+                            Span::styled(format!("{number:>number_width$} "), Self::GUTTER_STYLE),
+                        ];
+                        Self::render_line(area, y, buf, gutter, line);
+                    }
+                    buf.set_string(divider.x, y, "│", Self::GUTTER_STYLE);
+                }
+            }
+        }
+    }
+
+    /// Draws one line of the diff, or a filler where the other side has a line with no match.
+    fn render_line(
+        area: Rect,
+        y: u16,
+        buf: &mut Buffer,
+        mut spans: Vec<Span>,
+        line: Option<&DiffLine>,
+    ) {
+        let row = Rect::new(area.x, y, area.width, 1);
+        let Some(line) = line else {
+            let width: usize = spans.iter().map(Span::width).sum();
+            let filler = "╱".repeat((area.width as usize).saturating_sub(width));
+            spans.push(Span::styled(
+                filler,
+                Style::default().fg(Color::Rgb(0x44, 0x44, 0x4c)),
+            ));
+            buf.set_line(row.x, row.y, &Line::from(spans), row.width);
+            return;
+        };
+        let (style, emphasis) = match line.change {
+            Change::Equal => (Style::default(), Style::default()),
+            Change::Delete => (Self::DELETE_STYLE, Self::DELETE_EMPHASIS_STYLE),
+            Change::Insert => (Self::INSERT_STYLE, Self::INSERT_EMPHASIS_STYLE),
+        };
+        buf.set_style(row, style);
+        for (emphasized, text) in &line.segments {
+            spans.push(Span::styled(
+                text.replace('\t', "    "),
+                match emphasized {
+                    true => emphasis,
+                    false => style,
+                },
+            ));
+        }
+        buf.set_line(row.x, row.y, &Line::from(spans), row.width);
+    }
+}
+
+impl Component for DiffView {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        let page = self.height.max(1) as isize;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.close(),
+            KeyCode::Tab | KeyCode::Char('i') => {
+                self.inline = !self.inline;
+                self.goto_hunk(self.hunk);
+            }
+            KeyCode::Char('n') | KeyCode::Char(']') => self.goto_hunk(self.hunk + 1),
+            KeyCode::Char('N') | KeyCode::Char('[') => self.goto_hunk(self.hunk.saturating_sub(1)),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll_by(isize::MAX),
+            _ => {}
+        }
+        // The diff takes all input while it is open.
+        Ok(Action::Handled)
+    }
+
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll_by(3),
+            MouseEventKind::ScrollUp => self.scroll_by(-3),
+            _ => {}
+        }
+        Ok(Action::Handled)
+    }
+}
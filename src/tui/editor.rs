@@ -2,42 +2,337 @@
 //
 // SPDX-License-Identifier: GNU General Public License v3.0 or later
 
+use crate::editorconfig::{EditorConfig, FileFormat};
+use crate::formatter;
+use crate::highlight;
+use crate::image_file;
+use crate::merge::{self, Resolution};
+use crate::settings::{Indent, Keymap, Settings};
+use crate::symbols::{self, Symbol};
+#[cfg(feature = "tree-sitter")]
+use crate::syntax_tree::SyntaxTree;
+use crate::tui::clipboard::Clipboard;
+use crate::tui::command_line::{Command, CommandLine, CommandLineEvent};
 use crate::tui::component::{Action, Component, ComponentState, Focus, FocusState};
-use anyhow::{Context, Result, bail};
+use crate::tui::editor_overlay::EditorOverlay;
+use crate::tui::folds::{FoldMethod, Folds};
+use crate::tui::history::{Change, History, Snapshot};
+use crate::tui::image_view::ImageView;
+use crate::tui::markdown_preview::MarkdownPreview;
+use crate::tui::merge_view::{MergeEvent, MergeView};
+use crate::tui::multi_cursor::{self, Caret, MultiCursor};
+use crate::tui::search_bar::{SearchBar, SearchEvent};
+use crate::tui::text;
+use crate::tui::undo_tree::{UndoTree, UndoTreeEvent};
+use anyhow::{Result, bail};
+#[cfg(feature = "tree-sitter")]
+use edtui::actions::{Execute, SwitchMode};
 use edtui::{
-    EditorEventHandler, EditorState, EditorTheme, EditorView, LineNumbers, Lines, SyntaxHighlighter,
+    EditorEventHandler, EditorMode, EditorState, EditorTheme, EditorView, Index2, LineNumbers,
+    Lines, RowIndex, SyntaxHighlighter, THEME_SET,
 };
-use log::{error, trace};
+use log::{error, info, trace};
 use ratatui::buffer::Buffer;
-use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::{Alignment, Rect};
-use ratatui::prelude::{Color, Style};
-use ratatui::widgets::{Block, Borders, Padding, Widget};
+use ratatui::crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Position, Rect};
+use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Widget, Wrap};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::ops::Range;
 use std::path::PathBuf;
-use syntect::parsing::SyntaxSet;
+use std::rc::Rc;
+use syntect::parsing::SyntaxReference;
 
 pub struct Editor {
     pub state: EditorState,
     pub event_handler: EditorEventHandler,
     pub file_path: Option<std::path::PathBuf>,
-    syntax_set: SyntaxSet,
+    /// The file path with symlinks resolved, which is how the debugger keeps breakpoints. It is
+    /// found when the file is opened or saved, rather than each time breakpoints are drawn.
+    canonical_path: Option<PathBuf>,
     pub(crate) component_state: ComponentState,
+    /// The undo history of the buffer, shared by the editors for the same file in every pane.
+    history: Rc<RefCell<History>>,
+    /// Counts the changes made to the buffer, so other views of the same file can tell when it was
+    /// edited.
+    revision: u64,
+    /// True while a run of edits in insert mode is being recorded as a single undo step.
+    insert_group: bool,
+    search: SearchBar,
+    /// The revision of the buffer the search matches were last found in.
+    searched_revision: u64,
+    undo_tree: UndoTree,
+    preview: MarkdownPreview,
+    merge: MergeView,
+    settings: Settings,
+    /// The start of the selection in the standard keymap. The cursor is the other end.
+    selection_anchor: Option<Index2>,
+    clipboard: Clipboard,
+    command_line: CommandLine,
+    /// Carets for editing at several places at once. Empty while editing with a single cursor.
+    cursors: MultiCursor,
+    /// The corner of a rectangular block selection opposite the cursor.
+    block_anchor: Option<Index2>,
+    folds: Folds,
+    /// The buffer without the rows hidden by closed folds, which the EditorView draws while any
+    /// fold is closed. Its view keeps the scroll position for the folded display.
+    fold_state: EditorState,
+    /// The buffer row drawn on each row of `fold_state`. Empty while no folds are closed.
+    fold_rows: Vec<usize>,
+    /// The gutter drawn in the last frame, and the screen row and buffer row of each line number.
+    gutter_area: Rect,
+    gutter_rows: Vec<(u16, usize)>,
+    /// The rows with a breakpoint, and the row the program being debugged is stopped at, which
+    /// are kept by the debugger and given to each editor before it is drawn.
+    breakpoints: BTreeSet<usize>,
+    debug_row: Option<usize>,
+    /// True after `z` is pressed in vim normal mode, while waiting for the rest of a fold command.
+    pending_fold: bool,
+    /// The name of the language of the file, shown in the editor title.
+    language: String,
+    /// The language set with `:set ft=`, used instead of the language detected for the file.
+    language_override: Option<String>,
+    /// The syntax used to highlight the language.
+    syntax: &'static SyntaxReference,
+    /// The tree-sitter parse of the buffer, which replaces the highlighter for languages with a
+    /// tree-sitter grammar.
+    #[cfg(feature = "tree-sitter")]
+    syntax_tree: Option<SyntaxTree>,
+    /// The selections before each expansion to an enclosing syntax node, ending with the current
+    /// selection, so the selection can be shrunk back to them.
+    #[cfg(feature = "tree-sitter")]
+    expanded: Vec<(Index2, Index2)>,
+    indent: Indent,
+    /// The properties from `.editorconfig` files that apply to the file.
+    editorconfig: EditorConfig,
+    /// The line endings, encoding and final newline the file is saved with.
+    format: FileFormat,
+    /// The image drawn instead of the buffer for image files, which can't be edited.
+    image: Option<ImageView>,
+    /// True for files that couldn't be decoded, which are shown with invalid bytes replaced and
+    /// can't be saved.
+    read_only: bool,
+    /// The error shown instead of the buffer for files that couldn't be opened, which can't be
+    /// edited.
+    open_error: Option<String>,
 }
 
 impl Editor {
     pub const ID: &str = "Editor";
 
+    const HELP: &str = concat!(
+        "CTRL+S: Save file | (CTRL+F or /): Search | :w/:q/:e: Commands | ALT+U: Undo tree |",
+        " Help > About: All keys"
+    );
+
+    const STANDARD_HELP: &str = concat!(
+        "CTRL+S: Save file | CTRL+Z/Y: Undo / redo | CTRL+F: Search | ALT+U: Undo tree |",
+        " Help > About: All keys"
+    );
+
+    /// Every key handled by clide in the vim keymap. The status bar only has room for the most
+    /// common keys, so these are listed in the About dialog.
+    pub const KEYS: &[&str] = &[
+        "CTRL+S: Save file | (CTRL+F or /): Search and replace | ALT+U: Undo tree",
+        ":(w/q/wq/e/sp/vs/tabn/tabp/bd/diff): Commands",
+        "ALT+(↑/↓): Add cursor | ALT+N: Add next match | (CTRL+V or ALT+B): Block select",
+        "(za or ALT+F): Toggle fold | zo/zc: Open / close fold | (zR/zM or ALT+SHIFT+F): Unfold / fold all",
+        #[cfg(feature = "tree-sitter")]
+        "ALT+O/I: Expand / shrink selection | ALT+A: Parent node",
+        "ALT+(←/h): Previous tab | ALT+(l/→): Next tab | ALT+(1-9): Go to tab | ALT+(</>): Move tab | ALT+P: Pin tab",
+        "ALT+M: Markdown preview | F9: Toggle breakpoint",
+        "ALT+SHIFT+(O/T/B): Accept ours / theirs / both | ALT+SHIFT+(N/P): Next / previous conflict | ALT+SHIFT+M: Merge view",
+        "ALT+V/S: Split right/down | ALT+X: Close split | ALT+SHIFT+(←↓↑→): Focus split | ALT+(=/-): Resize split",
+        "All other input is handled by vim",
+    ];
+
+    /// Every key handled by clide in the standard keymap.
+    pub const STANDARD_KEYS: &[&str] = &[
+        "CTRL+S: Save file | CTRL+Z/Y: Undo / redo | CTRL+(X/C/V): Cut / copy / paste",
+        "SHIFT+(←↓↑→): Select | CTRL+A: Select all | CTRL+F: Search and replace | ALT+U: Undo tree",
+        "ALT+(↑/↓): Add cursor | ALT+N: Add next match | ALT+B: Block select",
+        "ALT+F: Toggle fold | ALT+SHIFT+F: Fold / unfold all",
+        #[cfg(feature = "tree-sitter")]
+        "ALT+O/I: Expand / shrink selection | ALT+A: Parent node",
+        "ALT+(←/h): Previous tab | ALT+(l/→): Next tab | ALT+(1-9): Go to tab | ALT+(</>): Move tab | ALT+P: Pin tab",
+        "ALT+M: Markdown preview | F9: Toggle breakpoint",
+        "ALT+SHIFT+(O/T/B): Accept ours / theirs / both | ALT+SHIFT+(N/P): Next / previous conflict | ALT+SHIFT+M: Merge view",
+        "ALT+V/S: Split right/down | ALT+X: Close split | ALT+SHIFT+(←↓↑→): Focus split | ALT+(=/-): Resize split",
+    ];
+
+    const MULTI_CURSOR_HELP: &str = concat!(
+        "ESC: Single cursor | ALT+(↑/↓): Add cursor | ALT+N: Add next match |",
+        " CTRL+(X/C/V): Cut / copy / paste | All other input edits at every cursor"
+    );
+
+    const BLOCK_HELP: &str = concat!(
+        "ESC: Cancel | I/A: Insert / append on each row | c: Change block | d/x: Delete block |",
+        " y: Yank block | Movement keys resize the block"
+    );
+
+    const STANDARD_BLOCK_HELP: &str = concat!(
+        "ESC: Cancel | (←↓↑→): Resize block | CTRL+(X/C): Cut / copy block |",
+        " Typing edits every row of the block"
+    );
+
+    const IMAGE_HELP: &str = concat!(
+        "Images can't be edited | ALT+(←/h): Previous tab | ALT+(l/→): Next tab |",
+        " ALT+(1-9): Go to tab | ALT+P: Pin tab | ALT+V/S: Split right/down | ALT+X: Close split"
+    );
+    const ERROR_HELP: &str = concat!(
+        "Files that failed to open can't be edited | ALT+(←/h): Previous tab | ALT+(l/→): Next tab |",
+        " ALT+(1-9): Go to tab | ALT+P: Pin tab | ALT+V/S: Split right/down | ALT+X: Close split"
+    );
+
+    const SELECTION_STYLE: Style = Style::new().bg(Color::Rgb(0x44, 0x47, 0x5a));
+
+    const SECONDARY_CURSOR_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Gray);
+
+    /// The syntax highlighting theme, which is bundled with edtui unless a user theme replaces it.
+    const THEME: &str = "dracula";
+
+    const GUTTER_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+    const BREAKPOINT_STYLE: Style = Style::new().fg(Color::Red);
+
+    /// The line the program being debugged is stopped at.
+    const DEBUG_ROW_STYLE: Style = Style::new().bg(Color::Rgb(0x4a, 0x45, 0x1e));
+
+    const BRACKET_MATCH_STYLE: Style = Style::new()
+        .bg(Color::Rgb(0x62, 0x72, 0xa4))
+        .add_modifier(Modifier::BOLD);
+
     pub fn new(path: &std::path::Path) -> Self {
         trace!(target:Self::ID, "Building {}", Self::ID);
-        Editor {
-            state: EditorState::default(),
+        let settings = Settings::load();
+        let mut state = EditorState::default();
+        let clipboard = Clipboard::new();
+        // Vim yank and put share the system clipboard with the standard keymap.
+        state.set_clipboard(clipboard);
+        // The standard keymap has no modes, so the vim emulation always stays in insert mode.
+        if settings.keymap == Keymap::Standard {
+            state.mode = EditorMode::Insert;
+        }
+        let mut editor = Editor {
+            state,
             event_handler: EditorEventHandler::default(),
             file_path: Some(path.to_owned()),
-            syntax_set: SyntaxSet::load_defaults_nonewlines(),
-            component_state: ComponentState::default().with_help_text(concat!(
-                "CTRL+S: Save file | ALT+(←/h): Previous tab | ALT+(l/→): Next tab |",
-                " All other input is handled by vim"
-            )),
+            canonical_path: Some(Self::canonicalize(path)),
+            component_state: ComponentState::default(),
+            history: Rc::default(),
+            revision: 0,
+            insert_group: false,
+            search: SearchBar::default(),
+            searched_revision: 0,
+            undo_tree: UndoTree::default(),
+            preview: MarkdownPreview::default(),
+            merge: MergeView::default(),
+            settings,
+            selection_anchor: None,
+            clipboard,
+            command_line: CommandLine::default(),
+            cursors: MultiCursor::default(),
+            block_anchor: None,
+            folds: Folds::new(FoldMethod::for_path(path)),
+            fold_state: EditorState::default(),
+            fold_rows: Vec::new(),
+            gutter_area: Rect::default(),
+            gutter_rows: Vec::new(),
+            breakpoints: BTreeSet::new(),
+            debug_row: None,
+            pending_fold: false,
+            language: String::new(),
+            language_override: None,
+            syntax: highlight::syntax_set().find_syntax_plain_text(),
+            #[cfg(feature = "tree-sitter")]
+            syntax_tree: None,
+            #[cfg(feature = "tree-sitter")]
+            expanded: Vec::new(),
+            indent: Indent::default(),
+            editorconfig: EditorConfig::for_file(path),
+            format: FileFormat::default(),
+            image: None,
+            read_only: false,
+            open_error: None,
+        };
+        editor.set_language(path, "");
+        editor.component_state.help_text = editor.help().to_string();
+        editor
+    }
+
+    pub fn keymap(&self) -> Keymap {
+        self.settings.keymap
+    }
+
+    /// The editing mode shown in the status bar.
+    pub fn mode_name(&self) -> String {
+        if self.open_error.is_some() {
+            return "ERROR".to_string();
+        }
+        if self.image.is_some() {
+            return "IMAGE".to_string();
+        }
+        if self.block_anchor.is_some() {
+            return "BLOCK".to_string();
+        }
+        if self.cursors.is_active() {
+            return format!("{} CURSORS", self.cursors.carets().len());
+        }
+        match self.settings.keymap {
+            Keymap::Standard => "STANDARD".to_string(),
+            Keymap::Vim if self.command_line.is_visible() => "COMMAND".to_string(),
+            Keymap::Vim => format!("{:?}", self.state.mode).to_uppercase(),
+        }
+    }
+
+    /// The background color for the editing mode in the status bar.
+    pub fn mode_color(&self) -> Color {
+        if self.open_error.is_some() {
+            return Color::Red;
+        }
+        if self.block_anchor.is_some() {
+            return Color::Magenta;
+        }
+        if self.cursors.is_active() {
+            return Color::LightCyan;
+        }
+        match (self.settings.keymap, self.state.mode) {
+            (Keymap::Standard, _) => Color::Yellow,
+            (Keymap::Vim, _) if self.command_line.is_visible() => Color::LightMagenta,
+            (Keymap::Vim, EditorMode::Insert) => Color::Green,
+            (Keymap::Vim, EditorMode::Visual) => Color::Magenta,
+            (Keymap::Vim, _) => Color::Blue,
+        }
+    }
+
+    /// Every key handled by clide in the keymap of the editor.
+    pub fn keys(&self) -> &'static [&'static str] {
+        match self.settings.keymap {
+            Keymap::Vim => Self::KEYS,
+            Keymap::Standard => Self::STANDARD_KEYS,
+        }
+    }
+
+    fn help(&self) -> &'static str {
+        if self.open_error.is_some() {
+            return Self::ERROR_HELP;
+        }
+        if self.image.is_some() {
+            return Self::IMAGE_HELP;
+        }
+        match (
+            self.settings.keymap,
+            self.block_anchor,
+            self.cursors.is_active(),
+        ) {
+            (Keymap::Vim, Some(_), _) => Self::BLOCK_HELP,
+            (Keymap::Standard, Some(_), _) => Self::STANDARD_BLOCK_HELP,
+            (_, None, true) => Self::MULTI_CURSOR_HELP,
+            (Keymap::Vim, None, false) => Self::HELP,
+            (Keymap::Standard, None, false) => Self::STANDARD_HELP,
         }
     }
 
@@ -54,86 +349,1997 @@ impl Editor {
 
     pub fn set_contents(&mut self, path: &std::path::Path) -> Result<()> {
         trace!(target:Self::ID, "Setting Editor contents from path {:?}", path);
-        if let Ok(contents) = std::fs::read_to_string(path) {
-            let lines: Vec<_> = contents
-                .lines()
-                .map(|line| line.chars().collect::<Vec<char>>())
-                .collect();
-            self.file_path = Some(PathBuf::from(path));
-            self.state.lines = Lines::new(lines);
-            self.state.cursor.row = 0;
-            self.state.cursor.col = 0;
+        self.open_error = None;
+        self.file_path = Some(PathBuf::from(path));
+        self.canonical_path = Some(Self::canonicalize(path));
+        if image_file::is_image(path) {
+            match ImageView::open(path) {
+                Ok(image) => {
+                    info!(target:Self::ID, "Opened image {path:?}: {}", image.info.summary());
+                    self.language = image.info.format.clone();
+                    self.image = Some(image);
+                }
+                Err(e) => self.show_open_error(format!("Failed to open image {path:?}: {e:#}")),
+            }
+            self.component_state.help_text = self.help().to_string();
+            return Ok(());
+        }
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            // The file is created when the buffer is saved.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                info!(target:Self::ID, "Opened new file {path:?}");
+                return Ok(());
+            }
+            Err(e) => {
+                self.show_open_error(format!("Failed to read {path:?}: {e}"));
+                self.component_state.help_text = self.help().to_string();
+                return Ok(());
+            }
+        };
+        self.editorconfig = EditorConfig::for_file(path);
+        let (format, contents) = match FileFormat::decode(&bytes, &self.editorconfig) {
+            Ok(decoded) => {
+                self.read_only = false;
+                decoded
+            }
+            Err(e) => {
+                error!(target:Self::ID, "Opening {path:?} read-only: {e:#}");
+                self.read_only = true;
+                let contents = String::from_utf8_lossy(&bytes).replace("\r\n", "\n");
+                let contents = contents.strip_suffix('\n').unwrap_or(&contents).to_string();
+                (FileFormat::default(), contents)
+            }
+        };
+        let lines: Vec<_> = contents
+            .split('\n')
+            .map(|line| line.chars().collect::<Vec<char>>())
+            .collect();
+        self.format = format;
+        self.set_language(path, &contents);
+        self.state.lines = Lines::new(lines);
+        self.revision += 1;
+        self.state.cursor.row = 0;
+        self.state.cursor.col = 0;
+        self.load_history(path);
+        let conflicts = merge::conflicts(&self.text_lines()).len();
+        if conflicts > 0 {
+            info!(target:Self::ID, "Found {conflicts} merge conflicts in {path:?}, ALT+SHIFT+M opens the merge view");
         }
         Ok(())
     }
 
-    pub fn save(&self) -> Result<()> {
-        if let Some(path) = &self.file_path {
-            trace!(target:Self::ID, "Saving Editor contents {:?}", path);
-            return std::fs::write(path, self.state.lines.to_string()).map_err(|e| e.into());
+    /// The full path of a file with symlinks resolved, or the path itself for files that don't
+    /// exist yet.
+    fn canonicalize(path: &std::path::Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    /// The file path with symlinks resolved, for finding the breakpoints in the file.
+    pub fn canonical_path(&self) -> Option<&std::path::Path> {
+        self.canonical_path.as_deref()
+    }
+
+    /// Shows an error in place of the buffer for a file that couldn't be opened.
+    fn show_open_error(&mut self, message: String) {
+        error!(target:Self::ID, "{message}");
+        self.image = None;
+        self.open_error = Some(message);
+    }
+
+    /// Finds the language of a file from its name and contents, unless it was set with
+    /// `:set ft=`, and the indentation for it from the settings and any `.editorconfig` files.
+    fn set_language(&mut self, path: &std::path::Path, contents: &str) {
+        let syntax = self
+            .language_override
+            .as_deref()
+            .and_then(highlight::find_syntax)
+            .unwrap_or_else(|| highlight::detect_syntax(path, contents, &self.settings.file_types));
+        self.language = syntax.name.clone();
+        self.syntax = syntax;
+        #[cfg(feature = "tree-sitter")]
+        {
+            self.syntax_tree = SyntaxTree::new(&self.language);
+        }
+        self.indent = self
+            .editorconfig
+            .indent(self.settings.indent_for(&self.language));
+    }
+
+    /// A highlighter for the language using the shared syntax and theme sets, which include any
+    /// user grammars and themes. EditorView takes ownership of its highlighter, so one is built for
+    /// each frame, but only the syntax and theme are cloned to build it.
+    fn highlighter(&self) -> Option<SyntaxHighlighter> {
+        let theme = highlight::theme(Self::THEME).or_else(|| THEME_SET.themes.get(Self::THEME))?;
+        Some(SyntaxHighlighter::with_sets(
+            theme.clone(),
+            highlight::shared_theme_set(),
+            self.syntax.clone(),
+            highlight::shared_syntax_set(),
+        ))
+    }
+
+    /// Sets the language of the buffer, or detects it again from the file if `name` is None.
+    fn override_language(&mut self, name: Option<String>) -> Result<()> {
+        if let Some(name) = &name
+            && highlight::find_syntax(name).is_none()
+        {
+            bail!("Unknown language: {name}");
+        }
+        self.language_override = name;
+        let path = self.file_path.clone().unwrap_or_default();
+        let contents = self.state.lines.to_string();
+        self.set_language(&path, &contents);
+        info!(target:Self::ID, "Set language of {path:?} to {}", self.language);
+        Ok(())
+    }
+
+    /// The symbols defined in the buffer, from the syntax tree when the language has a tree-sitter
+    /// grammar, or otherwise by matching each line against patterns for the language.
+    pub fn symbols(&mut self) -> Vec<Symbol> {
+        #[cfg(feature = "tree-sitter")]
+        if let Some(tree) = &mut self.syntax_tree {
+            tree.update(&self.state.lines.to_string());
+            let symbols = tree.symbols();
+            if !symbols.is_empty() {
+                return symbols;
+            }
+        }
+        symbols::extract(&self.language, &self.state.lines.to_string())
+    }
+
+    /// Moves the cursor to a position in the buffer, opening any folds hiding it.
+    pub fn goto(&mut self, row: usize, col: usize) {
+        let row = row.min(self.state.lines.len().saturating_sub(1));
+        let col = col.min(self.state.lines.len_col(row).unwrap_or(0));
+        self.selection_anchor = None;
+        self.state.selection = None;
+        if self.state.mode == EditorMode::Visual {
+            self.state.mode = EditorMode::Normal;
+        }
+        self.folds.open_at(row);
+        self.state.cursor = Index2::new(row, col);
+    }
+
+    /// Adds the file contents to the undo history after opening or reloading the file.
+    /// When the file is first opened, any history persisted from an earlier session is restored.
+    fn load_history(&mut self, path: &std::path::Path) {
+        if self.history.borrow().is_empty()
+            && self.settings.persist_undo
+            && let Some(history) = History::load(path)
+        {
+            *self.history.borrow_mut() = history;
+        }
+        // If the file was changed outside of clide, its contents are recorded as a new change.
+        if !self
+            .history
+            .borrow_mut()
+            .select(&self.state.lines.to_string())
+        {
+            self.record_change(self.pending_change().unwrap_or_default(), false);
+        }
+        self.insert_group = false;
+    }
+
+    /// The number of changes made to the buffer since the editor was created.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Shows the buffer of another editor for the same file, sharing its undo history so an edit
+    /// made in either editor can be undone from both.
+    pub fn share_buffer(&mut self, other: &Editor) {
+        self.history = Rc::clone(&other.history);
+        self.set_lines(other.state.lines.clone());
+    }
+
+    /// Replaces the buffer contents, keeping the cursor within the new bounds.
+    pub fn set_lines(&mut self, lines: Lines) {
+        let before = std::mem::replace(&mut self.state.lines, lines);
+        self.revision += 1;
+        // Edits after the buffer is replaced start a new undo step, rather than joining one that
+        // was started before it.
+        self.insert_group = false;
+        self.update_folds(&before);
+        let last_row = self.state.lines.len().saturating_sub(1);
+        self.state.cursor.row = self.state.cursor.row.min(last_row);
+        let row_len = self.state.lines.len_col(self.state.cursor.row).unwrap_or(0);
+        self.state.cursor.col = self.state.cursor.col.min(row_len);
+    }
+
+    /// The buffer contents as one string for each line.
+    pub fn text_lines(&self) -> Vec<String> {
+        self.state
+            .lines
+            .to_string()
+            .split('\n')
+            .map(String::from)
+            .collect()
+    }
+
+    /// The text of one row of the buffer.
+    fn line(&self, row: usize) -> Option<String> {
+        let line = self.state.lines.get(RowIndex::new(row))?;
+        Some(line.iter().collect())
+    }
+
+    /// The change to the buffer since the current state in the undo history.
+    fn pending_change(&self) -> Option<Change> {
+        Change::between(self.history.borrow().text(), text::chars(&self.state.lines))
+    }
+
+    /// Records an edit made to the buffer in place since the current state in the undo history,
+    /// returning false if the buffer is unchanged. With `group` the edit joins the current undo
+    /// step instead of starting a new one.
+    fn record_edit(&mut self, group: bool) -> bool {
+        let Some(change) = self.pending_change() else {
+            return false;
         };
-        error!(target:Self::ID, "Failed saving Editor contents; file_path was None");
-        bail!("File not saved. No file path set.")
+        self.revision += 1;
+        let (row, delta) = change.rows(self.history.borrow().text());
+        self.folds.edited(row, delta);
+        self.record_change(change, group);
+        true
     }
-}
 
-impl Widget for &mut Editor {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let lang = self
-            .file_path
-            .as_ref()
-            .and_then(|p| p.extension())
-            .map(|e| e.to_str().unwrap_or("md"))
-            .unwrap_or("md");
-        let lang_name = self
-            .syntax_set
-            .find_syntax_by_extension(lang)
-            .map(|s| s.name.to_string())
-            .unwrap_or_else(|| String::from("Unknown"));
-
-        EditorView::new(&mut self.state)
-            .wrap(true)
-            .theme(
-                EditorTheme::default().block(
-                    Block::default()
-                        .title(lang_name.to_owned())
-                        .title_style(Style::default().fg(Color::Yellow))
-                        .title_alignment(Alignment::Right)
-                        .borders(Borders::ALL)
-                        .padding(Padding::new(0, 0, 0, 1))
-                        .style(Style::default().fg(self.component_state.get_active_color())),
-                ),
-            )
-            .syntax_highlighter(SyntaxHighlighter::new("dracula", lang).ok())
-            .tab_width(2)
-            .line_numbers(LineNumbers::Absolute)
-            .render(area, buf);
+    /// Records a change to the buffer in the undo history. With `group` the change joins the
+    /// current undo step instead of starting a new one.
+    fn record_change(&mut self, change: Change, group: bool) {
+        let cursor = (self.state.cursor.row, self.state.cursor.col);
+        match group {
+            true => self.history.borrow_mut().amend(change, cursor),
+            false => self.history.borrow_mut().record(change, cursor),
+        }
     }
-}
 
-impl Component for Editor {
-    fn handle_event(&mut self, event: Event) -> Result<Action> {
-        if let Some(key_event) = event.as_key_event() {
-            // Handle events here that should not be passed on to the vim emulation handler.
-            match self.handle_key_events(key_event)? {
-                Action::Handled => return Ok(Action::Handled),
-                _ => {}
+    fn restore(&mut self, snapshot: Snapshot) {
+        let (row, col) = snapshot.cursor;
+        self.state.cursor = Index2::new(row, col);
+        self.state.selection = None;
+        self.insert_group = false;
+        let lines: Vec<String> = snapshot.text.split('\n').map(String::from).collect();
+        self.set_text_lines(&lines);
+    }
+
+    fn set_text_lines(&mut self, lines: &[String]) {
+        // The buffer always has at least one line, even when every line is removed.
+        if lines.is_empty() {
+            return self.set_text_lines(&[String::new()]);
+        }
+        let lines: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+        self.set_lines(Lines::new(lines));
+    }
+
+    /// Replaces the buffer contents with `lines` as a single undoable edit.
+    pub fn edit_lines(&mut self, lines: &[String]) {
+        self.set_text_lines(lines);
+        if let Some(change) = self.pending_change() {
+            self.record_change(change, false);
+        }
+        self.insert_group = false;
+    }
+
+    pub fn undo(&mut self) {
+        self.clear_carets();
+        let snapshot = self.history.borrow_mut().undo();
+        match snapshot {
+            Some(snapshot) => self.restore(snapshot),
+            None => info!(target:Self::ID, "Already at oldest change"),
+        }
+    }
+
+    pub fn redo(&mut self) {
+        self.clear_carets();
+        let snapshot = self.history.borrow_mut().redo();
+        match snapshot {
+            Some(snapshot) => self.restore(snapshot),
+            None => info!(target:Self::ID, "Already at newest change"),
+        }
+    }
+
+    fn toggle_undo_tree(&mut self) {
+        match self.undo_tree.is_visible() {
+            true => {
+                self.undo_tree.close();
+                self.component_state.help_text = self.help().to_string();
+            }
+            false => {
+                self.close_search();
+                self.undo_tree.open(&self.history.borrow());
+                self.component_state.help_text = UndoTree::HELP.to_string();
+            }
+        }
+    }
+
+    /// Shows or hides the rendered markdown beside the buffer.
+    fn toggle_preview(&mut self) {
+        if !self.preview.is_visible() && !self.is_markdown() {
+            info!(target:Self::ID, "No preview for {}", self.language);
+            return;
+        }
+        self.preview.toggle();
+    }
+
+    fn is_markdown(&self) -> bool {
+        matches!(self.language.as_str(), "Markdown" | "MultiMarkdown")
+    }
+
+    /// Resolves the merge conflict at the cursor, keeping one or both sides.
+    fn accept_conflict(&mut self, resolution: Resolution) {
+        let lines = self.text_lines();
+        let row = self.state.cursor.row;
+        let Some(conflict) = merge::conflicts(&lines)
+            .into_iter()
+            .find(|conflict| conflict.rows().contains(&row))
+        else {
+            info!(target:Self::ID, "No merge conflict at the cursor");
+            return;
+        };
+        self.edit_lines(&merge::resolve(&lines, &conflict, resolution));
+        self.goto(conflict.start, 0);
+        info!(target:Self::ID, "Accepted {resolution:?} for the conflict on line {}", conflict.start + 1);
+    }
+
+    /// Moves the cursor to the start of the next or previous merge conflict.
+    fn goto_conflict(&mut self, next: bool) {
+        let row = self.state.cursor.row;
+        let conflicts = merge::conflicts(&self.text_lines());
+        let conflict = match next {
+            true => conflicts.iter().find(|conflict| conflict.start > row),
+            false => conflicts.iter().rev().find(|conflict| conflict.end < row),
+        };
+        match conflict {
+            Some(conflict) => self.goto(conflict.start, 0),
+            None => info!(target:Self::ID, "No more merge conflicts"),
+        }
+    }
+
+    /// Sets the breakpoints shown in the gutter, and the row the program is stopped at.
+    pub fn set_debug_marks(&mut self, breakpoints: BTreeSet<usize>, debug_row: Option<usize>) {
+        self.breakpoints = breakpoints;
+        self.debug_row = debug_row;
+    }
+
+    /// The action that adds or removes a breakpoint, which are kept by the debugger.
+    fn toggle_breakpoint(&self, row: usize) -> Action {
+        match &self.canonical_path {
+            Some(path) => Action::ToggleBreakpoint(path.clone(), row),
+            None => Action::Noop,
+        }
+    }
+
+    fn toggle_merge_view(&mut self) {
+        if self.merge.is_visible() {
+            self.merge.close();
+            self.component_state.help_text = self.help().to_string();
+            return;
+        }
+        if !self.merge.open(self.text_lines(), self.state.cursor.row) {
+            info!(target:Self::ID, "No merge conflicts in {:?}", self.file_path);
+            return;
+        }
+        self.undo_tree.close();
+        self.search.close();
+        self.component_state.help_text = MergeView::HELP.to_string();
+    }
+
+    fn handle_merge_event(&mut self, event: MergeEvent) {
+        match event {
+            MergeEvent::Changed => {
+                // Each resolution is a separate edit, so it can be undone after the view is closed.
+                let result = self.merge.result();
+                self.edit_lines(&result);
+                self.goto(self.merge.selected_row(), 0);
+            }
+            MergeEvent::Close => self.toggle_merge_view(),
+            MergeEvent::Handled => {}
+        }
+    }
+
+    fn handle_undo_tree_event(&mut self, event: UndoTreeEvent) {
+        match event {
+            UndoTreeEvent::Jump(index) => {
+                let snapshot = self.history.borrow_mut().jump(index);
+                if let Some(snapshot) = snapshot {
+                    self.restore(snapshot);
+                }
+            }
+            UndoTreeEvent::Close => self.toggle_undo_tree(),
+            UndoTreeEvent::Handled => {}
+        }
+    }
+
+    fn open_search(&mut self) {
+        self.undo_tree.close();
+        self.search.open();
+        self.component_state.help_text = SearchBar::HELP.to_string();
+        self.search.find(&self.text_lines(), self.state.cursor);
+        self.goto_current_match();
+    }
+
+    fn close_search(&mut self) {
+        self.search.close();
+        self.component_state.help_text = self.help().to_string();
+    }
+
+    fn goto_current_match(&mut self) {
+        if let Some(m) = self.search.current_match() {
+            self.folds.open_at(m.row);
+            self.state.cursor = Index2::new(m.row, m.start);
+        }
+    }
+
+    fn handle_search_event(&mut self, event: SearchEvent) -> Result<()> {
+        match event {
+            SearchEvent::Changed => {
+                self.search.find(&self.text_lines(), self.state.cursor);
+                self.goto_current_match();
+            }
+            SearchEvent::Next => {
+                self.search.next();
+                self.goto_current_match();
+            }
+            SearchEvent::Previous => {
+                self.search.previous();
+                self.goto_current_match();
             }
+            SearchEvent::Replace => {
+                let mut lines = self.text_lines();
+                if let Some(end) = self.search.replace_current(&mut lines)? {
+                    self.edit_lines(&lines);
+                    // Continue from the end of the replaced text so a replacement that also
+                    // matches the query is not found again.
+                    self.search.find(&self.text_lines(), end);
+                    self.goto_current_match();
+                }
+            }
+            SearchEvent::ReplaceAll => {
+                let mut lines = self.text_lines();
+                let count = self.search.replace_all(&mut lines)?;
+                if count > 0 {
+                    self.edit_lines(&lines);
+                }
+                info!(target:SearchBar::ID, "Replaced {count} matches of {:?}", self.search.query);
+                self.search.find(&self.text_lines(), self.state.cursor);
+            }
+            SearchEvent::Close => self.close_search(),
+            SearchEvent::Handled => {}
+        }
+        Ok(())
+    }
+
+    /// Highlights the selections, extra cursors and search matches over the text drawn by the
+    /// EditorView.
+    fn render_overlay(&self, lines: &[String], view_area: Rect, buf: &mut Buffer) {
+        let state = self.view_state();
+        let Some(overlay) = EditorOverlay::new(view_area, buf, state, self.indent.width) else {
+            return;
+        };
+        // Highlights are given buffer rows, which are drawn on the rows of the folded display.
+        let highlight = |buf: &mut Buffer, row: usize, cols: Range<usize>, style: Style| {
+            if let Some(display_row) = self.display_row(row) {
+                overlay.highlight(buf, state, display_row, cols, style);
+            }
+        };
+        let displayed = overlay.visible_rows();
+        let visible =
+            self.buffer_row(displayed.start)..self.buffer_row(displayed.end.saturating_sub(1)) + 1;
+        if let Some(display_row) = self.debug_row.and_then(|row| self.display_row(row)) {
+            overlay.highlight_row(buf, display_row, Self::DEBUG_ROW_STYLE);
+        }
+        // Merge conflicts are highlighted across the full width of the text, so each side stands
+        // out from the lines around it.
+        for conflict in merge::conflicts(lines) {
+            for row in conflict.rows().filter(|row| visible.contains(row)) {
+                let style = match row {
+                    row if conflict.is_marker(row) => MergeView::MARKER_STYLE,
+                    row if conflict.ours().contains(&row) => MergeView::OURS_STYLE,
+                    row if conflict.base_rows().is_some_and(|base| base.contains(&row)) => {
+                        MergeView::BASE_STYLE
+                    }
+                    _ => MergeView::THEIRS_STYLE,
+                };
+                if let Some(display_row) = self.display_row(row) {
+                    overlay.highlight_row(buf, display_row, style);
+                }
+            }
+        }
+        let highlight_range = |buf: &mut Buffer, (start, end): (Index2, Index2), style: Style| {
+            for row in start.row.max(visible.start)..=end.row.min(visible.end.saturating_sub(1)) {
+                let first = if row == start.row { start.col } else { 0 };
+                let last = if row == end.row { end.col } else { usize::MAX };
+                highlight(buf, row, first..last, style);
+            }
+        };
+        #[cfg(feature = "tree-sitter")]
+        if let Some(tree) = &self.syntax_tree {
+            let bytes = tree.offset(visible.start, 0)..tree.offset(visible.end, 0);
+            for (range, (r, g, b)) in tree.highlights(bytes) {
+                let (start_row, start_col) = tree.position(range.start);
+                // A node ending at the start of a line ends with the line before it.
+                let end = match tree.position(range.end) {
+                    (row, 0) if row > start_row => Index2::new(row - 1, usize::MAX),
+                    (row, col) => Index2::new(row, col),
+                };
+                let style = Style::new().fg(Color::Rgb(r, g, b));
+                highlight_range(buf, (Index2::new(start_row, start_col), end), style);
+            }
+        }
+        if let Some(selection) = self.selection() {
+            highlight_range(buf, selection, Self::SELECTION_STYLE);
         }
+        for caret in self.block_carets(lines) {
+            highlight_range(buf, caret.range(), Self::SELECTION_STYLE);
+        }
+        for caret in self.cursors.carets() {
+            if caret.has_selection() {
+                highlight_range(buf, caret.range(), Self::SELECTION_STYLE);
+            }
+        }
+        // The primary caret is drawn by the EditorView.
+        for caret in self.cursors.carets().iter().skip(1) {
+            if visible.contains(&caret.head.row) {
+                let cols = caret.head.col..caret.head.col + 1;
+                highlight(buf, caret.head.row, cols, Self::SECONDARY_CURSOR_STYLE);
+            }
+        }
+        if let Some((bracket, matching)) = text::matching_bracket(lines, self.state.cursor) {
+            for at in [bracket, matching] {
+                highlight(buf, at.row, at.col..at.col + 1, Self::BRACKET_MATCH_STYLE);
+            }
+        }
+        if !self.search.is_visible() {
+            return;
+        }
+        let current = self.search.current_match();
+        for m in self
+            .search
+            .matches()
+            .iter()
+            .filter(|m| visible.contains(&m.row))
+        {
+            let style = match current == Some(*m) {
+                true => SearchBar::CURRENT_MATCH_STYLE,
+                false => SearchBar::MATCH_STYLE,
+            };
+            highlight(buf, m.row, m.start..m.end, style);
+        }
+    }
+
+    /// Passes an event to the vim emulation handler, recording any edit it makes in the history.
+    /// With the vim keymap, edits made during one visit to insert mode are undone together as they
+    /// are in vim. With the standard keymap, each typed word is undone together.
+    fn handle_vim_event(&mut self, event: Event) {
+        let typed_word = matches!(
+            event,
+            Event::Key(KeyEvent { code: KeyCode::Char(c), .. }) if c.is_alphanumeric() || c == '_'
+        );
+        let previous_row = self.state.cursor.row;
         self.event_handler.on_event(event, &mut self.state);
-        Ok(Action::Pass)
+        let changed = self.record_edit(self.insert_group);
+        self.skip_folded_rows(previous_row);
+        self.insert_group = match self.settings.keymap {
+            Keymap::Vim => self.state.mode == EditorMode::Insert && (self.insert_group || changed),
+            Keymap::Standard => changed && typed_word,
+        };
+        if self.settings.keymap == Keymap::Standard {
+            // Mouse selection and other input may switch vim modes, which the standard keymap
+            // does not use.
+            self.state.mode = EditorMode::Insert;
+            self.state.selection = None;
+        }
     }
 
-    /// The events for the vim emulation should be handled by EditorEventHandler::on_event.
-    /// These events are custom to the clide application.
-    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
-        match key {
-            KeyEvent {
-                code: KeyCode::Char('s'),
-                modifiers: KeyModifiers::CONTROL,
+    /// Moves or opens closed folds after the buffer changes from `before` to its current lines.
+    fn update_folds(&mut self, before: &Lines) {
+        if self.folds.is_empty() {
+            return;
+        }
+        let after = &self.state.lines;
+        let first_change = before
+            .iter_row()
+            .zip(after.iter_row())
+            .position(|(a, b)| a != b)
+            .unwrap_or(before.len().min(after.len()));
+        let delta = after.len() as isize - before.len() as isize;
+        self.folds.edited(first_change, delta);
+    }
+
+    /// Moves the cursor past a closed fold it was moved into, so a closed fold is passed over by
+    /// cursor movement as a single row.
+    fn skip_folded_rows(&mut self, previous_row: usize) {
+        let len = self.state.lines.len();
+        let row = self
+            .folds
+            .skip_hidden(self.state.cursor.row, previous_row, len);
+        if row != self.state.cursor.row {
+            self.state.cursor.row = row;
+            let row_len = self.state.lines.len_col(row).unwrap_or(0);
+            self.state.cursor.col = self.state.cursor.col.min(row_len);
+        }
+    }
+
+    /// Runs a fold command at the cursor. A cursor left inside a closed fold is moved to the row
+    /// the fold is shown on.
+    fn fold(&mut self, command: char) {
+        let lines = self.text_lines();
+        let row = self.state.cursor.row;
+        match command {
+            'a' => {
+                self.folds.toggle(&lines, row);
+            }
+            'c' => {
+                self.folds.close(&lines, row);
+            }
+            'o' => {
+                self.folds.open(row);
+            }
+            'M' => self.folds.close_all(&lines),
+            'R' => self.folds.open_all(),
+            _ => return,
+        }
+        let row = self.folds.visible_row(self.state.cursor.row);
+        if row != self.state.cursor.row {
+            self.state.cursor = Index2::new(row, 0);
+        }
+    }
+
+    /// The buffer row for a row of the folded display.
+    fn buffer_row(&self, display_row: usize) -> usize {
+        match self.fold_rows.is_empty() {
+            true => display_row,
+            false => self
+                .fold_rows
+                .get(display_row)
+                .copied()
+                .unwrap_or(display_row),
+        }
+    }
+
+    /// The row of the folded display for a buffer row, or None if the row is hidden by a fold.
+    fn display_row(&self, row: usize) -> Option<usize> {
+        match self.fold_rows.is_empty() {
+            true => Some(row),
+            false => self.fold_rows.binary_search(&row).ok(),
+        }
+    }
+
+    /// The state drawn by the EditorView, which is the buffer itself unless any folds are closed.
+    fn view_state(&self) -> &EditorState {
+        match self.fold_rows.is_empty() {
+            true => &self.state,
+            false => &self.fold_state,
+        }
+    }
+
+    /// Builds the folded display of the buffer for the EditorView, or clears it if no folds are
+    /// closed.
+    fn update_fold_state(&mut self, lines: &[String]) {
+        self.folds.validate(lines);
+        if self.folds.is_empty() {
+            self.fold_rows.clear();
+            return;
+        }
+        self.fold_rows = self.folds.visible_rows(lines.len());
+        let visible: Vec<Vec<char>> = self
+            .fold_rows
+            .iter()
+            .map(|row| lines[*row].chars().collect())
+            .collect();
+        self.fold_state.lines = Lines::new(visible);
+        // A cursor on a hidden row is drawn on the row of the fold hiding it.
+        let cursor = self.state.cursor;
+        let display_row = self
+            .fold_rows
+            .partition_point(|row| *row <= cursor.row)
+            .saturating_sub(1);
+        let row_len = self.fold_state.lines.len_col(display_row).unwrap_or(0);
+        self.fold_state.cursor = Index2::new(display_row, cursor.col.min(row_len));
+        self.fold_state.mode = self.state.mode;
+        self.fold_state.selection = None;
+    }
+
+    /// Draws breakpoints, line numbers and fold markers. EditorView line numbers would count the
+    /// rows of the folded display instead of the buffer, so the gutter is drawn by the Editor.
+    fn render_gutter(&mut self, lines: &[String], view_area: Rect, buf: &mut Buffer) {
+        self.gutter_rows.clear();
+        let Some(overlay) =
+            EditorOverlay::new(view_area, buf, self.view_state(), self.indent.width)
+        else {
+            return;
+        };
+        let starts: Vec<usize> = self
+            .folds
+            .regions(lines)
+            .iter()
+            .map(|region| region.start)
+            .collect();
+        let width = self.gutter_area.width.saturating_sub(4) as usize;
+        for (y, display_row) in overlay.line_starts() {
+            let row = self.buffer_row(display_row);
+            let marker = match (
+                self.folds.is_closed(row),
+                starts.binary_search(&row).is_ok(),
+            ) {
+                (true, _) => '▸',
+                (false, true) => '▾',
+                (false, false) => ' ',
+            };
+            let style = match row == self.state.cursor.row {
+                true => Style::default().fg(Color::Yellow),
+                false => Self::GUTTER_STYLE,
+            };
+            let breakpoint = match (Some(row) == self.debug_row, self.breakpoints.contains(&row)) {
+                (true, _) => "▶",
+                (false, true) => "●",
+                (false, false) => " ",
+            };
+            buf.set_string(self.gutter_area.x, y, breakpoint, Self::BREAKPOINT_STYLE);
+            buf.set_string(
+                self.gutter_area.x + 1,
+                y,
+                format!("{:>width$} {marker} ", row + 1),
+                style,
+            );
+            self.gutter_rows.push((y, row));
+        }
+    }
+
+    /// The row of a click on the breakpoint column or line number in the gutter.
+    fn breakpoint_click(&self, mouse: MouseEvent) -> Option<usize> {
+        let MouseEventKind::Down(MouseButton::Left) = mouse.kind else {
+            return None;
+        };
+        // The fold marker and the space around it are the last three columns.
+        let numbers = Rect {
+            width: self.gutter_area.width.saturating_sub(3),
+            ..self.gutter_area
+        };
+        if !numbers.contains(Position::new(mouse.column, mouse.row)) {
+            return None;
+        }
+        self.gutter_rows
+            .iter()
+            .find(|(y, _)| *y == mouse.row)
+            .map(|(_, row)| *row)
+    }
+
+    /// Handles mouse input for the gutter and the folded display. Returns false for input that
+    /// should be passed on to the vim emulation handler.
+    fn handle_fold_mouse(&mut self, mouse: MouseEvent) -> bool {
+        let position = Position::new(mouse.column, mouse.row);
+        if self.gutter_area.contains(position) {
+            if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+                let row = self
+                    .gutter_rows
+                    .iter()
+                    .find(|(y, _)| *y == mouse.row)
+                    .map(|(_, row)| *row);
+                if let Some(row) = row {
+                    self.state.cursor = Index2::new(row, 0);
+                    self.fold('a');
+                }
+            }
+            return true;
+        }
+        if self.fold_rows.is_empty() {
+            return false;
+        }
+        // The EditorView draws the folded display, so its positions are mapped back to the buffer.
+        self.event_handler
+            .on_event(Event::Mouse(mouse), &mut self.fold_state);
+        let cursor = self.fold_state.cursor;
+        let row = self.buffer_row(cursor.row);
+        let row_len = self.state.lines.len_col(row).unwrap_or(0);
+        self.state.cursor = Index2::new(row, cursor.col.min(row_len));
+        if self.settings.keymap == Keymap::Vim {
+            self.state.mode = match self.fold_state.mode {
+                EditorMode::Visual => EditorMode::Normal,
+                mode => mode,
+            };
+        }
+        true
+    }
+
+    fn open_command_line(&mut self) {
+        self.command_line.open();
+        self.component_state.help_text = CommandLine::HELP.to_string();
+    }
+
+    /// Shows a message in the command line row until the next key press.
+    pub fn show_message(&mut self, message: String) {
+        self.command_line.set_message(message);
+    }
+
+    fn close_command_line(&mut self) {
+        self.command_line.close();
+        self.component_state.help_text = self.help().to_string();
+    }
+
+    fn handle_command_line_event(&mut self, event: CommandLineEvent) -> Action {
+        match event {
+            CommandLineEvent::Submit(input) => {
+                self.close_command_line();
+                match self.run_command(&input) {
+                    Ok(action) => action,
+                    Err(e) => {
+                        error!(target:CommandLine::ID, "Failed to run command {input:?}: {e:#}");
+                        self.command_line.set_message(format!("{e:#}"));
+                        Action::Handled
+                    }
+                }
+            }
+            CommandLineEvent::Close => {
+                self.close_command_line();
+                Action::Handled
+            }
+            CommandLineEvent::Handled => Action::Handled,
+        }
+    }
+
+    /// Runs an ex-command, returning any Action the App needs to handle for it.
+    fn run_command(&mut self, input: &str) -> Result<Action> {
+        let command = Command::parse(input)?;
+        trace!(target:CommandLine::ID, "Running command {command:?}");
+        let action = match command {
+            // Files are saved by the App, which updates anything read from them.
+            Command::Write => Action::Save,
+            Command::WriteQuit => Action::SaveAndCloseTab,
+            Command::Quit | Command::BufferDelete => Action::CloseTab,
+            Command::QuitAll => Action::Quit,
+            Command::Edit(None) => Action::ReloadFile,
+            Command::Edit(Some(path)) => Action::OpenFile(path),
+            Command::TabNext => Action::NextTab,
+            Command::TabPrevious => Action::PreviousTab,
+            Command::Split => Action::SplitEditor(Direction::Vertical),
+            Command::VerticalSplit => Action::SplitEditor(Direction::Horizontal),
+            Command::GotoLine(line) => {
+                let last_row = self.state.lines.len().saturating_sub(1);
+                let row = line.saturating_sub(1).min(last_row);
+                self.folds.open_at(row);
+                self.state.cursor = Index2::new(row, 0);
+                Action::Handled
+            }
+            Command::SetLanguage(name) => {
+                self.override_language(name)?;
+                Action::Handled
+            }
+            Command::Diff(path) => Action::CompareBuffer(path),
+        };
+        Ok(action)
+    }
+
+    /// True while keys go to vim normal mode. Keys typed in the command line, undo tree or search
+    /// bar are not normal mode commands, even though the vim emulation stays in normal mode.
+    fn is_vim_normal_mode(&self) -> bool {
+        self.settings.keymap == Keymap::Vim
+            && self.state.mode == EditorMode::Normal
+            && !self.command_line.is_visible()
+            && !self.undo_tree.is_visible()
+            && !self.search.is_visible()
+            && self.block_anchor.is_none()
+    }
+
+    /// Starts editing with multiple cursors if it hasn't started, using the cursor and any
+    /// selection as the primary caret. Vim emulation is kept in insert mode while there are
+    /// multiple cursors, since each key typed is applied at every caret.
+    fn start_carets(&mut self) {
+        if !self.cursors.carets().is_empty() {
+            return;
+        }
+        let cursor = self.state.cursor;
+        let primary = match (self.selection_anchor, &self.state.selection) {
+            (Some(anchor), _) => Caret {
+                anchor,
+                head: cursor,
+            },
+            // Vim selections include the character under the cursor.
+            (None, Some(selection)) => {
+                let (start, end) = text::ordered(selection.start, selection.end);
+                Caret {
+                    anchor: start,
+                    head: Index2::new(end.row, end.col + 1),
+                }
+            }
+            (None, None) => Caret::new(cursor),
+        };
+        self.cursors.set(vec![primary]);
+        self.selection_anchor = None;
+        self.state.selection = None;
+        self.state.mode = EditorMode::Insert;
+        self.insert_group = false;
+    }
+
+    /// Moves the EditorView cursor to the primary caret. Once there is only one caret left, editing
+    /// returns to a single cursor, keeping any selection it has in the standard keymap.
+    fn sync_carets(&mut self) {
+        if let Some(primary) = self.cursors.primary() {
+            self.state.cursor = primary.head;
+            if !self.cursors.is_active() {
+                if self.settings.keymap == Keymap::Standard && primary.has_selection() {
+                    self.selection_anchor = Some(primary.anchor);
+                }
+                self.cursors.clear();
+            }
+        }
+        self.component_state.help_text = self.help().to_string();
+    }
+
+    /// Returns to editing with a single cursor at the primary caret.
+    fn clear_carets(&mut self) {
+        if let Some(primary) = self.cursors.primary() {
+            self.state.cursor = primary.head;
+            self.cursors.clear();
+            if self.settings.keymap == Keymap::Vim {
+                self.state.mode = EditorMode::Normal;
+            }
+        }
+        self.component_state.help_text = self.help().to_string();
+    }
+
+    fn add_caret_vertical(&mut self, up: bool) {
+        self.block_anchor = None;
+        self.start_carets();
+        self.cursors.add_vertical(&self.state.lines, up);
+        self.sync_carets();
+    }
+
+    fn add_next_occurrence(&mut self) {
+        self.block_anchor = None;
+        self.start_carets();
+        self.cursors.add_next_occurrence(&self.text_lines());
+        match self.cursors.primary() {
+            // The first use selects the word under the cursor, which is kept as a single caret
+            // until the next match is added.
+            Some(primary) if primary.has_selection() => {
+                self.state.cursor = primary.head;
+                self.component_state.help_text = self.help().to_string();
+            }
+            _ => self.clear_carets(),
+        }
+    }
+
+    /// Applies an edit at every caret as one undoable change. Typed words are grouped into a
+    /// single undo step, as they are in the standard keymap.
+    fn edit_carets<F>(&mut self, typed_word: bool, edit: F)
+    where
+        F: FnOnce(&mut MultiCursor, &mut Lines),
+    {
+        edit(&mut self.cursors, &mut self.state.lines);
+        self.record_edit(self.insert_group);
+        self.insert_group = typed_word;
+        self.sync_carets();
+    }
+
+    /// Handles key input while there are multiple cursors.
+    fn handle_carets_key(&mut self, key: KeyEvent) {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.clear_carets(),
+            KeyCode::Char('z') if control => self.undo(),
+            KeyCode::Char('y') if control => self.redo(),
+            KeyCode::Char('c') if control => {
+                let texts = self.cursors.selected_texts(&self.text_lines());
+                self.clipboard.copy(&texts.join("\n"));
+            }
+            KeyCode::Char('x') if control => {
+                let texts = self.cursors.selected_texts(&self.text_lines());
+                self.clipboard.copy(&texts.join("\n"));
+                self.edit_carets(false, |cursors, lines| cursors.delete_selections(lines));
+            }
+            KeyCode::Char('v') if control => {
+                // Text with a line for each caret is split between them.
+                let pasted = self.clipboard.paste();
+                let texts: Vec<String> = pasted.split('\n').map(String::from).collect();
+                self.edit_carets(false, |cursors, lines| cursors.insert(lines, &texts));
+            }
+            KeyCode::Char(c) if !control => {
+                let typed_word = c.is_alphanumeric() || c == '_';
+                let texts = [c.to_string()];
+                self.edit_carets(typed_word, |cursors, lines| cursors.insert(lines, &texts));
+            }
+            KeyCode::Enter | KeyCode::Tab => {
+                let texts = [match key.code {
+                    KeyCode::Enter => "\n".to_string(),
+                    _ => self.indent.unit(0),
+                }];
+                self.edit_carets(false, |cursors, lines| cursors.insert(lines, &texts));
+            }
+            KeyCode::Backspace => {
+                self.edit_carets(false, |cursors, lines| cursors.backspace(lines));
+            }
+            KeyCode::Delete => self.edit_carets(false, |cursors, lines| cursors.delete(lines)),
+            KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Home
+            | KeyCode::End => {
+                // Only the standard keymap selects with SHIFT.
+                let extend = shift && self.settings.keymap == Keymap::Standard;
+                self.cursors
+                    .move_carets(&self.state.lines, key.code, extend);
+                self.insert_group = false;
+                self.sync_carets();
+            }
+            _ => {}
+        }
+    }
+
+    fn start_block_selection(&mut self) {
+        self.clear_carets();
+        self.selection_anchor = None;
+        self.state.selection = None;
+        self.block_anchor = Some(self.state.cursor);
+        self.component_state.help_text = self.help().to_string();
+    }
+
+    fn cancel_block_selection(&mut self) {
+        self.block_anchor = None;
+        self.component_state.help_text = self.help().to_string();
+    }
+
+    /// A caret selecting each row of the block selection. Vim blocks include the column under the
+    /// cursor, as vim's visual block mode does.
+    fn block_carets(&self, lines: &[String]) -> Vec<Caret> {
+        let Some(anchor) = self.block_anchor else {
+            return Vec::new();
+        };
+        let inclusive = self.settings.keymap == Keymap::Vim;
+        multi_cursor::block_carets(lines, anchor, self.state.cursor, inclusive)
+    }
+
+    fn copy_block(&mut self) {
+        let lines = self.text_lines();
+        let mut cursors = MultiCursor::default();
+        cursors.set(self.block_carets(&lines));
+        let texts = cursors.selected_texts(&lines);
+        self.clipboard.copy(&texts.join("\n"));
+    }
+
+    /// Replaces the block selection with a caret on each of its rows.
+    fn carets_from_block(&mut self, position: fn(Caret) -> Caret) {
+        let carets = self.block_carets(&self.text_lines());
+        self.block_anchor = None;
+        self.cursors.set(carets.into_iter().map(position).collect());
+        self.state.mode = EditorMode::Insert;
+        self.insert_group = false;
+    }
+
+    /// Handles key input during a block selection. Returns false for input that should be passed
+    /// on, such as movement keys for the vim emulation handler that resize the block.
+    fn handle_block_key(&mut self, key: KeyEvent) -> bool {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        let keep = |caret: Caret| caret;
+        match (self.settings.keymap, key.code) {
+            (_, KeyCode::Esc) => self.cancel_block_selection(),
+            (Keymap::Vim, KeyCode::Char('I')) => {
+                self.carets_from_block(|caret| Caret::new(caret.range().0));
+                self.sync_carets();
+            }
+            (Keymap::Vim, KeyCode::Char('A')) => {
+                self.carets_from_block(|caret| Caret::new(caret.range().1));
+                self.sync_carets();
+            }
+            (Keymap::Vim, KeyCode::Char('c')) => {
+                self.carets_from_block(keep);
+                self.edit_carets(false, |cursors, lines| cursors.delete_selections(lines));
+            }
+            (Keymap::Vim, KeyCode::Char('d' | 'x')) => {
+                self.carets_from_block(keep);
+                self.edit_carets(false, |cursors, lines| cursors.delete_selections(lines));
+                self.clear_carets();
+                self.state.mode = EditorMode::Normal;
+            }
+            (Keymap::Vim, KeyCode::Char('y')) => {
+                self.copy_block();
+                self.cancel_block_selection();
+            }
+            (Keymap::Vim, _) => return false,
+            (Keymap::Standard, KeyCode::Char('c')) if control => self.copy_block(),
+            (
+                Keymap::Standard,
+                KeyCode::Left
+                | KeyCode::Right
+                | KeyCode::Up
+                | KeyCode::Down
+                | KeyCode::Home
+                | KeyCode::End,
+            ) => {
+                // Movement is passed on without SHIFT, which vim emulation may not expect.
+                let key = KeyEvent::new(key.code, key.modifiers - KeyModifiers::SHIFT);
+                self.event_handler
+                    .on_event(Event::Key(key), &mut self.state);
+            }
+            // Any other input edits every row of the block.
+            (Keymap::Standard, _) => {
+                self.carets_from_block(keep);
+                self.sync_carets();
+                if self.cursors.carets().is_empty() {
+                    return false;
+                }
+                self.handle_carets_key(key);
+            }
+        }
+        true
+    }
+
+    /// The line break inserted at the cursor. With auto-indent the new line has the indentation of
+    /// the current line, and one more level after an opening bracket.
+    fn newline(&self) -> String {
+        if !self.settings.auto_indent {
+            return "\n".to_string();
+        }
+        let Some(line) = self.line(self.state.cursor.row) else {
+            return "\n".to_string();
+        };
+        let indent = text::indentation(&line);
+        let before: String = line.chars().take(self.state.cursor.col).collect();
+        match before
+            .trim_end()
+            .chars()
+            .last()
+            .and_then(text::closing_bracket)
+        {
+            Some(_) => format!("\n{indent}{}", self.indent.unit(indent.chars().count())),
+            None => format!("\n{indent}"),
+        }
+    }
+
+    /// Records an edit typed in insert mode. With the vim keymap it joins the undo step for the
+    /// current visit to insert mode.
+    fn edit_typed(&mut self, cursor: Index2) {
+        self.state.cursor = cursor;
+        self.record_edit(self.insert_group);
+        self.insert_group = self.settings.keymap == Keymap::Vim;
+    }
+
+    /// Handles key input in insert mode for indentation and bracket pairs.
+    /// Returns false for input that should be passed on to the vim emulation handler.
+    fn handle_insert_key(&mut self, key: KeyEvent) -> bool {
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return false;
+        }
+        let cursor = self.state.cursor;
+        let Some(line) = self.state.lines.get(RowIndex::new(cursor.row)) else {
+            return false;
+        };
+        let prev = cursor
+            .col
+            .checked_sub(1)
+            .and_then(|col| line.get(col))
+            .copied();
+        let next = line.get(cursor.col).copied();
+        let is_closing = |c: char| matches!(c, ')' | ']' | '}' | '"' | '\'' | '`');
+        let pair = |c: char| match c {
+            '"' | '\'' | '`' => Some(c),
+            c => text::closing_bracket(c),
+        };
+        match key.code {
+            KeyCode::Enter => {
+                let newline = self.newline();
+                let end = text::replace_range(&mut self.state.lines, cursor, cursor, &newline);
+                // Between a pair of brackets the closing bracket is moved to its own line.
+                if self.settings.auto_indent
+                    && prev
+                        .and_then(text::closing_bracket)
+                        .is_some_and(|c| next == Some(c))
+                {
+                    let line = self.line(cursor.row).unwrap_or_default();
+                    let indent = format!("\n{}", text::indentation(&line));
+                    text::replace_range(&mut self.state.lines, end, end, &indent);
+                }
+                self.edit_typed(end);
+            }
+            KeyCode::Tab => {
+                let unit = self.indent.unit(cursor.col);
+                let end = text::replace_range(&mut self.state.lines, cursor, cursor, &unit);
+                self.edit_typed(end);
+            }
+            // Typing a closing character that is already next to the cursor moves over it.
+            KeyCode::Char(c) if self.settings.auto_close && is_closing(c) && next == Some(c) => {
+                self.state.cursor.col += 1;
+            }
+            KeyCode::Char(c) if self.settings.auto_close && pair(c).is_some() => {
+                // Only insert a pair before whitespace or a closing character, and never close
+                // a quote typed after a word, such as an apostrophe.
+                let quote = pair(c) == Some(c);
+                if next.is_some_and(|n| !n.is_whitespace() && !is_closing(n))
+                    || (quote && prev.is_some_and(|p| p.is_alphanumeric()))
+                {
+                    return false;
+                }
+                let close = pair(c).unwrap_or(c);
+                let pair = format!("{c}{close}");
+                text::replace_range(&mut self.state.lines, cursor, cursor, &pair);
+                self.edit_typed(Index2::new(cursor.row, cursor.col + 1));
+            }
+            // Deleting the opening character of an empty pair deletes both.
+            KeyCode::Backspace
+                if self.settings.auto_close
+                    && prev.and_then(pair).is_some_and(|c| next == Some(c)) =>
+            {
+                let start = Index2::new(cursor.row, cursor.col - 1);
+                let end = Index2::new(cursor.row, cursor.col + 1);
+                text::replace_range(&mut self.state.lines, start, end, "");
+                self.edit_typed(start);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Opens a line below or above the cursor in vim normal mode, as `o` and `O` do, with the
+    /// indentation of the current line.
+    fn open_line(&mut self, below: bool) {
+        let row = self.state.cursor.row;
+        let Some(line) = self.line(row) else {
+            return;
+        };
+        let cursor = match below {
+            true => {
+                let end = Index2::new(row, line.chars().count());
+                self.state.cursor = end;
+                let newline = self.newline();
+                text::replace_range(&mut self.state.lines, end, end, &newline)
+            }
+            false => {
+                let indent = text::indentation(&line);
+                let start = Index2::new(row, 0);
+                text::replace_range(&mut self.state.lines, start, start, &format!("{indent}\n"));
+                Index2::new(row, indent.chars().count())
+            }
+        };
+        self.state.mode = EditorMode::Insert;
+        self.insert_group = false;
+        self.edit_typed(cursor);
+    }
+
+    /// The selected range in the standard keymap, ordered from start to end.
+    fn selection(&self) -> Option<(Index2, Index2)> {
+        let anchor = self.selection_anchor?;
+        (anchor != self.state.cursor).then(|| text::ordered(anchor, self.state.cursor))
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selection()?;
+        Some(text::text_in_range(&self.text_lines(), start, end))
+    }
+
+    /// Deletes the selected text as one undoable edit, returning false if nothing was selected.
+    fn delete_selection(&mut self) -> bool {
+        let Some((start, end)) = self.selection() else {
+            return false;
+        };
+        self.state.cursor = text::replace_range(&mut self.state.lines, start, end, "");
+        self.selection_anchor = None;
+        self.record_edit(false);
+        self.insert_group = false;
+        true
+    }
+
+    /// Replaces the selection, or inserts at the cursor, as one undoable edit.
+    fn insert_text(&mut self, inserted: &str) {
+        let (start, end) = self
+            .selection()
+            .unwrap_or((self.state.cursor, self.state.cursor));
+        self.state.cursor = text::replace_range(&mut self.state.lines, start, end, inserted);
+        self.selection_anchor = None;
+        self.record_edit(false);
+        self.insert_group = false;
+    }
+
+    /// Handles key input for the standard keymap that differs from vim's insert mode.
+    /// Returns false for input that should be passed on to the vim emulation handler.
+    fn handle_standard_key(&mut self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            // Insert mode is never left, so ESC only clears the selection.
+            KeyCode::Esc => self.selection_anchor = None,
+            KeyCode::Char('z') if control => self.undo(),
+            KeyCode::Char('y') if control => self.redo(),
+            KeyCode::Char('a') if control => {
+                let lines = self.text_lines();
+                let last = lines.len().saturating_sub(1);
+                self.selection_anchor = Some(Index2::new(0, 0));
+                self.state.cursor = Index2::new(last, lines[last].chars().count());
+            }
+            KeyCode::Char('c') if control => {
+                if let Some(selected) = self.selected_text() {
+                    self.clipboard.copy(&selected);
+                }
+            }
+            KeyCode::Char('x') if control => {
+                if let Some(selected) = self.selected_text() {
+                    self.clipboard.copy(&selected);
+                    self.delete_selection();
+                }
+            }
+            KeyCode::Char('v') if control => {
+                let pasted = self.clipboard.paste();
+                self.insert_text(&pasted);
+            }
+            KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Home
+            | KeyCode::End
+            | KeyCode::PageUp
+            | KeyCode::PageDown => {
+                match shift {
+                    true => {
+                        self.selection_anchor.get_or_insert(self.state.cursor);
+                    }
+                    false => self.selection_anchor = None,
+                }
+                match key.code {
+                    KeyCode::Home => self.state.cursor.col = 0,
+                    KeyCode::End => {
+                        let row = self.state.cursor.row;
+                        self.state.cursor.col = self.state.lines.len_col(row).unwrap_or(0);
+                    }
+                    // Movement is passed on without SHIFT, which vim emulation may not expect.
+                    code => {
+                        let key = KeyEvent::new(code, key.modifiers - KeyModifiers::SHIFT);
+                        let previous_row = self.state.cursor.row;
+                        self.event_handler
+                            .on_event(Event::Key(key), &mut self.state);
+                        self.skip_folded_rows(previous_row);
+                    }
+                }
+                self.insert_group = false;
+            }
+            KeyCode::Backspace | KeyCode::Delete => return self.delete_selection(),
+            KeyCode::Enter if self.selection().is_some() => self.insert_text(&self.newline()),
+            // Typing over a selection replaces it.
+            KeyCode::Char(c) if !control && self.selection().is_some() => {
+                self.insert_text(&c.to_string())
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Handles text pasted into the terminal. Bracketed paste delivers the text in one event, so it
+    /// is inserted as a single undoable edit instead of being typed one key at a time.
+    fn paste(&mut self, pasted: &str) -> Result<Action> {
+        let pasted = pasted.replace("\r\n", "\n").replace('\r', "\n");
+        trace!(target:Self::ID, "Pasting {} characters", pasted.chars().count());
+        // The command line and search bar are single line inputs, so control characters are dropped.
+        let keys = pasted
+            .chars()
+            .filter(|c| !c.is_control())
+            .map(|c| KeyEvent::from(KeyCode::Char(c)));
+        if self.command_line.is_visible() {
+            keys.for_each(|key| {
+                self.command_line.handle_key_events(key);
+            });
+        } else if self.search.is_visible() && !self.undo_tree.is_visible() {
+            keys.for_each(|key| {
+                self.search.handle_key_events(key);
+            });
+            self.handle_search_event(SearchEvent::Changed)?;
+        } else if !self.cursors.carets().is_empty() {
+            let texts = [pasted];
+            self.edit_carets(false, |cursors, lines| cursors.insert(lines, &texts));
+        } else if !self.undo_tree.is_visible() {
+            self.insert_text(&pasted);
+        }
+        Ok(Action::Handled)
+    }
+
+    /// Writes the buffer to the file, using the line endings, encoding and final newline from any
+    /// `.editorconfig` files, or otherwise those the file was read with.
+    pub fn save(&mut self) -> Result<()> {
+        if self.image.is_some() {
+            bail!("Images can't be saved.")
+        }
+        if self.open_error.is_some() {
+            bail!("Files that failed to open can't be saved.")
+        }
+        if self.read_only {
+            bail!("Files that couldn't be decoded are read-only.")
+        }
+        if let Some(path) = self.file_path.clone() {
+            trace!(target:Self::ID, "Saving Editor contents {:?}", path);
+            // Pick up changes made to `.editorconfig` files since the file was opened.
+            self.editorconfig = EditorConfig::for_file(&path);
+            self.format.configure(&self.editorconfig);
+            self.run_formatter(&path);
+            if self.format.trim_trailing_whitespace {
+                self.trim_trailing_whitespace();
+            }
+            std::fs::write(&path, self.format.encode(&self.state.lines.to_string()))?;
+            // New files can only be resolved once they exist.
+            self.canonical_path = Some(Self::canonicalize(&path));
+            if self.settings.persist_undo
+                && let Err(e) = self.history.borrow().save(&path)
+            {
+                error!(target:History::ID, "Failed to save undo history: {e:#}");
+            }
+            return Ok(());
+        };
+        error!(target:Self::ID, "Failed saving Editor contents; file_path was None");
+        bail!("File not saved. No file path set.")
+    }
+
+    /// Formats the buffer with the formatter set for its language, as a single undoable edit.
+    /// If the formatter fails, the error is logged and the buffer is left as it was.
+    fn run_formatter(&mut self, path: &std::path::Path) {
+        let Some(command) = self
+            .settings
+            .formatter_for(&self.language)
+            .map(<[String]>::to_vec)
+        else {
+            return;
+        };
+        let text = self.state.lines.to_string();
+        // Formatters expect files to end with a newline.
+        match formatter::format(&command, path, &format!("{text}\n")) {
+            Ok(formatted) => {
+                let formatted = formatted.replace("\r\n", "\n");
+                let formatted = formatted.strip_suffix('\n').unwrap_or(&formatted);
+                if formatted != text {
+                    // The cursor stays at the same row and column, moved back within the buffer
+                    // if the line it was on became shorter.
+                    self.clear_carets();
+                    let lines: Vec<String> = formatted.split('\n').map(String::from).collect();
+                    self.edit_lines(&lines);
+                    info!(target:Self::ID, "Formatted {path:?} with {}", command[0]);
+                }
+            }
+            Err(e) => {
+                error!(target:Self::ID, "Failed to format {path:?}; Saving without formatting: {e:#}")
+            }
+        }
+    }
+
+    /// Removes whitespace from the end of each line as a single undoable edit.
+    fn trim_trailing_whitespace(&mut self) {
+        let lines = self.text_lines();
+        let trimmed: Vec<String> = lines
+            .iter()
+            .map(|line| line.trim_end().to_string())
+            .collect();
+        if trimmed != lines {
+            self.edit_lines(&trimmed);
+        }
+    }
+}
+
+/// Structural selection using the syntax tree of the buffer.
+#[cfg(feature = "tree-sitter")]
+impl Editor {
+    /// The syntax tree updated with the current buffer, if the language has a tree-sitter grammar.
+    fn updated_syntax_tree(&mut self) -> Option<&SyntaxTree> {
+        let text = self.state.lines.to_string();
+        let Some(tree) = &mut self.syntax_tree else {
+            info!(target:Self::ID, "No tree-sitter grammar for {}", self.language);
+            return None;
+        };
+        tree.update(&text);
+        Some(tree)
+    }
+
+    /// The selected range, or the cursor if nothing is selected. The end of the range is exclusive,
+    /// so vim selections end after the character at their end.
+    fn selected_range(&self) -> (Index2, Index2) {
+        let cursor = (self.state.cursor, self.state.cursor);
+        match (self.settings.keymap, &self.state.selection) {
+            (Keymap::Standard, _) => self.selection().unwrap_or(cursor),
+            (Keymap::Vim, Some(selection)) => {
+                let (start, end) = text::ordered(selection.start, selection.end);
+                (start, Index2::new(end.row, end.col + 1))
+            }
+            (Keymap::Vim, None) => cursor,
+        }
+    }
+
+    /// Selects a range, or clears the selection if the range is empty.
+    fn select_range(&mut self, (start, end): (Index2, Index2)) {
+        self.folds.open_at(start.row);
+        self.folds.open_at(end.row);
+        match (self.settings.keymap, start == end) {
+            (Keymap::Standard, empty) => {
+                self.selection_anchor = (!empty).then_some(start);
+                self.state.cursor = end;
+            }
+            (Keymap::Vim, true) => {
+                self.state.selection = None;
+                self.state.mode = EditorMode::Normal;
+                self.state.cursor = start;
+            }
+            (Keymap::Vim, false) => {
+                let end = Index2::new(end.row, end.col.saturating_sub(1));
+                // Visual mode starts a selection at the cursor, which is then moved to the range.
+                self.state.cursor = start;
+                SwitchMode(EditorMode::Visual).execute(&mut self.state);
+                if let Some(selection) = &mut self.state.selection {
+                    selection.end = end;
+                }
+                self.state.cursor = end;
+            }
+        }
+    }
+
+    /// Selects the smallest syntax node enclosing the selection, or the node at the cursor.
+    fn expand_selection(&mut self) {
+        let (start, end) = self.selected_range();
+        let Some(tree) = self.updated_syntax_tree() else {
+            return;
+        };
+        let Some(expanded) =
+            tree.expand(tree.offset(start.row, start.col)..tree.offset(end.row, end.col))
+        else {
+            return;
+        };
+        let position = |offset| {
+            let (row, col) = tree.position(offset);
+            Index2::new(row, col)
+        };
+        let expanded = (position(expanded.start), position(expanded.end));
+        // Start again if the selection was changed since it was last expanded.
+        if self.expanded.last() != Some(&(start, end)) {
+            self.expanded = vec![(start, end)];
+        }
+        self.expanded.push(expanded);
+        self.select_range(expanded);
+    }
+
+    /// Returns to the selection from before it was last expanded.
+    fn shrink_selection(&mut self) {
+        if self.expanded.len() < 2 || self.expanded.last() != Some(&self.selected_range()) {
+            self.expanded.clear();
+            return;
+        }
+        self.expanded.pop();
+        if let Some(previous) = self.expanded.last().copied() {
+            self.select_range(previous);
+        }
+    }
+
+    /// Moves the cursor to the start of the syntax node enclosing the node at the cursor.
+    fn goto_parent_node(&mut self) {
+        let cursor = self.state.cursor;
+        let Some(tree) = self.updated_syntax_tree() else {
+            return;
+        };
+        let Some((row, col)) = tree
+            .parent(tree.offset(cursor.row, cursor.col))
+            .map(|offset| tree.position(offset))
+        else {
+            return;
+        };
+        self.folds.open_at(row);
+        self.state.cursor = Index2::new(row, col);
+    }
+}
+
+impl Widget for &mut Editor {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.merge.is_visible() {
+            self.merge.render(area, buf);
+            return;
+        }
+        let (area, undo_tree_area) = match self.undo_tree.is_visible() {
+            true => {
+                let [area, undo_tree_area] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Length(UndoTree::WIDTH)])
+                        .areas(area);
+                (area, Some(undo_tree_area))
+            }
+            false => (area, None),
+        };
+        let (area, preview_area) = match self.preview.is_visible() && self.is_markdown() {
+            true => {
+                let [area, preview_area] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+                (area, Some(preview_area))
+            }
+            false => (area, None),
+        };
+        let (area, search_area) = match self.search.is_visible() {
+            true => {
+                let [area, search_area] =
+                    Layout::vertical([Constraint::Fill(1), Constraint::Length(SearchBar::HEIGHT)])
+                        .areas(area);
+                (area, Some(search_area))
+            }
+            false => (area, None),
+        };
+        let block = Block::default()
+            .title(match self.read_only {
+                true => format!("{} [read-only]", self.language),
+                false => self.language.to_owned(),
+            })
+            .title_style(Style::default().fg(Color::Yellow))
+            .title_alignment(Alignment::Right)
+            .borders(Borders::ALL)
+            .padding(Padding::new(0, 0, 0, 1))
+            .style(Style::default().fg(self.component_state.get_active_color()));
+        // The editing mode is shown in the App status bar, so the EditorView status line is
+        // hidden and the padding row below the text is used for the command line.
+        let text_area = block.inner(area);
+        let command_line_area = Rect {
+            y: text_area.bottom(),
+            height: 1,
+            ..text_area
+        };
+        block.render(area, buf);
+        if let Some(error) = &self.open_error {
+            Paragraph::new(error.as_str())
+                .style(Style::default().fg(Color::Red))
+                .wrap(Wrap { trim: false })
+                .render(text_area, buf);
+            return;
+        }
+        if let Some(image) = &mut self.image {
+            image.render(text_area, buf);
+            buf.set_string(
+                command_line_area.x,
+                command_line_area.y,
+                image.info.summary(),
+                Editor::GUTTER_STYLE,
+            );
+            return;
+        }
+        let lines = self.text_lines();
+        // Room for a breakpoint and the largest line number, followed by a fold marker and a space
+        // on each side.
+        let gutter_width = lines.len().to_string().len() as u16 + 4;
+        let [gutter_area, view_area] =
+            Layout::horizontal([Constraint::Length(gutter_width), Constraint::Fill(1)])
+                .areas(text_area);
+        self.gutter_area = gutter_area;
+        let theme = EditorTheme::default()
+            .cursor_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(EditorOverlay::CURSOR_BG),
+            )
+            .hide_status_line();
+
+        self.update_fold_state(&lines);
+        let highlighter = self.highlighter();
+        // Text is colored from the syntax tree when drawing the overlay instead.
+        #[cfg(feature = "tree-sitter")]
+        let highlighter = match &mut self.syntax_tree {
+            Some(tree) => {
+                tree.update(&lines.join("\n"));
+                None
+            }
+            None => highlighter,
+        };
+        let view_state = match self.fold_rows.is_empty() {
+            true => &mut self.state,
+            false => &mut self.fold_state,
+        };
+        EditorView::new(view_state)
+            .wrap(true)
+            .theme(theme)
+            .syntax_highlighter(highlighter)
+            .tab_width(self.indent.width)
+            .line_numbers(LineNumbers::None)
+            .render(view_area, buf);
+        self.render_gutter(&lines, view_area, buf);
+        if let Some(preview_area) = preview_area {
+            // The preview scrolls to the first buffer row drawn by the EditorView.
+            let top_row = self.gutter_rows.first().map_or(0, |(_, row)| *row);
+            self.preview
+                .render(preview_area, buf, &lines.join("\n"), top_row);
+        }
+
+        if let Some(search_area) = search_area {
+            // Matches are found again after the buffer changes, since another split may edit it.
+            if self.searched_revision != self.revision {
+                self.search.update(&lines);
+                self.searched_revision = self.revision;
+            }
+            self.search.render(search_area, buf);
+        }
+        self.render_overlay(&lines, view_area, buf);
+        if area.contains(command_line_area.as_position()) {
+            self.command_line.render(command_line_area, buf);
+        }
+        if let Some(undo_tree_area) = undo_tree_area {
+            self.undo_tree
+                .render(undo_tree_area, buf, &self.history.borrow());
+        }
+    }
+}
+
+impl Component for Editor {
+    fn handle_event(&mut self, event: Event) -> Result<Action> {
+        // Images and files that failed to open can't be edited, so input is only handled by the
+        // tabs and splits.
+        if self.image.is_some() || self.open_error.is_some() {
+            return Ok(Action::Noop);
+        }
+        // All input goes to the merge view while it is open.
+        if self.merge.is_visible() {
+            if let Some(key_event) = event.as_key_event() {
+                let merge_event = self.merge.handle_key_events(key_event);
+                self.handle_merge_event(merge_event);
+            } else if let Some(mouse_event) = event.as_mouse_event() {
+                let merge_event = self.merge.handle_mouse_events(mouse_event);
+                self.handle_merge_event(merge_event);
+            }
+            return Ok(Action::Handled);
+        }
+        if let Event::Paste(pasted) = &event {
+            return self.paste(pasted);
+        }
+        if let Some(key_event) = event.as_key_event() {
+            // Handle events here that should not be passed on to the vim emulation handler.
+            match self.handle_key_events(key_event)? {
+                Action::Handled => return Ok(Action::Handled),
+                action @ (Action::ToggleBreakpoint(..) | Action::Save) => return Ok(action),
+                _ => {}
+            }
+            // Any error from the last command is shown until the next key is pressed.
+            self.command_line.clear_message();
+            if self.command_line.is_visible() {
+                let command_line_event = self.command_line.handle_key_events(key_event);
+                return Ok(self.handle_command_line_event(command_line_event));
+            }
+            // All other key input goes to the undo tree or search bar while either is open.
+            if self.undo_tree.is_visible() {
+                let undo_tree_event = self.undo_tree.handle_key_events(key_event);
+                self.handle_undo_tree_event(undo_tree_event);
+                return Ok(Action::Handled);
+            }
+            if self.search.is_visible() {
+                let search_event = self.search.handle_key_events(key_event);
+                if let Err(e) = self.handle_search_event(search_event) {
+                    error!(target:SearchBar::ID, "Failed to search: {e:#}");
+                }
+                return Ok(Action::Handled);
+            }
+            if self.block_anchor.is_some() && self.handle_block_key(key_event) {
+                return Ok(Action::Handled);
+            }
+            if !self.cursors.carets().is_empty() {
+                self.handle_carets_key(key_event);
+                return Ok(Action::Handled);
+            }
+            if self.settings.keymap == Keymap::Standard && self.handle_standard_key(key_event) {
+                return Ok(Action::Handled);
+            }
+            if self.state.mode == EditorMode::Insert && self.handle_insert_key(key_event) {
+                return Ok(Action::Handled);
+            }
+        }
+        // Clicking to move the cursor clears the selection and any extra cursors.
+        if let Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(_),
+            ..
+        }) = event
+        {
+            self.selection_anchor = None;
+            self.cancel_block_selection();
+            self.clear_carets();
+        }
+        if let Event::Mouse(mouse) = event {
+            if let Some(row) = self.breakpoint_click(mouse) {
+                return Ok(self.toggle_breakpoint(row));
+            }
+            if self.handle_fold_mouse(mouse) {
+                return Ok(Action::Handled);
+            }
+        }
+        self.handle_vim_event(event);
+        Ok(Action::Pass)
+    }
+
+    /// The events for the vim emulation should be handled by EditorEventHandler::on_event.
+    /// These events are custom to the clide application.
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        if self.pending_fold {
+            self.pending_fold = false;
+            if let KeyCode::Char(command) = key.code {
+                self.fold(command);
+            }
+            return Ok(Action::Handled);
+        }
+        match key {
+            KeyEvent {
+                code: KeyCode::Char('z'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } if self.is_vim_normal_mode() => {
+                self.pending_fold = true;
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('f'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.fold('a');
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('F'),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT) => {
+                match self.folds.is_empty() {
+                    true => self.fold('M'),
+                    false => self.fold('R'),
+                }
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('s'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => Ok(Action::Save),
+            KeyEvent {
+                code: KeyCode::Char('f'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.open_search();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('u'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.toggle_undo_tree();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('m'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.toggle_preview();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char(c @ ('O' | 'T' | 'B')),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT) => {
+                self.accept_conflict(match c {
+                    'O' => Resolution::Ours,
+                    'T' => Resolution::Theirs,
+                    _ => Resolution::Both,
+                });
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char(c @ ('N' | 'P')),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT) => {
+                self.goto_conflict(c == 'N');
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('M'),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT) => {
+                self.toggle_merge_view();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::F(9),
+                ..
+            } => Ok(self.toggle_breakpoint(self.state.cursor.row)),
+            KeyEvent {
+                code: KeyCode::Char(':'),
+                ..
+            } if self.is_vim_normal_mode() => {
+                self.open_command_line();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('/'),
+                ..
+            } if self.is_vim_normal_mode() => {
+                self.open_search();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Up | KeyCode::Char('k'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.add_caret_vertical(true);
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Down | KeyCode::Char('j'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.add_caret_vertical(false);
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.add_next_occurrence();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('b'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.start_block_selection();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('v'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } if self.is_vim_normal_mode() => {
+                self.start_block_selection();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char(c @ ('o' | 'O')),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                ..
+            } if self.is_vim_normal_mode() && self.settings.auto_indent => {
+                self.open_line(c == 'o');
+                Ok(Action::Handled)
+            }
+            // Undo is handled by clide so edits made outside of vim emulation can be undone.
+            KeyEvent {
+                code: KeyCode::Char('u'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } if self.is_vim_normal_mode() => {
+                self.undo();
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } if self.is_vim_normal_mode() => {
+                self.redo();
+                Ok(Action::Handled)
+            }
+            #[cfg(feature = "tree-sitter")]
+            KeyEvent {
+                code: KeyCode::Char('o'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.expand_selection();
+                Ok(Action::Handled)
+            }
+            #[cfg(feature = "tree-sitter")]
+            KeyEvent {
+                code: KeyCode::Char('i'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.shrink_selection();
+                Ok(Action::Handled)
+            }
+            #[cfg(feature = "tree-sitter")]
+            KeyEvent {
+                code: KeyCode::Char('a'),
+                modifiers: KeyModifiers::ALT,
                 ..
             } => {
-                self.save().context("Failed to save file.")?;
+                self.goto_parent_node();
                 Ok(Action::Handled)
             }
             // For other events not handled here, pass to the vim emulation handler.
@@ -0,0 +1,472 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::tui::component::{Action, Component, Focus};
+use crate::tui::editor::Editor;
+use crate::tui::editor_tab::EditorTab;
+use anyhow::{Context, Result, bail};
+use log::{info, trace};
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Constraint, Direction, Layout, Position, Rect};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A node in the tree of editor splits.
+/// Each leaf is a pane with its own tab strip, and each split divides its area between two nodes.
+#[derive(Debug)]
+enum SplitNode {
+    Pane(usize),
+    Split {
+        direction: Direction,
+        /// Percentage of the split area given to the first node.
+        ratio: u16,
+        first: Box<SplitNode>,
+        second: Box<SplitNode>,
+    },
+}
+
+impl SplitNode {
+    const MIN_RATIO: u16 = 10;
+    const MAX_RATIO: u16 = 90;
+
+    /// Replaces the pane `id` with a split holding the original pane and the pane `new_id`.
+    fn split(self, id: usize, direction: Direction, new_id: usize) -> SplitNode {
+        match self {
+            SplitNode::Pane(pane) if pane == id => SplitNode::Split {
+                direction,
+                ratio: 50,
+                first: Box::new(SplitNode::Pane(pane)),
+                second: Box::new(SplitNode::Pane(new_id)),
+            },
+            SplitNode::Pane(_) => self,
+            SplitNode::Split {
+                direction: d,
+                ratio,
+                first,
+                second,
+            } => SplitNode::Split {
+                direction: d,
+                ratio,
+                first: Box::new(first.split(id, direction, new_id)),
+                second: Box::new(second.split(id, direction, new_id)),
+            },
+        }
+    }
+
+    /// Removes the pane `id`, collapsing its parent split into the remaining sibling.
+    fn remove(self, id: usize) -> Option<SplitNode> {
+        match self {
+            SplitNode::Pane(pane) if pane == id => None,
+            SplitNode::Pane(_) => Some(self),
+            SplitNode::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => match (first.remove(id), second.remove(id)) {
+                (Some(first), Some(second)) => Some(SplitNode::Split {
+                    direction,
+                    ratio,
+                    first: Box::new(first),
+                    second: Box::new(second),
+                }),
+                (Some(node), None) | (None, Some(node)) => Some(node),
+                (None, None) => None,
+            },
+        }
+    }
+
+    /// Grows the pane `id` by `delta` percent within the split that directly contains it.
+    fn resize(&mut self, id: usize, delta: i16) -> bool {
+        let SplitNode::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        if matches!(**first, SplitNode::Pane(pane) if pane == id) {
+            *ratio = ratio
+                .saturating_add_signed(delta)
+                .clamp(Self::MIN_RATIO, Self::MAX_RATIO);
+            true
+        } else if matches!(**second, SplitNode::Pane(pane) if pane == id) {
+            *ratio = ratio
+                .saturating_add_signed(-delta)
+                .clamp(Self::MIN_RATIO, Self::MAX_RATIO);
+            true
+        } else {
+            first.resize(id, delta) || second.resize(id, delta)
+        }
+    }
+
+    /// Collects the area of each pane in the tree.
+    fn layout(&self, area: Rect, areas: &mut Vec<(usize, Rect)>) {
+        match self {
+            SplitNode::Pane(pane) => areas.push((*pane, area)),
+            SplitNode::Split {
+                direction,
+                ratio,
+                first,
+                second,
+            } => {
+                let split = Layout::default()
+                    .direction(*direction)
+                    .constraints([Constraint::Percentage(*ratio), Constraint::Fill(1)])
+                    .split(area);
+                first.layout(split[0], areas);
+                second.layout(split[1], areas);
+            }
+        }
+    }
+}
+
+/// The editor area of the application, split recursively into panes of EditorTabs.
+/// Editors for the same file in different panes share their buffer contents and undo history.
+pub struct EditorLayout {
+    root: SplitNode,
+    panes: HashMap<usize, EditorTab>,
+    focused: usize,
+    next_id: usize,
+    /// The areas each pane was last rendered to, used for directional focus and mouse input.
+    areas: Vec<(usize, Rect)>,
+    /// The focused pane, the file open in it and the revision of its buffer when buffers were last
+    /// synced, so the buffer is only copied to other panes after it is edited.
+    synced: Option<(usize, PathBuf, u64)>,
+}
+
+impl EditorLayout {
+    pub const ID: &str = "EditorLayout";
+
+    /// The percentage a split is resized by for each key press.
+    const RESIZE_STEP: i16 = 5;
+
+    pub fn new() -> Self {
+        trace!(target:Self::ID, "Building {}", Self::ID);
+        Self {
+            root: SplitNode::Pane(0),
+            panes: HashMap::from([(0, EditorTab::new())]),
+            focused: 0,
+            next_id: 1,
+            areas: Vec::new(),
+            synced: None,
+        }
+    }
+
+    pub fn current_tab(&self) -> Option<&EditorTab> {
+        self.panes.get(&self.focused)
+    }
+
+    pub fn current_tab_mut(&mut self) -> Option<&mut EditorTab> {
+        self.panes.get_mut(&self.focused)
+    }
+
+    pub fn current_editor(&self) -> Option<&Editor> {
+        self.current_tab()?.current_editor()
+    }
+
+    pub fn current_editor_mut(&mut self) -> Option<&mut Editor> {
+        self.current_tab_mut()?.current_editor_mut()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.panes.values().all(|tab| tab.is_empty())
+    }
+
+    /// Finds the editor for a file opened in any pane.
+    pub fn find_editor(&self, path: &std::path::Path) -> Option<&Editor> {
+        self.panes.values().find_map(|tab| tab.find_editor(path))
+    }
+
+    /// Every editor open in any pane.
+    pub fn editors_mut(&mut self) -> impl Iterator<Item = &mut Editor> {
+        self.panes
+            .values_mut()
+            .flat_map(|tab| tab.editors.values_mut())
+    }
+
+    pub fn open_tab(&mut self, path: &std::path::Path) -> Result<()> {
+        self.current_tab_mut()
+            .context("Failed to get focused editor pane")?
+            .open_tab(path)?;
+        // If the file is already open in another pane, share its buffer.
+        if let Some(mut tab) = self.panes.remove(&self.focused) {
+            if let Some(other) = self.find_editor(path)
+                && let Some(editor) = tab.find_editor_mut(path)
+            {
+                editor.share_buffer(other);
+            }
+            self.panes.insert(self.focused, tab);
+        }
+        Ok(())
+    }
+
+    /// Closes the current tab in the focused pane, closing the pane itself if it becomes empty.
+    pub fn close_current_tab(&mut self) -> Result<()> {
+        self.current_tab_mut()
+            .context("Failed to get focused editor pane")?
+            .close_current_tab()?;
+        self.close_focused_if_empty()
+    }
+
+    /// Closes the focused pane if it has no tabs left and it is not the only pane.
+    fn close_focused_if_empty(&mut self) -> Result<()> {
+        let empty = self.current_tab().is_some_and(|tab| tab.is_empty());
+        if empty && self.panes.len() > 1 {
+            self.close_pane(self.focused)?;
+        }
+        Ok(())
+    }
+
+    /// Splits the focused pane, opening the current file in the new pane.
+    pub fn split(&mut self, direction: Direction) -> Result<()> {
+        let editor = self
+            .current_editor()
+            .context("Failed to split editor pane with no open tabs")?;
+        let path = editor
+            .file_path
+            .clone()
+            .context("Failed to split editor without a file path")?;
+
+        let mut tab = EditorTab::new();
+        tab.open_tab(&path)?;
+        if let Some(new_editor) = tab.current_editor_mut() {
+            new_editor.share_buffer(editor);
+            new_editor.state.cursor = editor.state.cursor;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        info!(target:Self::ID, "Splitting pane {} {:?} into new pane {id}", self.focused, direction);
+        let root = std::mem::replace(&mut self.root, SplitNode::Pane(0));
+        self.root = root.split(self.focused, direction, id);
+        self.panes.insert(id, tab);
+        self.focus_pane(id);
+        Ok(())
+    }
+
+    /// Closes a pane and all of its tabs, focusing the first remaining pane.
+    pub fn close_pane(&mut self, id: usize) -> Result<()> {
+        if self.panes.len() <= 1 {
+            bail!("Failed to close the last editor pane");
+        }
+        info!(target:Self::ID, "Closing editor pane {id}");
+        let root = std::mem::replace(&mut self.root, SplitNode::Pane(0));
+        self.root = root
+            .remove(id)
+            .context("Failed to close the last editor pane")?;
+        self.panes.remove(&id);
+        self.areas.retain(|(pane, _)| *pane != id);
+        if let Some(next) = self.panes.keys().min().copied() {
+            self.focused = next;
+            self.focus_pane(next);
+        }
+        Ok(())
+    }
+
+    pub fn resize(&mut self, delta: i16) {
+        trace!(target:Self::ID, "Resizing pane {} by {delta}%", self.focused);
+        self.root.resize(self.focused, delta);
+    }
+
+    fn focus_pane(&mut self, id: usize) {
+        if !self.panes.contains_key(&id) {
+            return;
+        }
+        trace!(target:Self::ID, "Focusing editor pane {id}");
+        if let Some(tab) = self.panes.get_mut(&self.focused) {
+            tab.set_current_tab_focus(Focus::Inactive);
+        }
+        self.focused = id;
+        if let Some(tab) = self.panes.get_mut(&id) {
+            tab.set_current_tab_focus(Focus::Active);
+        }
+    }
+
+    /// Moves focus to the nearest pane in the direction of (dx, dy).
+    fn focus_direction(&mut self, dx: i32, dy: i32) {
+        let Some((_, current)) = self.areas.iter().find(|(id, _)| *id == self.focused) else {
+            return;
+        };
+        let center = |r: &Rect| {
+            (
+                r.x as i32 + r.width as i32 / 2,
+                r.y as i32 + r.height as i32 / 2,
+            )
+        };
+        let (cx, cy) = center(current);
+        let next = self
+            .areas
+            .iter()
+            .filter(|(id, r)| {
+                *id != self.focused
+                    && match (dx.signum(), dy.signum()) {
+                        (1, _) => r.x >= current.right(),
+                        (-1, _) => r.right() <= current.x,
+                        (_, 1) => r.y >= current.bottom(),
+                        (_, -1) => r.bottom() <= current.y,
+                        _ => false,
+                    }
+            })
+            .min_by_key(|(_, r)| {
+                let (x, y) = center(r);
+                (x - cx).abs() + (y - cy).abs()
+            })
+            .map(|(id, _)| *id);
+        if let Some(next) = next {
+            self.focus_pane(next);
+        }
+    }
+
+    /// Copies the focused editor's buffer into every other editor showing the same file, if it was
+    /// edited since buffers were last synced.
+    fn sync_buffers(&mut self) {
+        let Some(editor) = self.current_editor() else {
+            return;
+        };
+        let Some(path) = editor.file_path.clone() else {
+            return;
+        };
+        let revision = editor.revision();
+        let edited = self
+            .synced
+            .as_ref()
+            .is_some_and(|(pane, synced_path, synced)| {
+                *pane == self.focused && *synced_path == path && *synced != revision
+            });
+        let lines = edited.then(|| editor.state.lines.clone());
+        self.synced = Some((self.focused, path.clone(), revision));
+        let Some(lines) = lines else {
+            return;
+        };
+        for (id, tab) in self.panes.iter_mut() {
+            if *id == self.focused {
+                continue;
+            }
+            if let Some(other) = tab.find_editor_mut(&path) {
+                other.set_lines(lines.clone());
+            }
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        // Edits made outside of input handled by the layout, such as formatting on save, are
+        // synced before drawing.
+        self.sync_buffers();
+        let mut areas = Vec::new();
+        self.root.layout(area, &mut areas);
+        for (id, pane_area) in &areas {
+            if let Some(tab) = self.panes.get_mut(id) {
+                let pane_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(1), // Editor tabs.
+                        Constraint::Fill(1),   // Editor contents.
+                    ])
+                    .split(*pane_area);
+                tab.render(pane_layout[0], pane_layout[1], buf);
+            }
+        }
+        self.areas = areas;
+    }
+}
+
+impl Component for EditorLayout {
+    fn handle_event(&mut self, event: Event) -> Result<Action> {
+        if let Some(mouse) = event.as_mouse_event() {
+            let action = self.handle_mouse_events(mouse)?;
+            self.sync_buffers();
+            return Ok(action);
+        }
+        if let Some(key) = event.as_key_event() {
+            let action = self.handle_key_events(key)?;
+            match action {
+                Action::Quit | Action::Handled => return Ok(action),
+                _ => {}
+            }
+        }
+        let action = match self.current_tab_mut() {
+            Some(tab) => tab.handle_event(event)?,
+            None => Action::Noop,
+        };
+        self.sync_buffers();
+        Ok(action)
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        if !key.modifiers.contains(KeyModifiers::ALT) {
+            return Ok(Action::Noop);
+        }
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('v') => self.split(Direction::Horizontal)?,
+            KeyCode::Char('s') => self.split(Direction::Vertical)?,
+            KeyCode::Char('x') => self.close_pane(self.focused)?,
+            KeyCode::Char('=') => self.resize(Self::RESIZE_STEP),
+            KeyCode::Char('-') => self.resize(-Self::RESIZE_STEP),
+            KeyCode::Char('H') => self.focus_direction(-1, 0),
+            KeyCode::Char('J') => self.focus_direction(0, 1),
+            KeyCode::Char('K') => self.focus_direction(0, -1),
+            KeyCode::Char('L') => self.focus_direction(1, 0),
+            KeyCode::Left if shift => self.focus_direction(-1, 0),
+            KeyCode::Down if shift => self.focus_direction(0, 1),
+            KeyCode::Up if shift => self.focus_direction(0, -1),
+            KeyCode::Right if shift => self.focus_direction(1, 0),
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    /// Applies tab management actions to the focused pane.
+    fn update(&mut self, action: Action) -> Result<Action> {
+        if let Action::SplitEditor(direction) = action {
+            self.split(direction)?;
+            return Ok(Action::Handled);
+        }
+        let tab = self
+            .current_tab_mut()
+            .context("Failed to get focused editor pane")?;
+        match action {
+            Action::NextTab => tab.next_editor(),
+            Action::PreviousTab => tab.prev_editor(),
+            Action::CloseAllTabs => tab.close_all_tabs(),
+            Action::CloseOtherTabs => tab.close_other_tabs(),
+            Action::CloseTabsToRight => tab.close_tabs_to_right(),
+            Action::TogglePinTab => tab.toggle_pin_current_tab(),
+            _ => return Ok(Action::Noop),
+        }
+        self.close_focused_if_empty()?;
+        Ok(Action::Handled)
+    }
+
+    /// Passes mouse input to the pane under the mouse, focusing the pane if it was clicked.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        let position = Position::new(mouse.column, mouse.row);
+        let Some(id) = self
+            .areas
+            .iter()
+            .find(|(_, area)| area.contains(position))
+            .map(|(id, _)| *id)
+        else {
+            return Ok(Action::Noop);
+        };
+        if matches!(mouse.kind, MouseEventKind::Down(_)) && id != self.focused {
+            self.focus_pane(id);
+        }
+        let action = match self.panes.get_mut(&id) {
+            Some(tab) => tab.handle_mouse_events(mouse)?,
+            None => Action::Noop,
+        };
+        // A middle click may have closed the last tab in the pane.
+        if id == self.focused {
+            self.close_focused_if_empty()?;
+        }
+        Ok(action)
+    }
+}
@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use edtui::EditorState;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Style};
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+/// Maps buffer positions to the screen cells drawn by edtui's EditorView.
+///
+/// EditorView does not expose its scroll offset, so the mapping is anchored on the cursor cell.
+/// The cursor is drawn with a background color no other text uses, and lines above and below it
+/// are laid out from there using the same wrapping and tab width as the view.
+pub struct EditorOverlay {
+    text_x: u16,
+    text_width: usize,
+    tab_width: usize,
+    /// The screen row, buffer row and first display column for each row of the text area.
+    rows: Vec<(u16, usize, usize)>,
+}
+
+impl EditorOverlay {
+    /// The background color of the cursor cell in the editor theme.
+    pub const CURSOR_BG: Color = Color::Rgb(0xe0, 0xe0, 0xe0);
+
+    /// Builds the mapping for an EditorView drawn in `area`, inside the editor block and gutter.
+    /// Returns None if the cursor is not visible on the screen.
+    pub fn new(area: Rect, buf: &Buffer, state: &EditorState, tab_width: usize) -> Option<Self> {
+        let cursor_cell = area
+            .positions()
+            .find(|p| buf.cell(*p).is_some_and(|c| c.bg == Self::CURSOR_BG))?;
+        let line = Self::line(state, state.cursor.row);
+        let cursor_x = Self::display_width(&line[..state.cursor.col.min(line.len())], tab_width);
+
+        // Find the width of the line number gutter that places the cursor where it was drawn.
+        let offset = (cursor_cell.x - area.x) as usize;
+        let gutter = (0..=offset).find(|gutter| {
+            let width = area.width as usize - gutter;
+            width > 0 && offset - gutter == cursor_x % width
+        })?;
+        let text_width = area.width as usize - gutter;
+
+        let mut rows = Vec::new();
+        let line_top = cursor_cell.y as i32 - (cursor_x / text_width) as i32;
+        // Lay out the cursor line and the lines below it.
+        let mut y = line_top;
+        for row in state.cursor.row..state.lines.len() {
+            if y >= area.bottom() as i32 {
+                break;
+            }
+            let height = Self::height(state, row, text_width, tab_width);
+            for segment in 0..height {
+                let screen_y = y + segment as i32;
+                if screen_y >= area.y as i32 && screen_y < area.bottom() as i32 {
+                    rows.push((screen_y as u16, row, segment * text_width));
+                }
+            }
+            y += height as i32;
+        }
+        // Lay out the lines above the cursor.
+        let mut y = line_top;
+        for row in (0..state.cursor.row).rev() {
+            if y <= area.y as i32 {
+                break;
+            }
+            let height = Self::height(state, row, text_width, tab_width);
+            y -= height as i32;
+            for segment in 0..height {
+                let screen_y = y + segment as i32;
+                if screen_y >= area.y as i32 && screen_y < area.bottom() as i32 {
+                    rows.push((screen_y as u16, row, segment * text_width));
+                }
+            }
+        }
+
+        Some(Self {
+            text_x: area.x + gutter as u16,
+            text_width,
+            tab_width,
+            rows,
+        })
+    }
+
+    /// Applies `style` to the characters in `cols` of a buffer row, if they are on the screen.
+    pub fn highlight(
+        &self,
+        buf: &mut Buffer,
+        state: &EditorState,
+        row: usize,
+        cols: Range<usize>,
+        style: Style,
+    ) {
+        let line = Self::line(state, row);
+        let start = Self::display_width(&line[..cols.start.min(line.len())], self.tab_width);
+        let end = Self::display_width(&line[..cols.end.min(line.len())], self.tab_width);
+        // Highlight a single cell for empty ranges, such as a cursor at the end of a line.
+        let end = end.max(start + 1);
+        for (y, _, first) in self.rows.iter().filter(|(_, r, _)| *r == row) {
+            let visible = (*first).max(start)..(first + self.text_width).min(end);
+            for x in visible {
+                let position = Position::new(self.text_x + (x - first) as u16, *y);
+                if let Some(cell) = buf.cell_mut(position) {
+                    // Never hide the cursor behind a highlight.
+                    if cell.bg != Self::CURSOR_BG {
+                        cell.set_style(style);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Applies `style` to the full width of the screen rows a buffer row is drawn on, including
+    /// the cells after the end of the line.
+    pub fn highlight_row(&self, buf: &mut Buffer, row: usize, style: Style) {
+        for (y, _, _) in self.rows.iter().filter(|(_, r, _)| *r == row) {
+            for x in 0..self.text_width {
+                let position = Position::new(self.text_x + x as u16, *y);
+                if let Some(cell) = buf.cell_mut(position)
+                    && cell.bg != Self::CURSOR_BG
+                {
+                    cell.set_style(style);
+                }
+            }
+        }
+    }
+
+    /// The screen row that each buffer row on the screen starts on, ordered from top to bottom.
+    pub fn line_starts(&self) -> Vec<(u16, usize)> {
+        let mut starts: Vec<(u16, usize)> = self
+            .rows
+            .iter()
+            .filter(|(_, _, first)| *first == 0)
+            .map(|(y, row, _)| (*y, *row))
+            .collect();
+        starts.sort();
+        starts
+    }
+
+    /// The range of buffer rows with at least one cell on the screen.
+    pub fn visible_rows(&self) -> Range<usize> {
+        let first = self.rows.iter().map(|(_, r, _)| *r).min().unwrap_or(0);
+        let last = self.rows.iter().map(|(_, r, _)| *r).max().unwrap_or(0);
+        first..last + 1
+    }
+
+    fn line(state: &EditorState, row: usize) -> Vec<char> {
+        state.lines.iter_row().nth(row).cloned().unwrap_or_default()
+    }
+
+    /// The number of screen rows a wrapped buffer row is drawn on.
+    fn height(state: &EditorState, row: usize, text_width: usize, tab_width: usize) -> usize {
+        let width = Self::display_width(&Self::line(state, row), tab_width);
+        width.div_ceil(text_width).max(1)
+    }
+
+    fn display_width(chars: &[char], tab_width: usize) -> usize {
+        chars
+            .iter()
+            .map(|c| match c {
+                '\t' => tab_width,
+                c => c.width().unwrap_or(0),
+            })
+            .sum()
+    }
+}
@@ -2,16 +2,19 @@
 //
 // SPDX-License-Identifier: GNU General Public License v3.0 or later
 
-use crate::tui::component::{Action, Component, Focus, FocusState};
+use crate::tui::component::{Action, Component, Focus, FocusState, tab_at};
 use crate::tui::editor::Editor;
 use anyhow::{Context, Result, anyhow};
-use log::{error, info, trace, warn};
+use log::{error, info, trace};
 use ratatui::buffer::Buffer;
-use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
-use ratatui::layout::Rect;
-use ratatui::prelude::{Color, Style};
+use ratatui::crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Position, Rect};
+use ratatui::prelude::{Color, Line, Style};
 use ratatui::widgets::{Block, Borders, Padding, Tabs, Widget};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Component as PathComponent;
 
 // Render the tabs with keys as titles
 // Tab keys can be file names.
@@ -20,6 +23,11 @@ pub struct EditorTab {
     pub(crate) editors: HashMap<String, Editor>,
     tab_order: Vec<String>,
     current_editor: usize,
+    /// Pinned tabs are kept at the start of the tab order and are not closed in bulk.
+    pinned: HashSet<String>,
+    /// The area and title widths of the tab strip from the last render, used for mouse input.
+    tabs_area: Rect,
+    title_widths: Vec<usize>,
 }
 
 impl EditorTab {
@@ -31,10 +39,16 @@ impl EditorTab {
             editors: HashMap::new(),
             tab_order: Vec::new(),
             current_editor: 0,
+            pinned: HashSet::new(),
+            tabs_area: Rect::default(),
+            title_widths: Vec::new(),
         }
     }
 
     pub fn next_editor(&mut self) {
+        if self.tab_order.is_empty() {
+            return;
+        }
         let next = (self.current_editor + 1) % self.tab_order.len();
         trace!(target:Self::ID, "Moving from {} to next editor tab at {}", self.current_editor, next);
         self.set_tab_focus(Focus::Active, next);
@@ -42,6 +56,9 @@ impl EditorTab {
     }
 
     pub fn prev_editor(&mut self) {
+        if self.tab_order.is_empty() {
+            return;
+        }
         let prev = self
             .current_editor
             .checked_sub(1)
@@ -51,6 +68,57 @@ impl EditorTab {
         self.current_editor = prev;
     }
 
+    /// Switches directly to the tab at `index`.
+    pub fn select_tab(&mut self, index: usize) {
+        if index >= self.tab_order.len() {
+            trace!(target:Self::ID, "Ignoring selection of invalid tab index {index}");
+            return;
+        }
+        trace!(target:Self::ID, "Selecting editor tab at {index}");
+        self.set_tab_focus(Focus::Active, index);
+        self.current_editor = index;
+    }
+
+    fn is_pinned(&self, index: usize) -> bool {
+        self.tab_order
+            .get(index)
+            .is_some_and(|key| self.pinned.contains(key))
+    }
+
+    /// Moves the current tab left or right by `offset`, without crossing between pinned and
+    /// unpinned tabs.
+    pub fn move_current_tab(&mut self, offset: isize) {
+        let current = self.current_editor;
+        let Some(target) = current.checked_add_signed(offset) else {
+            return;
+        };
+        if target >= self.tab_order.len() || self.is_pinned(target) != self.is_pinned(current) {
+            return;
+        }
+        trace!(target:Self::ID, "Moving editor tab from {current} to {target}");
+        self.tab_order.swap(current, target);
+        self.current_editor = target;
+    }
+
+    /// Pins or unpins the current tab, moving it to the boundary between pinned and unpinned tabs.
+    pub fn toggle_pin_current_tab(&mut self) {
+        let Some(key) = self.get_editor_key(self.current_editor) else {
+            return;
+        };
+        if !self.pinned.remove(&key) {
+            self.pinned.insert(key.clone());
+        }
+        info!(target:Self::ID, "Editor tab {key} pinned: {}", self.pinned.contains(&key));
+        self.tab_order.remove(self.current_editor);
+        let boundary = self
+            .tab_order
+            .iter()
+            .filter(|k| self.pinned.contains(*k))
+            .count();
+        self.tab_order.insert(boundary, key);
+        self.current_editor = boundary;
+    }
+
     pub fn get_editor_key(&self, index: usize) -> Option<String> {
         match self.tab_order.get(index) {
             None => {
@@ -72,6 +140,15 @@ impl EditorTab {
             .get_mut(&self.get_editor_key(self.current_editor)?)
     }
 
+    /// Finds the editor for a file opened in this EditorTab.
+    pub fn find_editor(&self, path: &std::path::Path) -> Option<&Editor> {
+        self.editors.get(&path.to_string_lossy().to_string())
+    }
+
+    pub fn find_editor_mut(&mut self, path: &std::path::Path) -> Option<&mut Editor> {
+        self.editors.get_mut(&path.to_string_lossy().to_string())
+    }
+
     pub fn set_current_tab_focus(&mut self, focus: Focus) {
         trace!(target:Self::ID, "Setting current tab {} focus to {:?}", self.current_editor, focus);
         self.set_tab_focus(focus, self.current_editor)
@@ -109,18 +186,16 @@ impl EditorTab {
 
     pub fn open_tab(&mut self, path: &std::path::Path) -> Result<()> {
         trace!(target:Self::ID, "Opening new EditorTab with path {:?}", path);
-        if self
-            .editors
-            .contains_key(&path.to_string_lossy().to_string())
-        {
-            warn!(target:Self::ID, "EditorTab already opened with this file");
+        let path_str = path.to_string_lossy().to_string();
+        if let Some(index) = self.tab_order.iter().position(|key| *key == path_str) {
+            trace!(target:Self::ID, "EditorTab already opened with this file, selecting it");
+            self.select_tab(index);
             return Ok(());
         }
 
-        let path_str = path.to_string_lossy().to_string();
-        self.tab_order.push(path_str.clone());
         let mut editor = Editor::new(path);
         editor.set_contents(path).context("Failed to open tab")?;
+        self.tab_order.push(path_str.clone());
         self.editors.insert(path_str, editor);
         self.current_editor = self.tab_order.len() - 1;
         Ok(())
@@ -141,27 +216,115 @@ impl EditorTab {
                 error!(target:Self::ID, "Failed to remove editor tab {key} with invalid index {index}")
             }
             Some(_) => {
-                self.prev_editor();
                 self.tab_order.remove(index);
+                self.pinned.remove(&key);
+                // Keep the same tab selected if it was after the closed tab.
+                if self.current_editor > index || self.current_editor >= self.tab_order.len() {
+                    self.current_editor = self.current_editor.saturating_sub(1);
+                }
+                if !self.tab_order.is_empty() {
+                    self.set_current_tab_focus(Focus::Active);
+                }
                 info!(target:Self::ID, "Closed editor tab {key} at index {index}")
             }
         }
         Ok(())
     }
 
+    /// Closes every unpinned tab for which `close` returns true, given the tab index and
+    /// the index of the current tab.
+    fn close_unpinned_tabs(&mut self, close: impl Fn(usize, usize) -> bool) {
+        let current_key = self.get_editor_key(self.current_editor);
+        let closing: Vec<String> = self
+            .tab_order
+            .iter()
+            .enumerate()
+            .filter(|(i, key)| !self.pinned.contains(*key) && close(*i, self.current_editor))
+            .map(|(_, key)| key.to_owned())
+            .collect();
+        for key in &closing {
+            self.editors.remove(key);
+        }
+        self.tab_order.retain(|key| !closing.contains(key));
+        info!(target:Self::ID, "Closed {} editor tabs", closing.len());
+
+        self.current_editor = current_key
+            .and_then(|key| self.tab_order.iter().position(|k| *k == key))
+            .unwrap_or(0);
+        if !self.tab_order.is_empty() {
+            self.set_current_tab_focus(Focus::Active);
+        }
+    }
+
+    pub fn close_all_tabs(&mut self) {
+        self.close_unpinned_tabs(|_, _| true);
+    }
+
+    pub fn close_other_tabs(&mut self) {
+        self.close_unpinned_tabs(|index, current| index != current);
+    }
+
+    pub fn close_tabs_to_right(&mut self) {
+        self.close_unpinned_tabs(|index, current| index > current);
+    }
+
     pub fn is_empty(&self) -> bool {
         self.editors.is_empty()
     }
 
+    /// Builds a title for each tab from its file name, adding the fewest parent directories
+    /// needed to tell apart files with the same name in different directories.
+    fn tab_titles(&self) -> Vec<String> {
+        let components: Vec<Vec<String>> = self
+            .tab_order
+            .iter()
+            .map(|key| {
+                std::path::Path::new(key)
+                    .components()
+                    .rev()
+                    .filter_map(|c| match c {
+                        PathComponent::Normal(name) => Some(name.to_string_lossy().to_string()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+        components
+            .iter()
+            .enumerate()
+            .map(|(i, parts)| {
+                if parts.is_empty() {
+                    return String::from("Unknown");
+                }
+                let mut depth = 1;
+                while depth < parts.len()
+                    && components.iter().enumerate().any(|(j, other)| {
+                        j != i && other.len() >= depth && other[..depth] == parts[..depth]
+                    })
+                {
+                    depth += 1;
+                }
+                let title = parts[..depth]
+                    .iter()
+                    .rev()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join("/");
+                match self.pinned.contains(&self.tab_order[i]) {
+                    true => format!("📌 {title}"),
+                    false => title,
+                }
+            })
+            .collect()
+    }
+
     pub fn render(&mut self, tabs_area: Rect, editor_area: Rect, buf: &mut Buffer) {
-        // TODO: Only file name is displayed in tab title, so files with the same name in different
-        //    directories will appear confusing.
-        let tab_titles = self.tab_order.iter().map(|t| {
-            std::path::Path::new(t)
-                .file_name()
-                .map(|f| f.to_string_lossy().to_string())
-                .unwrap_or_else(|| String::from("Unknown"))
-        });
+        let tab_titles = self.tab_titles();
+        self.tabs_area = tabs_area;
+        self.title_widths = tab_titles
+            .iter()
+            .map(|t| Line::from(t.as_str()).width())
+            .collect();
         // Don't set border color based on ComponentState::focus, the Editor renders the border.
         Tabs::new(tab_titles)
             .select(self.current_editor)
@@ -203,6 +366,33 @@ impl Component for EditorTab {
         Ok(Action::Noop)
     }
 
+    /// Handles clicks and scrolling on the tab strip, passing other mouse input to the editor.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        if mouse.row != self.tabs_area.y
+            || !self
+                .tabs_area
+                .contains(Position::new(mouse.column, mouse.row))
+        {
+            return match self.current_editor_mut() {
+                Some(editor) => editor.handle_event(Event::Mouse(mouse)),
+                None => Ok(Action::Noop),
+            };
+        }
+        let clicked = tab_at(
+            self.title_widths.iter().copied(),
+            self.tabs_area.x,
+            mouse.column,
+        );
+        match (mouse.kind, clicked) {
+            (MouseEventKind::Down(MouseButton::Left), Some(index)) => self.select_tab(index),
+            (MouseEventKind::Down(MouseButton::Middle), Some(index)) => self.close_tab(index)?,
+            (MouseEventKind::ScrollUp, _) => self.prev_editor(),
+            (MouseEventKind::ScrollDown, _) => self.next_editor(),
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
         match key {
             KeyEvent {
@@ -231,6 +421,43 @@ impl Component for EditorTab {
                 self.next_editor();
                 Ok(Action::Handled)
             }
+            KeyEvent {
+                code: KeyCode::Char('<'),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT) => {
+                self.move_current_tab(-1);
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('>'),
+                modifiers,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT) => {
+                self.move_current_tab(1);
+                Ok(Action::Handled)
+            }
+            KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                self.toggle_pin_current_tab();
+                Ok(Action::Handled)
+            }
+            // ALT+1 through ALT+8 select a tab directly, and ALT+9 selects the last tab.
+            KeyEvent {
+                code: KeyCode::Char(c @ '1'..='9'),
+                modifiers: KeyModifiers::ALT,
+                ..
+            } => {
+                let index = match c {
+                    '9' => self.tab_order.len().saturating_sub(1),
+                    _ => c as usize - '1' as usize,
+                };
+                self.select_tab(index);
+                Ok(Action::Handled)
+            }
             _ => Ok(Action::Noop),
         }
     }
@@ -4,7 +4,7 @@
 
 use crate::tui::component::{Action, Component, ComponentState, Focus, FocusState};
 use anyhow::{Context, Result, bail};
-use log::trace;
+use log::{info, trace};
 use ratatui::buffer::Buffer;
 use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent, MouseEventKind};
 use ratatui::layout::{Alignment, Position, Rect};
@@ -21,21 +21,28 @@ pub struct Explorer<'a> {
     pub(crate) root_path: PathBuf,
     tree_items: TreeItem<'a, String>,
     tree_state: TreeState<String>,
+    /// The file chosen to compare with the next file chosen.
+    compare_from: Option<PathBuf>,
     pub(crate) component_state: ComponentState,
 }
 
 impl<'a> Explorer<'a> {
     pub const ID: &'static str = "Explorer";
 
+    /// The length of the UUID appended to the path of each item to give it a unique identifier.
+    const UUID_LEN: usize = 36;
+
     pub fn new(path: &PathBuf) -> Result<Self> {
         trace!(target:Self::ID, "Building {}", Self::ID);
         let explorer = Explorer {
             root_path: path.to_owned(),
             tree_items: Self::build_tree_from_path(path.to_owned())?,
             tree_state: TreeState::default(),
+            compare_from: None,
             component_state: ComponentState::default().with_help_text(concat!(
                 "(↑/k)/(↓/j): Select item | ←/h: Close folder | →/l: Open folder |",
-                " Space: Open / close folder | Enter: Open file in new editor tab"
+                " Space: Open / close folder | Enter: Open file in new editor tab |",
+                " c: Compare file with the next file chosen"
             )),
         };
         Ok(explorer)
@@ -84,7 +91,11 @@ impl<'a> Explorer<'a> {
     }
 
     pub fn selected(&self) -> Result<String> {
-        if let Some(path) = self.tree_state.selected().last() {
+        if let Some(identifier) = self.tree_state.selected().last() {
+            // Each identifier is the path of the item followed by a UUID.
+            let path = identifier
+                .get(..identifier.len().saturating_sub(Self::UUID_LEN))
+                .context("Failed to get path from selected TreeItem")?;
             return Ok(std::path::absolute(path)?
                 .to_str()
                 .context("Failed to get absolute path to selected TreeItem")?
@@ -92,6 +103,26 @@ impl<'a> Explorer<'a> {
         }
         bail!("Failed to get selected TreeItem")
     }
+
+    /// Chooses the selected file to compare, comparing it with the file chosen before it if any.
+    fn compare_selected(&mut self) -> Action {
+        let Some(path) = self
+            .selected()
+            .ok()
+            .map(PathBuf::from)
+            .filter(|path| path.is_file())
+        else {
+            return Action::Noop;
+        };
+        match self.compare_from.take() {
+            Some(from) if from != path => Action::CompareFiles(from, path),
+            _ => {
+                info!(target:Self::ID, "Choose another file with `c` to compare with {path:?}");
+                self.compare_from = Some(path);
+                Action::Handled
+            }
+        }
+    }
 }
 
 impl<'a> Widget for &mut Explorer<'a> {
@@ -131,6 +162,7 @@ impl<'a> Component for Explorer<'a> {
             match self.handle_key_events(key_event)? {
                 Action::Handled => return Ok(Action::Handled),
                 Action::OpenTab => return Ok(Action::OpenTab),
+                Action::CompareFiles(from, to) => return Ok(Action::CompareFiles(from, to)),
                 _ => {}
             }
         }
@@ -153,6 +185,10 @@ impl<'a> Component for Explorer<'a> {
             // Otherwise fall through and handle Enter in the next match case.
         }
 
+        if key.code == KeyCode::Char('c') {
+            return Ok(self.compare_selected());
+        }
+
         let changed = match key.code {
             KeyCode::Up | KeyCode::Char('k') => self.tree_state.key_up(),
             KeyCode::Down | KeyCode::Char('j') => self.tree_state.key_down(),
@@ -0,0 +1,290 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use std::path::Path;
+
+/// A range of buffer rows that can be folded. The first row stays visible when the fold is closed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FoldRegion {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl FoldRegion {
+    fn contains(&self, row: usize) -> bool {
+        self.start <= row && row <= self.end
+    }
+
+    /// True if the row is hidden while the fold is closed.
+    fn hides(&self, row: usize) -> bool {
+        self.start < row && row <= self.end
+    }
+}
+
+/// How fold regions are found in a buffer.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FoldMethod {
+    /// Each line followed by lines that are indented further starts a fold.
+    #[default]
+    Indent,
+    /// Each markdown heading starts a fold that ends before the next heading of the same level.
+    Headings,
+}
+
+impl FoldMethod {
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("md" | "markdown") => FoldMethod::Headings,
+            _ => FoldMethod::Indent,
+        }
+    }
+}
+
+/// The closed folds of an Editor.
+/// Folds only change how the buffer is displayed. The buffer itself keeps every line, so saving,
+/// searching and undo work the same whether or not any folds are closed.
+#[derive(Debug, Default)]
+pub struct Folds {
+    method: FoldMethod,
+    /// Closed folds, ordered by their first row.
+    closed: Vec<FoldRegion>,
+}
+
+impl Folds {
+    /// The number of columns an indenting tab is counted as.
+    const TAB_WIDTH: usize = 4;
+
+    pub fn new(method: FoldMethod) -> Self {
+        Self {
+            method,
+            closed: Vec::new(),
+        }
+    }
+
+    /// True if no folds are closed.
+    pub fn is_empty(&self) -> bool {
+        self.closed.is_empty()
+    }
+
+    pub fn is_closed(&self, row: usize) -> bool {
+        self.closed.iter().any(|fold| fold.start == row)
+    }
+
+    /// All regions that can be folded, ordered by their first row.
+    pub fn regions(&self, lines: &[String]) -> Vec<FoldRegion> {
+        let mut regions = match self.method {
+            FoldMethod::Indent => Self::indent_regions(lines),
+            FoldMethod::Headings => Self::heading_regions(lines),
+        };
+        regions.sort_by_key(|region| region.start);
+        regions
+    }
+
+    fn indent_regions(lines: &[String]) -> Vec<FoldRegion> {
+        let mut regions = Vec::new();
+        // The indent and first row of each fold that is still open at the current row.
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        let mut last_text_row = 0;
+        for (row, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let indent = Self::indent(line);
+            while let Some((_, start)) = stack.pop_if(|(i, _)| indent <= *i) {
+                if last_text_row > start {
+                    regions.push(FoldRegion {
+                        start,
+                        end: last_text_row,
+                    });
+                }
+            }
+            stack.push((indent, row));
+            last_text_row = row;
+        }
+        for (_, start) in stack {
+            if last_text_row > start {
+                regions.push(FoldRegion {
+                    start,
+                    end: last_text_row,
+                });
+            }
+        }
+        regions
+    }
+
+    fn heading_regions(lines: &[String]) -> Vec<FoldRegion> {
+        let level = |line: &str| {
+            let hashes = line.chars().take_while(|c| *c == '#').count();
+            (hashes > 0 && line[hashes..].starts_with(' ')).then_some(hashes)
+        };
+        let mut regions = Vec::new();
+        let mut in_code_block = false;
+        let mut headings: Vec<(usize, usize)> = Vec::new();
+        for (row, line) in lines.iter().enumerate() {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+            if let Some(level) = level(line).filter(|_| !in_code_block) {
+                headings.push((row, level));
+            }
+        }
+        for (n, (start, level)) in headings.iter().enumerate() {
+            let next = headings[n + 1..]
+                .iter()
+                .find(|(_, l)| l <= level)
+                .map_or(lines.len(), |(row, _)| *row);
+            // Blank lines before the next heading are left outside the fold.
+            let end = (start + 1..next)
+                .rev()
+                .find(|row| !lines[*row].trim().is_empty());
+            if let Some(end) = end {
+                regions.push(FoldRegion { start: *start, end });
+            }
+        }
+        regions
+    }
+
+    fn indent(line: &str) -> usize {
+        line.chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { Self::TAB_WIDTH } else { 1 })
+            .sum()
+    }
+
+    /// Closes the innermost fold containing a row, returning the fold that was closed.
+    pub fn close(&mut self, lines: &[String], row: usize) -> Option<FoldRegion> {
+        // A closed fold is treated as a single row, so closing it again closes its parent.
+        let row = self.visible_row(row);
+        let region = self
+            .regions(lines)
+            .into_iter()
+            .filter(|region| region.contains(row) && !self.closed.contains(region))
+            .max_by_key(|region| region.start)?;
+        self.closed.push(region);
+        self.closed.sort_by_key(|fold| fold.start);
+        Some(region)
+    }
+
+    /// Opens the closed fold shown on a row, returning false if there is none.
+    pub fn open(&mut self, row: usize) -> bool {
+        let row = self.visible_row(row);
+        let count = self.closed.len();
+        self.closed.retain(|fold| fold.start != row);
+        self.closed.len() != count
+    }
+
+    /// Opens the fold on a row if it is closed, and otherwise closes the fold containing the row.
+    pub fn toggle(&mut self, lines: &[String], row: usize) -> Option<FoldRegion> {
+        match self.open(row) {
+            true => None,
+            false => self.close(lines, row),
+        }
+    }
+
+    pub fn close_all(&mut self, lines: &[String]) {
+        self.closed = self.regions(lines);
+    }
+
+    pub fn open_all(&mut self) {
+        self.closed.clear();
+    }
+
+    /// Opens every fold hiding a row, such as when the cursor is moved to a search match.
+    pub fn open_at(&mut self, row: usize) {
+        self.closed.retain(|fold| !fold.hides(row));
+    }
+
+    /// The row a hidden row is displayed on, which is the first row of its outermost closed fold.
+    pub fn visible_row(&self, row: usize) -> usize {
+        self.closed
+            .iter()
+            .filter(|fold| fold.hides(row))
+            .map(|fold| fold.start)
+            .min()
+            .unwrap_or(row)
+    }
+
+    /// The buffer row shown on each row of the display.
+    pub fn visible_rows(&self, len: usize) -> Vec<usize> {
+        let mut rows = Vec::with_capacity(len);
+        let mut row = 0;
+        while row < len {
+            rows.push(row);
+            // Skip past the hidden rows of any closed folds starting on this row.
+            row = self
+                .closed
+                .iter()
+                .filter(|fold| fold.start == row)
+                .map(|fold| fold.end)
+                .max()
+                .unwrap_or(row)
+                + 1;
+        }
+        rows
+    }
+
+    /// Moves a cursor that was moved onto a hidden row past the closed fold hiding it, so each
+    /// closed fold is passed over as a single row.
+    pub fn skip_hidden(&self, row: usize, previous: usize, len: usize) -> usize {
+        let Some(fold) = self
+            .closed
+            .iter()
+            .filter(|fold| fold.hides(row))
+            .min_by_key(|fold| fold.start)
+        else {
+            return row;
+        };
+        match row > previous && fold.end + 1 < len {
+            true => fold.end + 1,
+            false => fold.start,
+        }
+    }
+
+    /// Updates the closed folds after the buffer changed, starting from `row`, by adding or
+    /// removing `delta` rows. Folds after the change are moved, and any fold that rows were added
+    /// to or removed from is opened.
+    pub fn edited(&mut self, row: usize, delta: isize) {
+        if delta == 0 {
+            return;
+        }
+        self.closed.retain_mut(|fold| {
+            if fold.hides(row) {
+                return false;
+            }
+            if fold.start >= row {
+                let start = fold.start as isize + delta;
+                // The first row of the fold was removed.
+                if start < row as isize {
+                    return false;
+                }
+                fold.start = start as usize;
+                fold.end = (fold.end as isize + delta) as usize;
+            }
+            true
+        });
+    }
+
+    /// Keeps each closed fold matching a region found in the buffer, dropping those that no longer
+    /// match, such as after undoing the change that added them.
+    pub fn validate(&mut self, lines: &[String]) {
+        if self.closed.is_empty() {
+            return;
+        }
+        let regions = self.regions(lines);
+        self.closed.retain_mut(|fold| {
+            match regions
+                .iter()
+                .filter(|region| region.start == fold.start)
+                .min_by_key(|region| region.end.abs_diff(fold.end))
+            {
+                Some(region) => {
+                    *fold = *region;
+                    true
+                }
+                None => false,
+            }
+        });
+        self.closed.dedup();
+    }
+}
@@ -0,0 +1,441 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::config;
+use anyhow::{Context, Result, bail};
+use log::{trace, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The buffer contents and cursor position of an editor at one point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub text: String,
+    /// The cursor row and column.
+    pub cursor: (usize, usize),
+}
+
+/// An edit to the buffer text, replacing the text removed at a byte offset with the text inserted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    pub start: usize,
+    pub removed: String,
+    pub inserted: String,
+}
+
+impl Change {
+    /// The change from `old` to the text of `new`, from the first to the last character that
+    /// differ. Returns None if the texts are the same.
+    pub fn between<I>(old: &str, new: I) -> Option<Self>
+    where
+        I: DoubleEndedIterator<Item = char> + Clone,
+    {
+        let prefix: usize = old
+            .chars()
+            .zip(new.clone())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let new_len: usize = new.clone().map(char::len_utf8).sum();
+        if prefix == old.len() && prefix == new_len {
+            return None;
+        }
+        // The suffix can't overlap the prefix in either text.
+        let limit = (old.len() - prefix).min(new_len - prefix);
+        let mut suffix = 0;
+        for (a, b) in old[prefix..].chars().rev().zip(new.clone().rev()) {
+            if a != b || suffix + a.len_utf8() > limit {
+                break;
+            }
+            suffix += a.len_utf8();
+        }
+        let mut inserted = String::new();
+        let mut offset = 0;
+        for c in new {
+            if offset >= prefix && offset < new_len - suffix {
+                inserted.push(c);
+            }
+            offset += c.len_utf8();
+        }
+        Some(Self {
+            start: prefix,
+            removed: old[prefix..old.len() - suffix].to_string(),
+            inserted,
+        })
+    }
+
+    fn apply(&self, text: &mut String) {
+        text.replace_range(self.start..self.start + self.removed.len(), &self.inserted);
+    }
+
+    fn revert(&self, text: &mut String) {
+        text.replace_range(self.start..self.start + self.inserted.len(), &self.removed);
+    }
+
+    /// The change made by this change followed by `next`, where `text` is the text after this
+    /// change. The combined change covers both, including any text between them.
+    fn then(&self, next: &Change, text: &str) -> Self {
+        let end = self.start + self.inserted.len();
+        let next_end = next.start + next.removed.len();
+        let (start, stop) = (self.start.min(next.start), end.max(next_end));
+        Self {
+            start,
+            removed: [&text[start..self.start], &self.removed, &text[end..stop]].concat(),
+            inserted: [
+                &text[start..next.start],
+                &next.inserted,
+                &text[next_end..stop],
+            ]
+            .concat(),
+        }
+    }
+
+    /// The row the change starts on and the number of lines it adds, which is negative for a
+    /// change that removes lines. `text` is the text before the change.
+    pub fn rows(&self, text: &str) -> (usize, isize) {
+        let row = text[..self.start].matches('\n').count();
+        let added = self.inserted.matches('\n').count() as isize;
+        (row, added - self.removed.matches('\n').count() as isize)
+    }
+
+    /// The number of bytes of text kept for this change.
+    fn size(&self) -> usize {
+        self.removed.len() + self.inserted.len()
+    }
+}
+
+/// A state of the buffer in the undo tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryNode {
+    /// Increases with each change to the buffer, so later changes have higher numbers.
+    pub seq: usize,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// The child that redo moves to, which is the branch most recently undone or created.
+    redo: Option<usize>,
+    /// Unix time in seconds when the change was made.
+    pub time: u64,
+    /// The change from the parent state to this state, which is empty for the root.
+    change: Change,
+    /// The cursor row and column after the change.
+    cursor: (usize, usize),
+}
+
+/// Undo tree for an Editor.
+/// Edits made through vim emulation and edits made by clide, such as search and replace, are
+/// both recorded here so `u` and `CTRL+R` can undo and redo either. Making a change after an undo
+/// starts a new branch instead of discarding the undone changes, so any earlier state of the buffer
+/// can be returned to from the undo tree panel.
+/// Only the text of the root state is kept, with each later state stored as its change from its
+/// parent.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    current: usize,
+    next_seq: usize,
+    nodes: Vec<HistoryNode>,
+    /// The text of the root state.
+    base: String,
+    /// The text of the current state, which is found from the root when the history is loaded.
+    #[serde(skip)]
+    text: String,
+}
+
+impl History {
+    pub const ID: &str = "History";
+
+    /// The maximum number of states kept for each file.
+    const MAX_ENTRIES: usize = 1000;
+
+    /// The maximum number of bytes of changed text kept for each file.
+    const MAX_BYTES: usize = 8 * 1024 * 1024;
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[HistoryNode] {
+        &self.nodes
+    }
+
+    /// The index of the node for the current state of the buffer.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// The text of the current state of the buffer.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Records a change to the current state as a new child state.
+    pub fn record(&mut self, change: Change, cursor: (usize, usize)) {
+        change.apply(&mut self.text);
+        let parent = (!self.nodes.is_empty()).then_some(self.current);
+        let change = match parent {
+            Some(_) => change,
+            None => {
+                self.base = self.text.clone();
+                Change::default()
+            }
+        };
+        let index = self.nodes.len();
+        self.nodes.push(HistoryNode {
+            seq: self.next_seq,
+            parent,
+            children: Vec::new(),
+            redo: None,
+            time: now(),
+            change,
+            cursor,
+        });
+        self.next_seq += 1;
+        if let Some(parent) = parent {
+            self.nodes[parent].children.push(index);
+            self.nodes[parent].redo = Some(index);
+        }
+        self.current = index;
+        self.prune();
+    }
+
+    /// Adds a change to the current state without adding a new undo step.
+    pub fn amend(&mut self, change: Change, cursor: (usize, usize)) {
+        let Some(node) = self.nodes.get_mut(self.current) else {
+            return self.record(change, cursor);
+        };
+        match node.parent {
+            Some(_) => node.change = node.change.then(&change, &self.text),
+            None => change.apply(&mut self.base),
+        }
+        node.cursor = cursor;
+        node.time = now();
+        change.apply(&mut self.text);
+        self.prune();
+    }
+
+    /// Moves to the parent of the current state, returning the state to restore.
+    pub fn undo(&mut self) -> Option<Snapshot> {
+        let parent = self.nodes.get(self.current)?.parent?;
+        self.nodes[parent].redo = Some(self.current);
+        self.nodes[self.current].change.revert(&mut self.text);
+        self.current = parent;
+        Some(self.snapshot())
+    }
+
+    /// Moves to the most recently visited child of the current state, returning the state to restore.
+    pub fn redo(&mut self) -> Option<Snapshot> {
+        let child = self.nodes.get(self.current)?.redo?;
+        self.nodes[child].change.apply(&mut self.text);
+        self.current = child;
+        Some(self.snapshot())
+    }
+
+    /// Moves to any state in the tree, returning the state to restore.
+    pub fn jump(&mut self, index: usize) -> Option<Snapshot> {
+        self.nodes.get(index)?;
+        let mut text = std::mem::take(&mut self.text);
+        self.walk(&mut text, index);
+        self.text = text;
+        // Redo from each ancestor should follow the path to the new state.
+        let mut child = index;
+        while let Some(parent) = self.nodes[child].parent {
+            self.nodes[parent].redo = Some(child);
+            child = parent;
+        }
+        self.current = index;
+        Some(self.snapshot())
+    }
+
+    /// Moves to the latest state with the given text, returning false if there is none.
+    pub fn select(&mut self, text: &str) -> bool {
+        if !self.nodes.is_empty() && self.text == text {
+            return true;
+        }
+        // Only states with the same length as the text are rebuilt to compare them.
+        let mut lens = vec![0; self.nodes.len()];
+        let mut candidates = Vec::new();
+        // Each state is recorded after its parent, so parents are visited first.
+        let mut order: Vec<usize> = (0..self.nodes.len()).collect();
+        order.sort_by_key(|index| self.nodes[*index].seq);
+        for index in order {
+            let node = &self.nodes[index];
+            let parent_len = node.parent.map_or(self.base.len(), |parent| lens[parent]);
+            lens[index] =
+                (parent_len + node.change.inserted.len()).saturating_sub(node.change.removed.len());
+            if lens[index] == text.len() {
+                candidates.push(index);
+            }
+        }
+        candidates.sort_by_key(|index| std::cmp::Reverse(self.nodes[*index].seq));
+        for index in candidates {
+            let mut state = self.text.clone();
+            self.walk(&mut state, index);
+            if state == text {
+                self.current = index;
+                self.text = state;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Loads the persisted history for a file, if there is one.
+    pub fn load(file: &Path) -> Option<Self> {
+        let path = Self::history_path(file).ok()?;
+        if !path.exists() {
+            return None;
+        }
+        match fs::read_to_string(&path)
+            .context(format!("Failed to read {path:?}"))
+            .and_then(|contents| {
+                toml::from_str(&contents).context(format!("Failed to parse {path:?}"))
+            })
+            .and_then(|mut history: Self| {
+                history.rebuild_text()?;
+                Ok(history)
+            }) {
+            Ok(history) => Some(history),
+            Err(e) => {
+                warn!(target:Self::ID, "Ignoring undo history for {file:?}: {e:#}");
+                None
+            }
+        }
+    }
+
+    /// Persists the history for a file so it can be loaded in a later session.
+    pub fn save(&self, file: &Path) -> Result<()> {
+        let path = Self::history_path(file)?;
+        trace!(target:Self::ID, "Saving undo history for {file:?} to {path:?}");
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).context(format!("Failed to create directory {dir:?}"))?;
+        }
+        let contents = toml::to_string(self).context("Failed to serialize undo history")?;
+        fs::write(&path, contents).context(format!("Failed to write {path:?}"))
+    }
+
+    /// The file used to store the history for `file`, named after its full path as vim does.
+    fn history_path(file: &Path) -> Result<PathBuf> {
+        let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        let name = file.to_string_lossy().replace(['/', '\\', ':'], "%");
+        Ok(config::data_dir()?
+            .join("undo")
+            .join(format!("{name}.toml")))
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            text: self.text.clone(),
+            cursor: self.nodes[self.current].cursor,
+        }
+    }
+
+    /// Finds the text of the current state by applying each change from the root, checking that
+    /// each change matches the text it is applied to.
+    fn rebuild_text(&mut self) -> Result<()> {
+        let mut path = vec![self.current];
+        while let Some(parent) = self.nodes.get(path[path.len() - 1]).and_then(|n| n.parent) {
+            path.push(parent);
+        }
+        let mut text = self.base.clone();
+        for index in path.into_iter().rev().skip(1) {
+            let change = &self
+                .nodes
+                .get(index)
+                .context(format!("Missing state {index}"))?
+                .change;
+            let end = change.start + change.removed.len();
+            if text.get(change.start..end) != Some(change.removed.as_str()) {
+                bail!("State {index} does not match the text before it");
+            }
+            change.apply(&mut text);
+        }
+        self.text = text;
+        Ok(())
+    }
+
+    /// Changes `text` from the text of the current state to the text of the state at `index`,
+    /// undoing changes up to the closest common ancestor and then redoing changes down to `index`.
+    fn walk(&self, text: &mut String, index: usize) {
+        let mut down = vec![index];
+        while let Some(parent) = self.nodes[down[down.len() - 1]].parent {
+            down.push(parent);
+        }
+        let mut up = self.current;
+        let common = loop {
+            if let Some(position) = down.iter().position(|i| *i == up) {
+                break position;
+            }
+            self.nodes[up].change.revert(text);
+            match self.nodes[up].parent {
+                Some(parent) => up = parent,
+                // The tree has a single root, which is an ancestor of every state.
+                None => return,
+            }
+        };
+        for index in down[..common].iter().rev() {
+            self.nodes[*index].change.apply(text);
+        }
+    }
+
+    /// Removes the oldest states once the tree has more than MAX_ENTRIES states, or its changes
+    /// are larger than MAX_BYTES.
+    /// Only a leaf or a root with a single child can be removed, keeping the tree connected.
+    fn prune(&mut self) {
+        while self.nodes.len() > Self::MAX_ENTRIES || self.size() > Self::MAX_BYTES {
+            let oldest = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(index, node)| {
+                    *index != self.current
+                        && (node.children.is_empty()
+                            || (node.parent.is_none() && node.children.len() == 1))
+                })
+                .min_by_key(|(_, node)| node.seq)
+                .map(|(index, _)| index);
+            match oldest {
+                Some(index) => self.remove(index),
+                None => break,
+            }
+        }
+    }
+
+    /// The number of bytes of changed text kept in the tree.
+    fn size(&self) -> usize {
+        self.nodes.iter().map(|node| node.change.size()).sum()
+    }
+
+    fn remove(&mut self, index: usize) {
+        let node = self.nodes.remove(index);
+        let shift = |i: usize| if i > index { i - 1 } else { i };
+        if let Some(parent) = node.parent {
+            let parent = &mut self.nodes[shift(parent)];
+            parent.children.retain(|child| *child != index);
+            if parent.redo == Some(index) {
+                parent.redo = None;
+            }
+        }
+        for child in node.children {
+            let child = &mut self.nodes[shift(child)];
+            child.parent = None;
+            // The child of a removed root becomes the root, so its text is kept in full.
+            std::mem::take(&mut child.change).apply(&mut self.base);
+        }
+        for node in self.nodes.iter_mut() {
+            node.parent = node.parent.map(shift);
+            node.redo = node.redo.map(shift);
+            node.children
+                .iter_mut()
+                .for_each(|child| *child = shift(*child));
+        }
+        self.current = shift(self.current);
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::image_file::{self, ImageInfo};
+use anyhow::Result;
+use image::RgbaImage;
+use image::imageops::{self, FilterType};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use std::path::Path;
+
+/// An image file drawn in place of the text of an editor.
+/// Each cell of the terminal draws two pixels, using the upper half block character with the top
+/// pixel as the foreground color and the bottom pixel as the background color.
+#[derive(Debug)]
+pub struct ImageView {
+    pub info: ImageInfo,
+    pixels: RgbaImage,
+    /// The image scaled to fit the area drawn in the last frame, so it is only scaled again when
+    /// the area is resized.
+    scaled: Option<((u16, u16), RgbaImage)>,
+}
+
+impl ImageView {
+    /// SVG images are drawn at this size before they are scaled to fit the editor.
+    const SVG_SIZE: u32 = 1024;
+
+    /// Transparent pixels are blended with the editor background.
+    const BACKGROUND: [u8; 3] = [0x28, 0x2a, 0x36];
+
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            info: ImageInfo::read(path)?,
+            pixels: image_file::decode(path, Self::SVG_SIZE)?,
+            scaled: None,
+        })
+    }
+
+    /// The image scaled to the largest size that fits in an area of cells, keeping its aspect
+    /// ratio. Images smaller than the area are not enlarged.
+    fn scaled(&mut self, width: u16, height: u16) -> &RgbaImage {
+        let size = (width, height);
+        if self.scaled.as_ref().is_none_or(|(s, _)| *s != size) {
+            let (image_width, image_height) = self.pixels.dimensions();
+            let scale = (width as f64 / image_width.max(1) as f64)
+                .min(height as f64 * 2.0 / image_height.max(1) as f64)
+                .min(1.0);
+            let scaled_width = ((image_width as f64 * scale).round() as u32).max(1);
+            let scaled_height = ((image_height as f64 * scale).round() as u32).max(1);
+            let scaled = imageops::resize(
+                &self.pixels,
+                scaled_width,
+                scaled_height,
+                FilterType::Triangle,
+            );
+            self.scaled = Some((size, scaled));
+        }
+        &self.scaled.as_ref().expect("Image was scaled").1
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let image = self.scaled(area.width, area.height);
+        // The image is centered in the area.
+        let columns = image.width() as u16;
+        let rows = image.height().div_ceil(2) as u16;
+        let left = area.x + (area.width - columns.min(area.width)) / 2;
+        let top = area.y + (area.height - rows.min(area.height)) / 2;
+        for row in 0..rows.min(area.height) {
+            for column in 0..columns.min(area.width) {
+                let x = column as u32;
+                let y = row as u32 * 2;
+                let upper = Self::color(image, x, y);
+                let lower = Self::color(image, x, y + 1);
+                buf[(left + column, top + row)]
+                    .set_symbol("▀")
+                    .set_fg(upper)
+                    .set_bg(lower);
+            }
+        }
+    }
+
+    /// The color of a pixel blended with the background, or the background below the image.
+    fn color(image: &RgbaImage, x: u32, y: u32) -> Color {
+        let [r, g, b] = Self::BACKGROUND;
+        let Some(pixel) = image.get_pixel_checked(x, y) else {
+            return Color::Rgb(r, g, b);
+        };
+        let [pr, pg, pb, alpha] = pixel.0;
+        let blend = |color: u8, background: u8| {
+            ((color as u32 * alpha as u32 + background as u32 * (255 - alpha as u32)) / 255) as u8
+        };
+        Color::Rgb(blend(pr, r), blend(pg, g), blend(pb, b))
+    }
+}
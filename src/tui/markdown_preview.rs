@@ -0,0 +1,493 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::markdown::{self, LineIndex};
+use pulldown_cmark::{Alignment, Event, HeadingLevel, Parser, Tag, TagEnd};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Padding, Paragraph, Widget};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A panel beside the editor showing a markdown buffer as styled text.
+/// It scrolls with the editor, so the first line shown is rendered from the top visible row.
+#[derive(Debug, Default)]
+pub struct MarkdownPreview {
+    visible: bool,
+    /// The text and width the lines were rendered for, so they are only rendered again when
+    /// either one changes.
+    rendered: Option<(String, u16)>,
+    /// Each rendered line and the row of the source it was rendered from.
+    lines: Vec<(usize, Line<'static>)>,
+}
+
+impl MarkdownPreview {
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Draws the text starting from the line rendered from `top_row` of the source.
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, text: &str, top_row: usize) {
+        let block = Block::default()
+            .title(" Preview ")
+            .borders(Borders::LEFT)
+            .padding(Padding::horizontal(1))
+            .border_style(Style::default().fg(Color::DarkGray));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if self
+            .rendered
+            .as_ref()
+            .is_none_or(|(t, w)| t != text || *w != inner.width)
+        {
+            self.lines = Renderer::new(text, inner.width as usize).render(text);
+            self.rendered = Some((text.to_string(), inner.width));
+        }
+        // Rows only increase through the rendered lines, so the first line at or below the top
+        // row can be found with a binary search.
+        let top = self
+            .lines
+            .partition_point(|(row, _)| *row < top_row)
+            .min(self.lines.len().saturating_sub(1));
+        let lines: Vec<Line> = self.lines[top..]
+            .iter()
+            .take(inner.height as usize)
+            .map(|(_, line)| line.clone())
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+/// The cells of a table, which are drawn once every row is known so the columns line up.
+#[derive(Default)]
+struct Table {
+    alignments: Vec<Alignment>,
+    /// The source row of each table row, and the text of its cells.
+    rows: Vec<(usize, Vec<String>)>,
+    /// The number of rows in the table head.
+    head: usize,
+    cell: String,
+}
+
+/// Renders markdown to lines of styled text wrapped to a width.
+struct Renderer {
+    index: LineIndex,
+    width: usize,
+    lines: Vec<(usize, Line<'static>)>,
+    /// The words of the paragraph or heading being rendered, and the source row of each.
+    words: Vec<(usize, Span<'static>)>,
+    /// The styles of the inline tags that are open, such as emphasis inside of a link.
+    styles: Vec<Style>,
+    /// The next number of each open list, or None for a bulleted list.
+    lists: Vec<Option<u64>>,
+    /// The width of the bullet of each open list item, which its wrapped lines are indented by.
+    indents: Vec<usize>,
+    /// The bullet of the current list item, drawn before its first line.
+    bullet: Option<String>,
+    quotes: usize,
+    in_code_block: bool,
+    table: Option<Table>,
+    /// The row of the source being rendered, for lines that aren't from a text event.
+    row: usize,
+}
+
+impl Renderer {
+    const CODE_STYLE: Style = Style::new().fg(Color::LightGreen);
+    const MARKER_STYLE: Style = Style::new().fg(Color::DarkGray);
+    const BULLET_STYLE: Style = Style::new().fg(Color::Yellow);
+    const LINK_STYLE: Style = Style::new()
+        .fg(Color::LightBlue)
+        .add_modifier(Modifier::UNDERLINED);
+
+    fn new(text: &str, width: usize) -> Self {
+        Self {
+            index: LineIndex::new(text),
+            width: width.max(1),
+            lines: Vec::new(),
+            words: Vec::new(),
+            styles: Vec::new(),
+            lists: Vec::new(),
+            indents: Vec::new(),
+            bullet: None,
+            quotes: 0,
+            in_code_block: false,
+            table: None,
+            row: 0,
+        }
+    }
+
+    fn render(mut self, text: &str) -> Vec<(usize, Line<'static>)> {
+        for (event, range) in Parser::new_ext(text, markdown::options()).into_offset_iter() {
+            self.row = self.index.row(range.start);
+            match event {
+                Event::Start(tag) => self.start(tag),
+                Event::End(tag) => self.end(tag),
+                Event::Text(text) if self.in_code_block => {
+                    for line in text.lines() {
+                        self.push_code_line(line);
+                        self.row += 1;
+                    }
+                }
+                Event::Text(text) => self.push_text(&text, self.style()),
+                Event::Code(code) => {
+                    let style = self.style().patch(Self::CODE_STYLE);
+                    self.push_text(&code, style);
+                }
+                Event::Html(html) | Event::InlineHtml(html) => {
+                    self.push_text(&html, self.style().patch(Self::MARKER_STYLE))
+                }
+                Event::FootnoteReference(name) => {
+                    self.push_text(&format!("[{name}]"), Self::LINK_STYLE)
+                }
+                Event::TaskListMarker(checked) => {
+                    let marker = match checked {
+                        true => "[x] ",
+                        false => "[ ] ",
+                    };
+                    self.push_text(marker, Self::BULLET_STYLE);
+                }
+                Event::SoftBreak => self.push_text(" ", self.style()),
+                Event::HardBreak => self.flush(),
+                Event::Rule => {
+                    self.flush();
+                    let width = self.width.saturating_sub(self.prefix_width());
+                    self.push_line(vec![Span::styled("─".repeat(width), Self::MARKER_STYLE)]);
+                    self.blank();
+                }
+                _ => {}
+            }
+        }
+        self.flush();
+        // Blank lines only separate blocks, so the last one isn't needed.
+        if self.lines.last().is_some_and(|(_, line)| line.width() == 0) {
+            self.lines.pop();
+        }
+        self.lines
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                let color = match level {
+                    HeadingLevel::H1 => Color::LightMagenta,
+                    HeadingLevel::H2 => Color::LightBlue,
+                    _ => Color::LightCyan,
+                };
+                self.styles
+                    .push(Style::new().fg(color).add_modifier(Modifier::BOLD));
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quotes += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.flush();
+                self.in_code_block = true;
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let bullet = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.indents.push(bullet.width());
+                self.bullet = Some(bullet);
+            }
+            Tag::Table(alignments) => {
+                self.flush();
+                self.table = Some(Table {
+                    alignments,
+                    ..Table::default()
+                });
+            }
+            Tag::TableHead | Tag::TableRow => {
+                let row = self.row;
+                if let Some(table) = &mut self.table {
+                    table.rows.push((row, Vec::new()));
+                }
+            }
+            Tag::Emphasis => self
+                .styles
+                .push(Style::new().add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.styles.push(Style::new().add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => self
+                .styles
+                .push(Style::new().add_modifier(Modifier::CROSSED_OUT)),
+            Tag::Link { .. } => self.styles.push(Self::LINK_STYLE),
+            Tag::Image { .. } => {
+                self.push_text("🖼 ", self.style());
+                self.styles.push(Self::LINK_STYLE);
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => {
+                self.flush();
+                if self.lists.is_empty() {
+                    self.blank();
+                }
+            }
+            TagEnd::Heading(level) => {
+                let width: usize = self.words.iter().map(|(_, word)| word.width()).sum();
+                self.flush();
+                self.styles.pop();
+                // The largest headings are underlined, like setext headings.
+                let underline = match level {
+                    HeadingLevel::H1 => "═",
+                    HeadingLevel::H2 => "─",
+                    _ => "",
+                };
+                if !underline.is_empty() {
+                    let width = width.min(self.width.saturating_sub(self.prefix_width()));
+                    self.push_line(vec![Span::styled(
+                        underline.repeat(width),
+                        Self::MARKER_STYLE,
+                    )]);
+                }
+                self.blank();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                // The blank line after the last paragraph of the quote is replaced by one outside.
+                if self
+                    .lines
+                    .last()
+                    .is_some_and(|(_, line)| line.width() == self.quotes * 2)
+                {
+                    self.lines.pop();
+                }
+                self.quotes = self.quotes.saturating_sub(1);
+                self.blank();
+            }
+            TagEnd::CodeBlock => {
+                self.in_code_block = false;
+                self.blank();
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank();
+                }
+            }
+            TagEnd::Item => {
+                self.flush();
+                self.indents.pop();
+                self.bullet = None;
+            }
+            TagEnd::TableCell => {
+                if let Some(table) = &mut self.table {
+                    let cell = std::mem::take(&mut table.cell);
+                    if let Some((_, cells)) = table.rows.last_mut() {
+                        cells.push(cell.trim().to_string());
+                    }
+                }
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.head = table.rows.len();
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.push_table(table);
+                }
+                self.blank();
+            }
+            TagEnd::Emphasis
+            | TagEnd::Strong
+            | TagEnd::Strikethrough
+            | TagEnd::Link
+            | TagEnd::Image => {
+                self.styles.pop();
+            }
+            _ => {}
+        }
+    }
+
+    /// The style of the inline tags that are open.
+    fn style(&self) -> Style {
+        self.styles
+            .iter()
+            .fold(Style::default(), |style, s| style.patch(*s))
+    }
+
+    /// Adds text to the paragraph being rendered, split into words so it can be wrapped.
+    fn push_text(&mut self, text: &str, style: Style) {
+        if let Some(table) = &mut self.table {
+            table.cell.push_str(text);
+            return;
+        }
+        for word in text.split_inclusive(' ') {
+            self.words
+                .push((self.row, Span::styled(word.to_string(), style)));
+        }
+    }
+
+    fn push_code_line(&mut self, line: &str) {
+        let line = line.replace('\t', "    ");
+        self.push_line(vec![
+            Span::styled("  ", Self::MARKER_STYLE),
+            Span::styled(line, Self::CODE_STYLE),
+        ]);
+    }
+
+    /// The width of the block quote and list indent drawn before each line.
+    fn prefix_width(&self) -> usize {
+        self.quotes * 2 + self.indents.iter().sum::<usize>()
+    }
+
+    /// The block quote bars and list indent drawn before each line, with the bullet if it is the
+    /// first line of a list item.
+    fn prefix(&mut self) -> Vec<Span<'static>> {
+        let mut prefix = Vec::new();
+        if self.quotes > 0 {
+            prefix.push(Span::styled("│ ".repeat(self.quotes), Self::MARKER_STYLE));
+        }
+        let indent: usize = self.indents.iter().sum();
+        match self.bullet.take() {
+            Some(bullet) => {
+                prefix.push(Span::raw(" ".repeat(indent - bullet.width())));
+                prefix.push(Span::styled(bullet, Self::BULLET_STYLE));
+            }
+            None if indent > 0 => prefix.push(Span::raw(" ".repeat(indent))),
+            None => {}
+        }
+        prefix
+    }
+
+    fn push_line(&mut self, spans: Vec<Span<'static>>) {
+        let mut line = self.prefix();
+        line.extend(spans);
+        self.lines.push((self.row, Line::from(line)));
+    }
+
+    /// Adds a blank line between blocks, unless the last line is already blank.
+    fn blank(&mut self) {
+        if self
+            .lines
+            .last()
+            .is_none_or(|(_, line)| line.width() == self.quotes * 2)
+        {
+            return;
+        }
+        let row = self.lines.last().map_or(0, |(row, _)| *row);
+        let line = match self.quotes {
+            0 => Line::default(),
+            quotes => Line::from(Span::styled("│ ".repeat(quotes), Self::MARKER_STYLE)),
+        };
+        self.lines.push((row, line));
+    }
+
+    /// Wraps the words of the paragraph being rendered into lines.
+    fn flush(&mut self) {
+        if self.words.is_empty() {
+            return;
+        }
+        let width = self.width.saturating_sub(self.prefix_width()).max(1);
+        let mut line: Vec<Span<'static>> = Vec::new();
+        let mut line_width = 0;
+        let mut row = None;
+        for (word_row, mut word) in std::mem::take(&mut self.words) {
+            // The space after the last word of a line may hang past the edge of the panel.
+            if !line.is_empty() && line_width + word.content.trim_end().width() > width {
+                self.push_wrapped(row.take(), std::mem::take(&mut line));
+                line_width = 0;
+            }
+            // Words wider than the panel are split across lines.
+            while line.is_empty() && word.content.trim_end().width() > width {
+                let split = split_at_width(&word.content, width);
+                let rest = word.content[split..].to_string();
+                let start = Span::styled(word.content[..split].to_string(), word.style);
+                self.push_wrapped(Some(word_row), vec![start]);
+                word = Span::styled(rest, word.style);
+            }
+            if line.is_empty() && word.content.trim().is_empty() {
+                continue;
+            }
+            row.get_or_insert(word_row);
+            line_width += word.width();
+            line.push(word);
+        }
+        if !line.is_empty() {
+            self.push_wrapped(row, line);
+        }
+    }
+
+    fn push_wrapped(&mut self, row: Option<usize>, spans: Vec<Span<'static>>) {
+        if let Some(row) = row {
+            self.row = row;
+        }
+        self.push_line(spans);
+    }
+
+    /// Draws a table with each column as wide as its widest cell.
+    fn push_table(&mut self, table: Table) {
+        let columns = table.rows.iter().map(|(_, cells)| cells.len()).max();
+        let mut widths = vec![0; columns.unwrap_or(0)];
+        for (_, cells) in &table.rows {
+            for (width, cell) in widths.iter_mut().zip(cells) {
+                *width = (*width).max(cell.width());
+            }
+        }
+        let separator = Span::styled(" │ ", Self::MARKER_STYLE);
+        for (i, (row, cells)) in table.rows.iter().enumerate() {
+            self.row = *row;
+            let style = match i < table.head {
+                true => Style::new().add_modifier(Modifier::BOLD),
+                false => Style::default(),
+            };
+            let mut spans = Vec::new();
+            for (column, width) in widths.iter().enumerate() {
+                if column > 0 {
+                    spans.push(separator.clone());
+                }
+                let cell = cells.get(column).map_or("", String::as_str);
+                let padding = width - cell.width();
+                let (left, right) = match table.alignments.get(column) {
+                    Some(Alignment::Right) => (padding, 0),
+                    Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+                    _ => (0, padding),
+                };
+                spans.push(Span::styled(
+                    format!("{}{cell}{}", " ".repeat(left), " ".repeat(right)),
+                    style,
+                ));
+            }
+            self.push_line(spans);
+            if i + 1 == table.head {
+                let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+                self.push_line(vec![Span::styled(rule.join("─┼─"), Self::MARKER_STYLE)]);
+            }
+        }
+    }
+}
+
+/// The byte index to split text at so the start fits in a width, keeping at least one character.
+fn split_at_width(text: &str, width: usize) -> usize {
+    let mut total = 0;
+    for (i, c) in text.char_indices() {
+        total += c.width().unwrap_or(0);
+        if total > width {
+            return i.max(c.len_utf8());
+        }
+    }
+    text.len()
+}
@@ -2,26 +2,36 @@
 //
 // SPDX-License-Identifier: GNU General Public License v3.0 or later
 
-use crate::tui::component::{Action, Component, ComponentState, FocusState};
+use crate::debugger::DebugCommand;
+use crate::recent::Recent;
+use crate::tui::component::{Action, Component, ComponentState, FocusState, tab_at};
 use crate::tui::menu_bar::MenuBarItemOption::{
-    About, CloseTab, Exit, Reload, Save, ShowHideExplorer, ShowHideLogger,
+    About, CloseAllTabs, CloseOtherTabs, CloseTab, CloseTabsToRight, CompareWithSaved,
+    DebugContinue, DebugPause, DebugStepIn, DebugStepOut, DebugStepOver, DebugStop, Exit, PinTab,
+    RecentFiles, RecentProjects, Reload, Save, ShowHideDebugger, ShowHideExplorer, ShowHideLogger,
+    ShowHideOutline, SwapExplorerSide,
 };
 use anyhow::Context;
 use log::trace;
 use ratatui::buffer::Buffer;
-use ratatui::crossterm::event::{KeyCode, KeyEvent};
-use ratatui::layout::Rect;
+use ratatui::crossterm::event::{
+    Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Position, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Line;
 use ratatui::widgets::{
     Block, Borders, Clear, List, ListItem, ListState, StatefulWidget, Tabs, Widget,
 };
+use std::path::PathBuf;
 use strum::{EnumIter, FromRepr, IntoEnumIterator};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, FromRepr, EnumIter)]
 enum MenuBarItem {
     File,
     View,
+    Tabs,
+    Debug,
     Help,
 }
 
@@ -30,9 +40,25 @@ enum MenuBarItemOption {
     Save,
     CloseTab,
     Reload,
+    CompareWithSaved,
+    RecentFiles,
+    RecentProjects,
     Exit,
     ShowHideExplorer,
     ShowHideLogger,
+    ShowHideOutline,
+    SwapExplorerSide,
+    PinTab,
+    CloseOtherTabs,
+    CloseTabsToRight,
+    CloseAllTabs,
+    DebugContinue,
+    DebugStepOver,
+    DebugStepIn,
+    DebugStepOut,
+    DebugPause,
+    DebugStop,
+    ShowHideDebugger,
     About,
 }
 
@@ -41,11 +67,27 @@ impl MenuBarItemOption {
         match self {
             Save => "Save",
             Reload => "Reload",
+            CompareWithSaved => "Compare with saved",
             Exit => "Exit",
             ShowHideExplorer => "Show / hide explorer",
             ShowHideLogger => "Show / hide logger",
+            ShowHideOutline => "Show / hide outline",
+            SwapExplorerSide => "Move explorer left / right",
+            PinTab => "Pin / unpin tab",
+            CloseOtherTabs => "Close other tabs",
+            CloseTabsToRight => "Close tabs to the right",
+            CloseAllTabs => "Close all tabs",
+            DebugContinue => "Start / continue",
+            DebugStepOver => "Step over",
+            DebugStepIn => "Step into",
+            DebugStepOut => "Step out",
+            DebugPause => "Pause",
+            DebugStop => "Stop",
+            ShowHideDebugger => "Show / hide debugger",
             About => "About",
             CloseTab => "Close tab",
+            RecentFiles => "Open recent file",
+            RecentProjects => "Open recent project",
         }
     }
 }
@@ -67,14 +109,39 @@ impl MenuBarItem {
         match self {
             MenuBarItem::File => "File",
             MenuBarItem::View => "View",
+            MenuBarItem::Tabs => "Tabs",
+            MenuBarItem::Debug => "Debug",
             MenuBarItem::Help => "Help",
         }
     }
 
     pub fn options(&self) -> &[MenuBarItemOption] {
         match self {
-            MenuBarItem::File => &[Save, CloseTab, Reload, Exit],
-            MenuBarItem::View => &[ShowHideExplorer, ShowHideLogger],
+            MenuBarItem::File => &[
+                Save,
+                CloseTab,
+                Reload,
+                CompareWithSaved,
+                RecentFiles,
+                RecentProjects,
+                Exit,
+            ],
+            MenuBarItem::View => &[
+                ShowHideExplorer,
+                ShowHideLogger,
+                ShowHideOutline,
+                SwapExplorerSide,
+            ],
+            MenuBarItem::Tabs => &[PinTab, CloseOtherTabs, CloseTabsToRight, CloseAllTabs],
+            MenuBarItem::Debug => &[
+                DebugContinue,
+                DebugStepOver,
+                DebugStepIn,
+                DebugStepOut,
+                DebugPause,
+                DebugStop,
+                ShowHideDebugger,
+            ],
             MenuBarItem::Help => &[About],
         }
     }
@@ -85,12 +152,27 @@ pub struct MenuBar {
     opened: Option<MenuBarItem>,
     pub(crate) component_state: ComponentState,
     list_state: ListState,
+    /// The option whose nested list of recent entries is currently open, if any.
+    sub_menu: Option<MenuBarItemOption>,
+    sub_list_state: ListState,
+    recent_files: Vec<PathBuf>,
+    recent_projects: Vec<PathBuf>,
+    /// Areas from the last render, used for mouse input.
+    title_bar_area: Rect,
+    drop_down_area: Option<Rect>,
+    sub_menu_area: Option<Rect>,
 }
 
 impl MenuBar {
     pub const ID: &str = "MenuBar";
 
     const DEFAULT_HELP: &str = "(←/h)/(→/l): Select option | Enter: Choose selection";
+    const DROP_DOWN_HELP: &str = concat!(
+        "(↑/k)/(↓/j): Select option | Enter: Choose selection |",
+        " ESC/Q: Close drop-down menu"
+    );
+    const SUB_MENU_HELP: &str =
+        "(↑/k)/(↓/j): Select entry | Enter: Open selection | (←/h)/ESC/Q: Close recent list";
     pub fn new() -> Self {
         trace!(target:Self::ID, "Building {}", Self::ID);
         Self {
@@ -98,9 +180,111 @@ impl MenuBar {
             opened: None,
             component_state: ComponentState::default().with_help_text(Self::DEFAULT_HELP),
             list_state: ListState::default().with_selected(Some(0)),
+            sub_menu: None,
+            sub_list_state: ListState::default().with_selected(Some(0)),
+            recent_files: Vec::new(),
+            recent_projects: Vec::new(),
+            title_bar_area: Rect::default(),
+            drop_down_area: None,
+            sub_menu_area: None,
+        }
+    }
+
+    /// Returns true if the position is over the title bar or an open menu.
+    pub fn contains(&self, position: Position) -> bool {
+        self.title_bar_area.contains(position)
+            || self.drop_down_area.is_some_and(|a| a.contains(position))
+            || self.sub_menu_area.is_some_and(|a| a.contains(position))
+    }
+
+    fn open_menu(&mut self, item: MenuBarItem) {
+        self.selected = item;
+        self.opened = Some(item);
+        self.sub_menu = None;
+        self.list_state.select_first();
+        self.component_state.help_text = Self::DROP_DOWN_HELP.to_string();
+    }
+
+    /// Runs the option at `index` in the opened drop-down menu.
+    fn choose_option(&mut self, index: usize) -> anyhow::Result<Action> {
+        let selection = *self
+            .selected
+            .options()
+            .get(index)
+            .context("Failed to get selected MenuBar option")?;
+        match selection {
+            Save => Ok(Action::Save),
+            Exit => Ok(Action::Quit),
+            Reload => Ok(Action::ReloadFile),
+            CompareWithSaved => Ok(Action::CompareBuffer(None)),
+            ShowHideExplorer => Ok(Action::ShowHideExplorer),
+            ShowHideLogger => Ok(Action::ShowHideLogger),
+            ShowHideOutline => Ok(Action::ShowHideOutline),
+            SwapExplorerSide => Ok(Action::SwapExplorerSide),
+            PinTab => Ok(Action::TogglePinTab),
+            CloseOtherTabs => Ok(Action::CloseOtherTabs),
+            CloseTabsToRight => Ok(Action::CloseTabsToRight),
+            CloseAllTabs => Ok(Action::CloseAllTabs),
+            DebugContinue => Ok(Action::Debug(DebugCommand::Continue)),
+            DebugStepOver => Ok(Action::Debug(DebugCommand::StepOver)),
+            DebugStepIn => Ok(Action::Debug(DebugCommand::StepIn)),
+            DebugStepOut => Ok(Action::Debug(DebugCommand::StepOut)),
+            DebugPause => Ok(Action::Debug(DebugCommand::Pause)),
+            DebugStop => Ok(Action::Debug(DebugCommand::Stop)),
+            ShowHideDebugger => Ok(Action::ShowHideDebugger),
+            About => Ok(Action::ShowHideAbout),
+            CloseTab => Ok(Action::CloseTab),
+            RecentFiles | RecentProjects => {
+                self.sub_menu = Some(selection);
+                self.sub_list_state.select_first();
+                self.component_state.help_text = Self::SUB_MENU_HELP.to_string();
+                Ok(Action::Handled)
+            }
+        }
+    }
+
+    /// Opens the recent entry at `index` in the nested list.
+    fn choose_recent(&mut self, sub_menu: MenuBarItemOption, index: usize) -> Action {
+        match self.sub_menu_entries(sub_menu).get(index).cloned() {
+            Some(path) => {
+                self.close_menus();
+                match sub_menu {
+                    RecentProjects => Action::OpenProject(path),
+                    _ => Action::OpenFile(path),
+                }
+            }
+            None => Action::Noop,
+        }
+    }
+
+    /// Finds the row of a bordered list under the mouse.
+    fn list_row_at(area: Rect, state: &ListState, mouse: MouseEvent) -> Option<usize> {
+        let inner_top = area.y + 1;
+        (mouse.row >= inner_top && mouse.row < area.bottom().saturating_sub(1))
+            .then(|| (mouse.row - inner_top) as usize + state.offset())
+    }
+
+    /// Updates the entries shown in the recent files and projects lists.
+    pub fn set_recent(&mut self, recent: &Recent) {
+        self.recent_files = recent.files.clone();
+        self.recent_projects = recent.projects.clone();
+    }
+
+    fn sub_menu_entries(&self, option: MenuBarItemOption) -> &[PathBuf] {
+        match option {
+            RecentProjects => &self.recent_projects,
+            _ => &self.recent_files,
         }
     }
 
+    pub(crate) fn close_menus(&mut self) {
+        self.opened = None;
+        self.sub_menu = None;
+        self.component_state.help_text = Self::DEFAULT_HELP.to_string();
+        self.list_state.select_first();
+        self.sub_list_state.select_first();
+    }
+
     fn render_title_bar(&self, area: Rect, buf: &mut Buffer) {
         let titles: Vec<Line> = MenuBarItem::iter()
             .map(|item| Line::from(item.id().to_owned()))
@@ -129,7 +313,7 @@ impl MenuBar {
         area: Rect,
         buf: &mut Buffer,
         opened: MenuBarItem,
-    ) {
+    ) -> Rect {
         let popup_area = Self::rect_under_option(title_bar_anchor, area, 27, 10);
         Clear::default().render(popup_area, buf);
         let options = opened.options().iter().map(|i| ListItem::new(i.id()));
@@ -147,6 +331,51 @@ impl MenuBar {
             buf,
             &mut self.list_state,
         );
+        popup_area
+    }
+
+    fn render_sub_menu(
+        &mut self,
+        drop_down_anchor: Rect,
+        area: Rect,
+        buf: &mut Buffer,
+        sub_menu: MenuBarItemOption,
+    ) -> Rect {
+        // Open the nested list to the right of the drop-down menu.
+        let popup_area = Rect {
+            x: drop_down_anchor.x + drop_down_anchor.width,
+            y: drop_down_anchor.y,
+            width: 60.min(
+                area.width
+                    .saturating_sub(drop_down_anchor.x + drop_down_anchor.width),
+            ),
+            height: drop_down_anchor.height,
+        };
+        Clear::default().render(popup_area, buf);
+        let entries = self.sub_menu_entries(sub_menu);
+        let items: Vec<ListItem> = if entries.is_empty() {
+            vec![ListItem::new("No recent entries")]
+        } else {
+            entries
+                .iter()
+                .map(|p| ListItem::new(p.to_string_lossy().to_string()))
+                .collect()
+        };
+        StatefulWidget::render(
+            List::new(items)
+                .block(Block::bordered().title(sub_menu.id()))
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::Blue)
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol(">> "),
+            popup_area,
+            buf,
+            &mut self.sub_list_state,
+        );
+        popup_area
     }
 
     fn rect_under_option(anchor: Rect, area: Rect, width: u16, height: u16) -> Rect {
@@ -174,14 +403,95 @@ impl Widget for &mut MenuBar {
             height: 3,
         };
         self.render_title_bar(title_bar_area, buf);
+        self.title_bar_area = title_bar_area;
+        self.drop_down_area = None;
+        self.sub_menu_area = None;
         if let Some(opened) = self.opened {
-            self.render_drop_down(title_bar_area, area, buf, opened);
+            let drop_down_area = self.render_drop_down(title_bar_area, area, buf, opened);
+            self.drop_down_area = Some(drop_down_area);
+            if let Some(sub_menu) = self.sub_menu {
+                self.sub_menu_area =
+                    Some(self.render_sub_menu(drop_down_area, area, buf, sub_menu));
+            }
         }
     }
 }
 
 impl Component for MenuBar {
+    fn handle_event(&mut self, event: Event) -> anyhow::Result<Action> {
+        match event {
+            Event::Key(key) => self.handle_key_events(key),
+            Event::Mouse(mouse) => self.handle_mouse_events(mouse),
+            _ => Ok(Action::Noop),
+        }
+    }
+
+    /// Clicking a title opens its drop-down menu, and clicking an option runs it.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> anyhow::Result<Action> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return Ok(Action::Noop);
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        if let (Some(sub_menu), Some(area)) = (self.sub_menu, self.sub_menu_area)
+            && area.contains(position)
+        {
+            return Ok(match Self::list_row_at(area, &self.sub_list_state, mouse) {
+                Some(row) => self.choose_recent(sub_menu, row),
+                None => Action::Handled,
+            });
+        }
+        if let Some(area) = self.drop_down_area
+            && area.contains(position)
+        {
+            return match Self::list_row_at(area, &self.list_state, mouse) {
+                Some(row) if row < self.selected.options().len() => {
+                    self.list_state.select(Some(row));
+                    self.choose_option(row)
+                }
+                _ => Ok(Action::Handled),
+            };
+        }
+        if self.title_bar_area.contains(position) {
+            let widths = MenuBarItem::iter().map(|item| Line::from(item.id()).width());
+            match tab_at(widths, self.title_bar_area.x + 1, mouse.column)
+                .and_then(MenuBarItem::from_repr)
+            {
+                // Clicking the opened title again closes its drop-down menu.
+                Some(item) if self.opened == Some(item) => self.close_menus(),
+                Some(item) => self.open_menu(item),
+                None => self.close_menus(),
+            }
+            return Ok(Action::Handled);
+        }
+        Ok(Action::Noop)
+    }
+
     fn handle_key_events(&mut self, key: KeyEvent) -> anyhow::Result<Action> {
+        if let Some(sub_menu) = self.sub_menu {
+            // Keybinds for the nested list of recent entries.
+            return match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.sub_list_state.select_previous();
+                    Ok(Action::Handled)
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.sub_list_state.select_next();
+                    Ok(Action::Handled)
+                }
+                KeyCode::Enter => match self.sub_list_state.selected() {
+                    Some(selected) => Ok(self.choose_recent(sub_menu, selected)),
+                    None => Ok(Action::Noop),
+                },
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::Esc | KeyCode::Char('q') => {
+                    self.sub_menu = None;
+                    self.sub_list_state.select_first();
+                    self.component_state.help_text = Self::DROP_DOWN_HELP.to_string();
+                    Ok(Action::Handled)
+                }
+                _ => Ok(Action::Noop),
+            };
+        }
+
         if self.opened.is_some() {
             // Keybinds for popup menu.
             match key.code {
@@ -193,29 +503,12 @@ impl Component for MenuBar {
                     self.list_state.select_next();
                     Ok(Action::Handled)
                 }
-                KeyCode::Enter => {
-                    if let Some(selected) = self.list_state.selected() {
-                        let selection = self
-                            .selected
-                            .options()
-                            .get(selected)
-                            .context("Failed to get selected MenuBar option")?;
-                        return match selection {
-                            Save => Ok(Action::Save),
-                            Exit => Ok(Action::Quit),
-                            Reload => Ok(Action::ReloadFile),
-                            ShowHideExplorer => Ok(Action::ShowHideExplorer),
-                            ShowHideLogger => Ok(Action::ShowHideLogger),
-                            About => Ok(Action::ShowHideAbout),
-                            CloseTab => Ok(Action::CloseTab),
-                        };
-                    }
-                    Ok(Action::Noop)
-                }
+                KeyCode::Enter => match self.list_state.selected() {
+                    Some(selected) => self.choose_option(selected),
+                    None => Ok(Action::Noop),
+                },
                 KeyCode::Esc | KeyCode::Char('q') => {
-                    self.opened = None;
-                    self.component_state.help_text = Self::DEFAULT_HELP.to_string();
-                    self.list_state.select_first();
+                    self.close_menus();
                     Ok(Action::Handled)
                 }
                 _ => Ok(Action::Noop),
@@ -232,12 +525,7 @@ impl Component for MenuBar {
                     Ok(Action::Handled)
                 }
                 KeyCode::Enter => {
-                    self.opened = Some(self.selected);
-                    self.component_state.help_text = concat!(
-                        "(↑/k)/(↓/j): Select option | Enter: Choose selection |",
-                        " ESC/Q: Close drop-down menu"
-                    )
-                    .to_string();
+                    self.open_menu(self.selected);
                     Ok(Action::Handled)
                 }
                 _ => Ok(Action::Noop),
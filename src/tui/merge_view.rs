@@ -0,0 +1,384 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::merge::{self, Conflict, Resolution};
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Widget};
+
+/// The result of input to the MergeView that the Editor needs to act on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeEvent {
+    /// A conflict was resolved or reset, so the buffer should be replaced with the result.
+    Changed,
+    Close,
+    Handled,
+}
+
+/// What a line in one of the panes of the merge view is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Common,
+    Ours,
+    Base,
+    Theirs,
+    Marker,
+}
+
+/// A line drawn in one of the panes, with its line number in the text of that pane.
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    number: usize,
+    /// The index of the line in the text the view was opened with.
+    line: usize,
+    kind: Kind,
+}
+
+/// A row of the merge view, with a line from our side, the result and their side. Lines of a
+/// conflict are padded so the rows after it line up.
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    cells: [Option<Cell>; 3],
+    /// The index of the conflict the row belongs to.
+    conflict: Option<usize>,
+}
+
+/// A three-pane view resolving the merge conflicts in a file, showing our side, the result and
+/// their side of each conflict next to each other.
+/// The view keeps the text it was opened with, so conflicts can be resolved again or reset
+/// until it is closed.
+#[derive(Debug, Default)]
+pub struct MergeView {
+    visible: bool,
+    /// The buffer when the view was opened, with every conflict unresolved.
+    lines: Vec<String>,
+    conflicts: Vec<Conflict>,
+    resolutions: Vec<Option<Resolution>>,
+    /// The index of the selected conflict.
+    selected: usize,
+    /// The first row shown.
+    scroll: usize,
+    /// The number of rows drawn in the last frame, used to scroll by a page.
+    height: usize,
+}
+
+impl MergeView {
+    pub const HELP: &str = concat!(
+        "ESC: Close merge view | o/t/b: Accept ours / theirs / both | r: Reset conflict |",
+        " (n/]): Next conflict | (N/[): Previous conflict | (↑/k)/(↓/j): Scroll |",
+        " PgUp/PgDn: Scroll page | g/G: Top / bottom"
+    );
+
+    pub const OURS_STYLE: Style = Style::new().bg(Color::Rgb(0x1f, 0x3a, 0x26));
+    pub const BASE_STYLE: Style = Style::new().bg(Color::Rgb(0x33, 0x34, 0x3a));
+    pub const THEIRS_STYLE: Style = Style::new().bg(Color::Rgb(0x1f, 0x2c, 0x4a));
+    pub const MARKER_STYLE: Style = Style::new()
+        .fg(Color::Gray)
+        .bg(Color::Rgb(0x44, 0x47, 0x5a))
+        .add_modifier(Modifier::BOLD);
+
+    /// The unchanged rows shown above a conflict selected with next or previous.
+    const CONTEXT: usize = 3;
+
+    const GUTTER_STYLE: Style = Style::new().fg(Color::DarkGray);
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Opens the view for the conflicts in `lines`, selecting the first conflict that ends on or
+    /// after `row`. Returns false if there are no conflicts.
+    pub fn open(&mut self, lines: Vec<String>, row: usize) -> bool {
+        let conflicts = merge::conflicts(&lines);
+        if conflicts.is_empty() {
+            return false;
+        }
+        let selected = conflicts
+            .iter()
+            .position(|conflict| conflict.end >= row)
+            .unwrap_or(0);
+        self.resolutions = vec![None; conflicts.len()];
+        self.conflicts = conflicts;
+        self.lines = lines;
+        self.visible = true;
+        self.select(selected);
+        true
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.lines.clear();
+        self.conflicts.clear();
+        self.resolutions.clear();
+    }
+
+    /// The text with each resolved conflict replaced by the lines it was resolved with.
+    pub fn result(&self) -> Vec<String> {
+        let mut result = Vec::with_capacity(self.lines.len());
+        let mut row = 0;
+        for (conflict, resolution) in self.conflicts.iter().zip(&self.resolutions) {
+            result.extend_from_slice(&self.lines[row..conflict.start]);
+            match resolution {
+                Some(resolution) => result.extend(conflict.resolved(&self.lines, *resolution)),
+                None => result.extend_from_slice(&self.lines[conflict.rows()]),
+            }
+            row = conflict.end + 1;
+        }
+        result.extend_from_slice(&self.lines[row..]);
+        result
+    }
+
+    /// The row of the result that the selected conflict starts on. A conflict resolved with no
+    /// lines has no rows in the result, so the row after it is used.
+    pub fn selected_row(&self) -> usize {
+        self.rows()
+            .iter()
+            .skip_while(|row| row.conflict != Some(self.selected))
+            .find_map(|row| row.cells[1])
+            .map_or(usize::MAX, |cell| cell.number)
+    }
+
+    fn unresolved(&self) -> usize {
+        self.resolutions.iter().filter(|r| r.is_none()).count()
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        // The next line number in our side, the result and their side.
+        let mut numbers = [0; 3];
+        let mut push = |rows: &mut Vec<Row>, lines: [Option<(usize, Kind)>; 3], conflict| {
+            let mut cells = [None; 3];
+            for (pane, line) in lines.iter().enumerate() {
+                if let Some((line, kind)) = *line {
+                    cells[pane] = Some(Cell {
+                        number: numbers[pane],
+                        line,
+                        kind,
+                    });
+                    numbers[pane] += 1;
+                }
+            }
+            rows.push(Row { cells, conflict });
+        };
+        let common = |line| Some((line, Kind::Common));
+        let mut line = 0;
+        for (index, (conflict, resolution)) in
+            self.conflicts.iter().zip(&self.resolutions).enumerate()
+        {
+            for line in line..conflict.start {
+                push(&mut rows, [common(line); 3], None);
+            }
+            let ours: Vec<_> = conflict.ours().map(|line| (line, Kind::Ours)).collect();
+            let theirs: Vec<_> = conflict.theirs().map(|line| (line, Kind::Theirs)).collect();
+            let result: Vec<_> = match resolution {
+                Some(Resolution::Ours) => ours.clone(),
+                Some(Resolution::Theirs) => theirs.clone(),
+                Some(Resolution::Both) => [ours.clone(), theirs.clone()].concat(),
+                None => conflict
+                    .rows()
+                    .map(|line| {
+                        let kind = match line {
+                            line if conflict.is_marker(line) => Kind::Marker,
+                            line if conflict.ours().contains(&line) => Kind::Ours,
+                            line if conflict.theirs().contains(&line) => Kind::Theirs,
+                            _ => Kind::Base,
+                        };
+                        (line, kind)
+                    })
+                    .collect(),
+            };
+            // Conflicts with no lines on any side still get a row, so they can be seen.
+            let height = ours.len().max(theirs.len()).max(result.len()).max(1);
+            for i in 0..height {
+                let lines = [ours.get(i), result.get(i), theirs.get(i)].map(|l| l.copied());
+                push(&mut rows, lines, Some(index));
+            }
+            line = conflict.end + 1;
+        }
+        for line in line..self.lines.len() {
+            push(&mut rows, [common(line); 3], None);
+        }
+        rows
+    }
+
+    /// Selects a conflict and scrolls to it.
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.conflicts.len().saturating_sub(1));
+        self.scroll = self
+            .rows()
+            .iter()
+            .position(|row| row.conflict == Some(self.selected))
+            .map_or(0, |row| row.saturating_sub(Self::CONTEXT));
+    }
+
+    /// Resolves the selected conflict, then selects the next conflict that is unresolved.
+    fn resolve(&mut self, resolution: Option<Resolution>) -> MergeEvent {
+        let Some(current) = self.resolutions.get_mut(self.selected) else {
+            return MergeEvent::Handled;
+        };
+        *current = resolution;
+        if resolution.is_some() {
+            let count = self.conflicts.len();
+            let next = (1..count)
+                .map(|offset| (self.selected + offset) % count)
+                .find(|i| self.resolutions[*i].is_none());
+            if let Some(next) = next {
+                self.select(next);
+            }
+        }
+        MergeEvent::Changed
+    }
+
+    fn scroll_by(&mut self, rows: isize) {
+        let last = self.rows().len().saturating_sub(self.height.max(1));
+        self.scroll = self.scroll.saturating_add_signed(rows).min(last);
+    }
+
+    pub fn handle_key_events(&mut self, key: KeyEvent) -> MergeEvent {
+        let page = self.height.max(1) as isize;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return MergeEvent::Close,
+            KeyCode::Char('o') => return self.resolve(Some(Resolution::Ours)),
+            KeyCode::Char('t') => return self.resolve(Some(Resolution::Theirs)),
+            KeyCode::Char('b') => return self.resolve(Some(Resolution::Both)),
+            KeyCode::Char('r') => return self.resolve(None),
+            KeyCode::Char('n') | KeyCode::Char(']') => self.select(self.selected + 1),
+            KeyCode::Char('N') | KeyCode::Char('[') => self.select(self.selected.saturating_sub(1)),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll_by(1),
+            KeyCode::PageUp => self.scroll_by(-page),
+            KeyCode::PageDown => self.scroll_by(page),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::End | KeyCode::Char('G') => self.scroll_by(isize::MAX),
+            _ => {}
+        }
+        MergeEvent::Handled
+    }
+
+    pub fn handle_mouse_events(&mut self, mouse: MouseEvent) -> MergeEvent {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll_by(3),
+            MouseEventKind::ScrollUp => self.scroll_by(-3),
+            _ => {}
+        }
+        MergeEvent::Handled
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let status = match self.unresolved() {
+            0 => " All conflicts resolved ".to_string(),
+            unresolved => format!(
+                " Conflict {} of {} | {unresolved} unresolved ",
+                self.selected + 1,
+                self.conflicts.len()
+            ),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Merge conflicts ")
+            .title_bottom(Line::from(status).right_aligned())
+            .border_style(Style::default().fg(Color::LightYellow));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [header, body] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+        let columns = |area| {
+            Layout::horizontal([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .areas::<5>(area)
+        };
+        self.height = body.height as usize;
+
+        // Each side is titled with the label after its marker, such as a branch name.
+        let label = |row: Option<usize>| {
+            row.map(|row| Conflict::label(&self.lines, row))
+                .filter(|label| !label.is_empty())
+                .map_or(String::new(), |label| format!(" ({label})"))
+        };
+        let first = self.conflicts.first();
+        let titles = [
+            format!("Ours{}", label(first.map(|c| c.start))),
+            "Result".to_string(),
+            format!("Theirs{}", label(first.map(|c| c.end))),
+        ];
+        let [ours, _, result, _, theirs] = columns(header);
+        for (title, area) in titles.iter().zip([ours, result, theirs]) {
+            let title = Span::styled(title, Style::default().fg(Color::Yellow));
+            buf.set_line(area.x + 1, area.y, &Line::from(title), area.width);
+        }
+
+        let rows = self.rows();
+        let number_width = self.lines.len().to_string().len();
+        let panes = columns(body);
+        let first = rows
+            .iter()
+            .position(|row| row.conflict == Some(self.selected));
+        for (y, (index, row)) in rows
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(body.height as usize)
+            .enumerate()
+        {
+            let y = body.y + y as u16;
+            for (pane, area) in [panes[0], panes[2], panes[4]].into_iter().enumerate() {
+                let marker = match Some(index) == first && pane == 0 {
+                    true => Span::styled("▶", Style::default().fg(Color::Yellow)),
+                    false => Span::raw(" "),
+                };
+                self.render_cell(area, y, buf, marker, row.cells[pane], number_width);
+            }
+            for divider in [panes[1], panes[3]] {
+                buf.set_string(divider.x, y, "│", Self::GUTTER_STYLE);
+            }
+        }
+    }
+
+    /// Draws a line of one pane, or a filler where the line of another pane has no match.
+    fn render_cell(
+        &self,
+        area: Rect,
+        y: u16,
+        buf: &mut Buffer,
+        marker: Span,
+        cell: Option<Cell>,
+        number_width: usize,
+    ) {
+        let row = Rect::new(area.x, y, area.width, 1);
+        let Some(cell) = cell else {
+            let filler = "╱".repeat((area.width as usize).saturating_sub(1));
+            let filler = Span::styled(filler, Style::default().fg(Color::Rgb(0x44, 0x44, 0x4c)));
+            buf.set_line(row.x, row.y, &Line::from(vec![marker, filler]), row.width);
+            return;
+        };
+        let style = match cell.kind {
+            Kind::Common => Style::default(),
+            Kind::Ours => Self::OURS_STYLE,
+            Kind::Base => Self::BASE_STYLE,
+            Kind::Theirs => Self::THEIRS_STYLE,
+            Kind::Marker => Self::MARKER_STYLE,
+        };
+        buf.set_style(row, style);
+        let number = Span::styled(
+            format!("{:>number_width$} ", cell.number + 1),
+            Self::GUTTER_STYLE,
+        );
+        let text = Span::styled(self.lines[cell.line].replace('\t', "    "), style);
+        buf.set_line(
+            row.x,
+            row.y,
+            &Line::from(vec![marker, number, text]),
+            row.width,
+        );
+    }
+}
@@ -0,0 +1,356 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::tui::text;
+use edtui::{Index2, Lines};
+use ratatui::crossterm::event::KeyCode;
+
+/// A cursor and the other end of its selection. Nothing is selected when both are equal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Caret {
+    pub anchor: Index2,
+    pub head: Index2,
+}
+
+impl Caret {
+    pub fn new(at: Index2) -> Self {
+        Self {
+            anchor: at,
+            head: at,
+        }
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.anchor != self.head
+    }
+
+    /// The selected range, ordered from start to end.
+    pub fn range(&self) -> (Index2, Index2) {
+        text::ordered(self.anchor, self.head)
+    }
+}
+
+/// Cursors used to edit several places in an Editor at once.
+/// The first caret is the primary caret, which follows the cursor of the EditorView. Edits are
+/// applied at every caret, from the end of the buffer to the start, so each edit leaves the
+/// positions of the carets before it unchanged.
+#[derive(Debug, Default)]
+pub struct MultiCursor {
+    carets: Vec<Caret>,
+}
+
+impl MultiCursor {
+    /// True while there is more than one caret.
+    pub fn is_active(&self) -> bool {
+        self.carets.len() > 1
+    }
+
+    pub fn carets(&self) -> &[Caret] {
+        &self.carets
+    }
+
+    pub fn primary(&self) -> Option<Caret> {
+        self.carets.first().copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.carets.clear();
+    }
+
+    /// Replaces all carets. Carets at the same position as an earlier caret are dropped.
+    pub fn set(&mut self, carets: Vec<Caret>) {
+        self.carets.clear();
+        for caret in carets {
+            self.add(caret);
+        }
+    }
+
+    /// Adds a caret, returning false if there is already a caret at the same position.
+    pub fn add(&mut self, caret: Caret) -> bool {
+        if self.carets.iter().any(|c| c.head == caret.head) {
+            return false;
+        }
+        self.carets.push(caret);
+        true
+    }
+
+    /// Adds a caret on the row above the highest caret, or below the lowest caret.
+    /// The new caret is placed in the column of the primary caret where the row is long enough.
+    pub fn add_vertical(&mut self, lines: &Lines, up: bool) {
+        let Some(primary) = self.primary() else {
+            return;
+        };
+        let rows = self.carets.iter().map(|c| c.head.row);
+        let row = match up {
+            true => rows.min().and_then(|row| row.checked_sub(1)),
+            false => rows
+                .max()
+                .map(|row| row + 1)
+                .filter(|row| *row < lines.len()),
+        };
+        if let Some(row) = row {
+            let col = primary.head.col.min(lines.len_col(row).unwrap_or(0));
+            self.add(Caret::new(Index2::new(row, col)));
+        }
+    }
+
+    /// Selects the next occurrence of the primary caret's selection with a new caret, searching
+    /// from the last caret added and wrapping around the end of the buffer. If the primary caret has
+    /// no selection, the word under it is selected instead.
+    pub fn add_next_occurrence(&mut self, lines: &[String]) {
+        let Some(primary) = self.primary() else {
+            return;
+        };
+        if !primary.has_selection() {
+            if let Some(word) = word_at(lines, primary.head) {
+                self.carets[0] = word;
+            }
+            return;
+        }
+        let (start, end) = primary.range();
+        let needle = text::text_in_range(lines, start, end);
+        let haystack = lines.join("\n");
+        let from = self
+            .carets
+            .last()
+            .map_or(0, |c| byte_offset(lines, c.range().1));
+        let found = haystack[from..]
+            .match_indices(&needle)
+            .map(|(i, _)| from + i)
+            .chain(haystack[..from].match_indices(&needle).map(|(i, _)| i))
+            .map(|i| {
+                let start = position_at(&haystack, i);
+                let end = position_at(&haystack, i + needle.len());
+                Caret {
+                    anchor: start,
+                    head: end,
+                }
+            })
+            .find(|caret| self.carets.iter().all(|c| c.range() != caret.range()));
+        if let Some(caret) = found {
+            self.add(caret);
+        }
+    }
+
+    /// Moves every caret for a cursor key. When `extend` is true the selections are extended,
+    /// otherwise they are cleared.
+    pub fn move_carets(&mut self, lines: &Lines, code: KeyCode, extend: bool) {
+        let line_len = |row: usize| lines.len_col(row).unwrap_or(0);
+        for caret in self.carets.iter_mut() {
+            let Index2 { row, col } = caret.head;
+            caret.head = match code {
+                KeyCode::Left if col > 0 => Index2::new(row, col - 1),
+                KeyCode::Left if row > 0 => Index2::new(row - 1, line_len(row - 1)),
+                KeyCode::Right if col < line_len(row) => Index2::new(row, col + 1),
+                KeyCode::Right if row + 1 < lines.len() => Index2::new(row + 1, 0),
+                KeyCode::Up if row > 0 => Index2::new(row - 1, col.min(line_len(row - 1))),
+                KeyCode::Down if row + 1 < lines.len() => {
+                    Index2::new(row + 1, col.min(line_len(row + 1)))
+                }
+                KeyCode::Home => Index2::new(row, 0),
+                KeyCode::End => Index2::new(row, line_len(row)),
+                _ => caret.head,
+            };
+            if !extend {
+                caret.anchor = caret.head;
+            }
+        }
+        self.dedup();
+    }
+
+    /// Caret indices in the order the carets appear in the buffer.
+    fn buffer_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.carets.len()).collect();
+        order.sort_by_key(|i| {
+            let (start, _) = self.carets[*i].range();
+            (start.row, start.col)
+        });
+        order
+    }
+
+    /// The selected text of each caret, in the order the carets appear in the buffer.
+    pub fn selected_texts(&self, lines: &[String]) -> Vec<String> {
+        self.buffer_order()
+            .into_iter()
+            .map(|i| {
+                let (start, end) = self.carets[i].range();
+                text::text_in_range(lines, start, end)
+            })
+            .collect()
+    }
+
+    /// Inserts text at every caret, replacing any selections. If there is one text for each caret,
+    /// the texts are inserted at the carets in buffer order, as when pasting lines copied from the
+    /// same carets.
+    pub fn insert(&mut self, lines: &mut Lines, texts: &[String]) {
+        let mut rank = vec![0; self.carets.len()];
+        for (n, index) in self.buffer_order().into_iter().enumerate() {
+            rank[index] = n;
+        }
+        let joined = texts.join("\n");
+        self.edit(lines, |_, index, caret| {
+            let text = match texts.len() == rank.len() {
+                true => &texts[rank[index]],
+                false => &joined,
+            };
+            let (start, end) = caret.range();
+            (start, end, text.to_string())
+        });
+    }
+
+    /// Deletes the selection of each caret, leaving carets without a selection in place.
+    pub fn delete_selections(&mut self, lines: &mut Lines) {
+        self.insert(lines, &[String::new()]);
+    }
+
+    /// Deletes the selection of each caret, or the character before each caret.
+    pub fn backspace(&mut self, lines: &mut Lines) {
+        self.edit(lines, |lines, _, caret| {
+            let (start, end) = caret.range();
+            match (caret.has_selection(), start) {
+                (true, _) => (start, end, String::new()),
+                (false, Index2 { row: 0, col: 0 }) => (start, end, String::new()),
+                (false, Index2 { row, col: 0 }) => (
+                    Index2::new(row - 1, lines.len_col(row - 1).unwrap_or(0)),
+                    end,
+                    String::new(),
+                ),
+                (false, Index2 { row, col }) => (Index2::new(row, col - 1), end, String::new()),
+            }
+        });
+    }
+
+    /// Deletes the selection of each caret, or the character after each caret.
+    pub fn delete(&mut self, lines: &mut Lines) {
+        self.edit(lines, |lines, _, caret| {
+            let (start, end) = caret.range();
+            if caret.has_selection() {
+                return (start, end, String::new());
+            }
+            let Index2 { row, col } = end;
+            let end = match col < lines.len_col(row).unwrap_or(0) {
+                true => Index2::new(row, col + 1),
+                false if row + 1 < lines.len() => Index2::new(row + 1, 0),
+                false => end,
+            };
+            (start, end, String::new())
+        });
+    }
+
+    /// Replaces a range at each caret. `replace` is given each caret and its index, and returns the
+    /// range to replace and the text to insert. Each caret is left at the end of its inserted text.
+    fn edit<F>(&mut self, lines: &mut Lines, mut replace: F)
+    where
+        F: FnMut(&Lines, usize, Caret) -> (Index2, Index2, String),
+    {
+        let mut order = self.buffer_order();
+        order.reverse();
+        // The start of the last range replaced, which later ranges must not overlap.
+        let mut limit: Option<Index2> = None;
+        let mut edited: Vec<usize> = Vec::new();
+        for index in order {
+            let (start, end, inserted) = replace(lines, index, self.carets[index]);
+            let end = match limit {
+                Some(limit) if (end.row, end.col) > (limit.row, limit.col) => limit,
+                _ => end,
+            };
+            let new_end = text::replace_range(lines, start, end, &inserted);
+            for edited in &edited {
+                let caret = &mut self.carets[*edited];
+                caret.anchor = shift(caret.anchor, end, new_end);
+                caret.head = shift(caret.head, end, new_end);
+            }
+            self.carets[index] = Caret::new(new_end);
+            limit = Some(start);
+            edited.push(index);
+        }
+        self.dedup();
+    }
+
+    fn dedup(&mut self) {
+        let carets = std::mem::take(&mut self.carets);
+        self.set(carets);
+    }
+}
+
+/// The carets for a rectangular block between two corners, one for each row, each selecting the
+/// columns of the block. If `inclusive` is true the block includes the column of the rightmost
+/// corner. The caret on the row of `cursor` is first so it remains the primary.
+pub fn block_carets(
+    lines: &[String],
+    anchor: Index2,
+    cursor: Index2,
+    inclusive: bool,
+) -> Vec<Caret> {
+    let left = anchor.col.min(cursor.col);
+    let right = anchor.col.max(cursor.col) + usize::from(inclusive);
+    let rows = anchor.row.min(cursor.row)..=anchor.row.max(cursor.row);
+    let mut carets: Vec<Caret> = rows
+        .map(|row| {
+            let len = line_len(lines, row);
+            Caret {
+                anchor: Index2::new(row, left.min(len)),
+                head: Index2::new(row, right.min(len)),
+            }
+        })
+        .collect();
+    if let Some(index) = carets.iter().position(|c| c.head.row == cursor.row) {
+        let primary = carets.remove(index);
+        carets.insert(0, primary);
+    }
+    carets
+}
+
+/// Maps a position after the end of a replaced range to where it is once the text is replaced.
+fn shift(position: Index2, end: Index2, new_end: Index2) -> Index2 {
+    if (position.row, position.col) < (end.row, end.col) {
+        return position;
+    }
+    match position.row == end.row {
+        true => Index2::new(new_end.row, new_end.col + position.col - end.col),
+        false => Index2::new(position.row + new_end.row - end.row, position.col),
+    }
+}
+
+fn line_len(lines: &[String], row: usize) -> usize {
+    lines.get(row).map_or(0, |line| line.chars().count())
+}
+
+/// The byte offset of a position in the lines joined by newlines.
+fn byte_offset(lines: &[String], at: Index2) -> usize {
+    let before: usize = lines.iter().take(at.row).map(|line| line.len() + 1).sum();
+    let col = lines.get(at.row).map_or(0, |line| {
+        line.char_indices()
+            .nth(at.col)
+            .map_or(line.len(), |(index, _)| index)
+    });
+    before + col
+}
+
+/// The position of a byte offset in text with lines joined by newlines.
+fn position_at(text: &str, offset: usize) -> Index2 {
+    let before = &text[..offset];
+    let row = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Index2::new(row, before[line_start..].chars().count())
+}
+
+/// The word containing a position, selected from its start to its end.
+fn word_at(lines: &[String], at: Index2) -> Option<Caret> {
+    let chars: Vec<char> = lines.get(at.row)?.chars().collect();
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+    let col = at.col.min(chars.len());
+    let start = chars[..col]
+        .iter()
+        .rposition(|c| !is_word(c))
+        .map_or(0, |i| i + 1);
+    let end = chars[col..]
+        .iter()
+        .position(|c| !is_word(c))
+        .map_or(chars.len(), |i| col + i);
+    (start < end).then(|| Caret {
+        anchor: Index2::new(at.row, start),
+        head: Index2::new(at.row, end),
+    })
+}
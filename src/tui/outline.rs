@@ -0,0 +1,174 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::symbols::Symbol;
+use crate::tui::component::{Action, Component, ComponentState, Focus, FocusState};
+use crate::tui::editor::Editor;
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget, Widget};
+use std::path::PathBuf;
+
+/// Lists the functions, types and headings in the current editor, selecting the symbol at the
+/// cursor until the outline is focused.
+pub struct Outline {
+    symbols: Vec<Symbol>,
+    list_state: ListState,
+    /// The file and buffer contents the symbols were found in, so they are only found again
+    /// after the buffer changes.
+    path: Option<PathBuf>,
+    text: String,
+    /// The area drawn in the last frame, used to find the symbol under the mouse.
+    area: Rect,
+    pub(crate) component_state: ComponentState,
+}
+
+impl Outline {
+    pub const ID: &str = "Outline";
+
+    pub fn new() -> Self {
+        trace!(target:Self::ID, "Building {}", Self::ID);
+        Self {
+            symbols: Vec::new(),
+            list_state: ListState::default(),
+            path: None,
+            text: String::new(),
+            area: Rect::default(),
+            component_state: ComponentState::default().with_help_text(concat!(
+                "(↑/k)/(↓/j): Select symbol | Home/End: First / last symbol |",
+                " Enter: Go to symbol in editor"
+            )),
+        }
+    }
+
+    /// Finds the symbols in an editor if its buffer changed. While the outline isn't focused, the
+    /// symbol containing the cursor is selected.
+    pub fn update(&mut self, editor: Option<&mut Editor>) {
+        let Some(editor) = editor else {
+            self.symbols.clear();
+            self.path = None;
+            self.text.clear();
+            return;
+        };
+        let text = editor.state.lines.to_string();
+        if self.path != editor.file_path || self.text != text {
+            trace!(target:Self::ID, "Finding symbols in {:?}", editor.file_path);
+            self.symbols = editor.symbols();
+            self.path = editor.file_path.clone();
+            self.text = text;
+        }
+        if !self.is_active() {
+            let cursor = editor.state.cursor;
+            let current = self
+                .symbols
+                .iter()
+                .rposition(|s| (s.row, s.col) <= (cursor.row, cursor.col));
+            self.list_state.select(current);
+        }
+    }
+
+    /// The position of the selected symbol.
+    fn selected_position(&self) -> Option<(usize, usize)> {
+        let symbol = self.symbols.get(self.list_state.selected()?)?;
+        Some((symbol.row, symbol.col))
+    }
+
+    fn goto_selected(&self) -> Action {
+        match self.selected_position() {
+            Some((row, col)) => Action::GoToPosition(row, col),
+            None => Action::Noop,
+        }
+    }
+}
+
+impl Widget for &mut Outline {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = area;
+        let items: Vec<ListItem> = self
+            .symbols
+            .iter()
+            .map(|symbol| {
+                ListItem::new(Line::from(vec![
+                    Span::raw("  ".repeat(symbol.depth)),
+                    Span::styled(symbol.kind.label(), Style::default().fg(Color::Magenta)),
+                    Span::raw(" "),
+                    Span::raw(symbol.name.as_str()),
+                ]))
+            })
+            .collect();
+        StatefulWidget::render(
+            List::new(items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Outline")
+                        .border_style(Style::default().fg(self.component_state.get_active_color()))
+                        .title_style(Style::default().fg(Color::Green))
+                        .title_alignment(Alignment::Center),
+                )
+                .highlight_style(
+                    Style::new()
+                        .fg(Color::Black)
+                        .bg(Color::Rgb(57, 59, 64))
+                        .add_modifier(Modifier::BOLD),
+                ),
+            area,
+            buf,
+            &mut self.list_state,
+        );
+    }
+}
+
+impl Component for Outline {
+    fn handle_event(&mut self, event: Event) -> Result<Action> {
+        match event {
+            Event::Key(key) => self.handle_key_events(key),
+            Event::Mouse(mouse) => self.handle_mouse_events(mouse),
+            _ => Ok(Action::Noop),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.list_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.list_state.select_next(),
+            KeyCode::Home => self.list_state.select_first(),
+            KeyCode::End => self.list_state.select_last(),
+            KeyCode::Enter => return Ok(self.goto_selected()),
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    /// Clicking a symbol goes to it in the editor.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.list_state.scroll_down_by(1),
+            MouseEventKind::ScrollUp => self.list_state.scroll_up_by(1),
+            MouseEventKind::Down(_) => {
+                let inner_top = self.area.y + 1;
+                if mouse.row < inner_top || mouse.row >= self.area.bottom().saturating_sub(1) {
+                    return Ok(Action::Noop);
+                }
+                let row = (mouse.row - inner_top) as usize + self.list_state.offset();
+                if row >= self.symbols.len() {
+                    return Ok(Action::Noop);
+                }
+                self.list_state.select(Some(row));
+                return Ok(self.goto_selected());
+            }
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    fn is_active(&self) -> bool {
+        self.component_state.focus == Focus::Active
+    }
+}
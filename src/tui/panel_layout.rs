@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::config;
+use log::{error, trace};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExplorerSide {
+    #[default]
+    Left,
+    Right,
+}
+
+/// A border between two panels that can be dragged with the mouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Divider {
+    Explorer,
+    Logger,
+}
+
+/// Sizes and placement of the TUI panels, remembered across sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    /// Width of the Explorer in columns.
+    pub explorer_width: u16,
+    /// Height of the Logger as a percentage of the terminal height.
+    pub logger_height: u16,
+    pub explorer_side: ExplorerSide,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            explorer_width: 30,
+            logger_height: 30,
+            explorer_side: ExplorerSide::Left,
+        }
+    }
+}
+
+impl PanelLayout {
+    pub const ID: &str = "PanelLayout";
+
+    const FILE_NAME: &str = "tui_layout.toml";
+    const MIN_EXPLORER_WIDTH: u16 = 10;
+    const MAX_EXPLORER_WIDTH: u16 = 120;
+    const MIN_LOGGER_HEIGHT: u16 = 10;
+    const MAX_LOGGER_HEIGHT: u16 = 80;
+
+    pub fn load() -> Self {
+        let mut layout: Self = config::load(Self::FILE_NAME);
+        // Clamp values in case the file was edited by hand.
+        layout.set_explorer_width(layout.explorer_width);
+        layout.set_logger_height(layout.logger_height);
+        layout
+    }
+
+    pub fn save(&self) {
+        trace!(target:Self::ID, "Saving panel layout {self:?}");
+        if let Err(e) = config::save(Self::FILE_NAME, self) {
+            error!(target:Self::ID, "Failed to save panel layout: {e:#}");
+        }
+    }
+
+    pub fn set_explorer_width(&mut self, width: u16) {
+        self.explorer_width = width.clamp(Self::MIN_EXPLORER_WIDTH, Self::MAX_EXPLORER_WIDTH);
+    }
+
+    pub fn set_logger_height(&mut self, height: u16) {
+        self.logger_height = height.clamp(Self::MIN_LOGGER_HEIGHT, Self::MAX_LOGGER_HEIGHT);
+    }
+
+    pub fn resize_explorer(&mut self, delta: i16) {
+        self.set_explorer_width(self.explorer_width.saturating_add_signed(delta));
+    }
+
+    pub fn resize_logger(&mut self, delta: i16) {
+        self.set_logger_height(self.logger_height.saturating_add_signed(delta));
+    }
+
+    pub fn toggle_explorer_side(&mut self) {
+        self.explorer_side = match self.explorer_side {
+            ExplorerSide::Left => ExplorerSide::Right,
+            ExplorerSide::Right => ExplorerSide::Left,
+        };
+    }
+}
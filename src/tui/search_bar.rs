@@ -0,0 +1,343 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use anyhow::{Context, Result};
+use edtui::Index2;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Widget};
+use regex::{Captures, Regex, RegexBuilder};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchOptions {
+    pub regex: bool,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+}
+
+/// A match on a single buffer row, as a range of character columns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchMatch {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SearchField {
+    Find,
+    Replace,
+}
+
+/// The result of input to the SearchBar that the Editor needs to act on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchEvent {
+    /// The query or options changed and matches should be found again.
+    Changed,
+    Next,
+    Previous,
+    Replace,
+    ReplaceAll,
+    Close,
+    Handled,
+}
+
+/// A search and replace bar shown under an Editor.
+#[derive(Debug, Default)]
+pub struct SearchBar {
+    pub query: String,
+    pub replacement: String,
+    pub options: SearchOptions,
+    visible: bool,
+    field: Option<SearchField>,
+    matches: Vec<SearchMatch>,
+    current: Option<usize>,
+    error: Option<String>,
+}
+
+impl SearchBar {
+    pub const ID: &str = "SearchBar";
+
+    pub const HELP: &str = concat!(
+        "ESC: Close search | TAB: Find / Replace | ENTER: Next match / Replace |",
+        " (↑/↓): Previous / Next match | CTRL+A: Replace all | CTRL+R: Regex |",
+        " CTRL+K: Match case | CTRL+W: Whole word"
+    );
+
+    pub const MATCH_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Yellow);
+
+    pub const CURRENT_MATCH_STYLE: Style = Style::new().fg(Color::Black).bg(Color::LightRed);
+
+    /// The height of the search bar, including borders.
+    pub const HEIGHT: u16 = 3;
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn open(&mut self) {
+        self.visible = true;
+        self.field = Some(SearchField::Find);
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.field = None;
+    }
+
+    pub fn matches(&self) -> &[SearchMatch] {
+        &self.matches
+    }
+
+    pub fn current_match(&self) -> Option<SearchMatch> {
+        self.current.and_then(|i| self.matches.get(i)).copied()
+    }
+
+    /// Builds the regex for the current query and options, or None if the query is empty.
+    pub fn regex(&self) -> Result<Option<Regex>> {
+        if self.query.is_empty() {
+            return Ok(None);
+        }
+        let pattern = match self.options.regex {
+            true => self.query.clone(),
+            false => regex::escape(&self.query),
+        };
+        let pattern = match self.options.whole_word {
+            true => format!(r"\b(?:{pattern})\b"),
+            false => pattern,
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.options.case_sensitive)
+            .build()
+            .map(Some)
+            .context("Invalid search pattern")
+    }
+
+    /// Finds all matches in `lines`, selecting the first match at or after the cursor.
+    pub fn find(&mut self, lines: &[String], cursor: Index2) {
+        self.update(lines);
+        self.current = self
+            .matches
+            .iter()
+            .position(|m| (m.row, m.start) >= (cursor.row, cursor.col))
+            .or((!self.matches.is_empty()).then_some(0));
+    }
+
+    /// Finds all matches in `lines` again, keeping the current match index where possible.
+    pub fn update(&mut self, lines: &[String]) {
+        self.error = None;
+        self.matches = match self.regex() {
+            Ok(Some(regex)) => find_matches(lines, &regex),
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                self.error = Some(format!("{e:#}"));
+                Vec::new()
+            }
+        };
+        self.current = match self.matches.len() {
+            0 => None,
+            len => Some(self.current.unwrap_or(0).min(len - 1)),
+        };
+    }
+
+    pub fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = Some(self.current.map_or(0, |i| (i + 1) % self.matches.len()));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.matches.is_empty() {
+            let len = self.matches.len();
+            self.current = Some(self.current.map_or(len - 1, |i| (i + len - 1) % len));
+        }
+    }
+
+    /// Replaces the current match in `lines`, returning the position following the replaced
+    /// text or None if there was nothing to replace.
+    pub fn replace_current(&self, lines: &mut [String]) -> Result<Option<Index2>> {
+        let (Some(regex), Some(m)) = (self.regex()?, self.current_match()) else {
+            return Ok(None);
+        };
+        let Some(line) = lines.get_mut(m.row) else {
+            return Ok(None);
+        };
+        let Some(captures) = regex.captures_at(line, byte_index(line, m.start)) else {
+            return Ok(None);
+        };
+        let range = captures.get(0).map_or(0..0, |whole| whole.range());
+        let replacement = self.expand(&captures);
+        let end = line[..range.start].chars().count() + replacement.chars().count();
+        line.replace_range(range, &replacement);
+        Ok(Some(Index2::new(m.row, end)))
+    }
+
+    /// Replaces every match in `lines`, returning the number of replacements made.
+    pub fn replace_all(&self, lines: &mut [String]) -> Result<usize> {
+        let Some(regex) = self.regex()? else {
+            return Ok(0);
+        };
+        let mut count = 0;
+        for line in lines.iter_mut() {
+            let found = regex.find_iter(line).filter(|m| !m.is_empty()).count();
+            if found == 0 {
+                continue;
+            }
+            count += found;
+            *line = regex
+                .replace_all(line, |captures: &Captures| self.expand(captures))
+                .into_owned();
+        }
+        Ok(count)
+    }
+
+    /// The replacement text for a match. Capture groups such as `$1` are expanded in regex mode.
+    fn expand(&self, captures: &Captures) -> String {
+        let mut replacement = String::new();
+        // Empty matches are never shown as results, so they are left unchanged.
+        if captures.get(0).is_some_and(|m| m.is_empty()) {
+            return replacement;
+        }
+        match self.options.regex {
+            true => captures.expand(&self.replacement, &mut replacement),
+            false => replacement.push_str(&self.replacement),
+        }
+        replacement
+    }
+
+    pub fn handle_key_events(&mut self, key: KeyEvent) -> SearchEvent {
+        let field = self.field.unwrap_or(SearchField::Find);
+        match key.code {
+            KeyCode::Esc => SearchEvent::Close,
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.field = Some(match field {
+                    SearchField::Find => SearchField::Replace,
+                    SearchField::Replace => SearchField::Find,
+                });
+                SearchEvent::Handled
+            }
+            KeyCode::Enter => match field {
+                SearchField::Find => SearchEvent::Next,
+                SearchField::Replace => SearchEvent::Replace,
+            },
+            KeyCode::Down => SearchEvent::Next,
+            KeyCode::Up => SearchEvent::Previous,
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c {
+                'a' => SearchEvent::ReplaceAll,
+                'r' => {
+                    self.options.regex = !self.options.regex;
+                    SearchEvent::Changed
+                }
+                'k' => {
+                    self.options.case_sensitive = !self.options.case_sensitive;
+                    SearchEvent::Changed
+                }
+                'w' => {
+                    self.options.whole_word = !self.options.whole_word;
+                    SearchEvent::Changed
+                }
+                _ => SearchEvent::Handled,
+            },
+            KeyCode::Backspace => match field {
+                SearchField::Find => {
+                    self.query.pop();
+                    SearchEvent::Changed
+                }
+                SearchField::Replace => {
+                    self.replacement.pop();
+                    SearchEvent::Handled
+                }
+            },
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::ALT) => match field {
+                SearchField::Find => {
+                    self.query.push(c);
+                    SearchEvent::Changed
+                }
+                SearchField::Replace => {
+                    self.replacement.push(c);
+                    SearchEvent::Handled
+                }
+            },
+            // Input meant for the search bar should never reach the vim emulation handler.
+            _ => SearchEvent::Handled,
+        }
+    }
+
+    fn field_spans(&self, label: &str, value: &str, field: SearchField) -> Vec<Span<'_>> {
+        let style = match self.field == Some(field) {
+            true => Style::default().add_modifier(Modifier::REVERSED),
+            false => Style::default(),
+        };
+        vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{value} "), style),
+            Span::raw("  "),
+        ]
+    }
+
+    fn option_span(label: &str, enabled: bool) -> Span<'_> {
+        match enabled {
+            true => Span::styled(label, Style::default().fg(Color::Black).bg(Color::Yellow)),
+            false => Span::styled(label, Style::default().fg(Color::DarkGray)),
+        }
+    }
+}
+
+impl Widget for &SearchBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let status = match (&self.error, self.current) {
+            (Some(error), _) => Span::styled(error.to_owned(), Style::default().fg(Color::Red)),
+            (None, Some(current)) => {
+                Span::raw(format!("{} of {}", current + 1, self.matches.len()))
+            }
+            (None, None) if self.query.is_empty() => Span::raw(""),
+            (None, None) => Span::styled("No results", Style::default().fg(Color::Red)),
+        };
+        let mut spans = self.field_spans("Find", &self.query, SearchField::Find);
+        spans.extend(self.field_spans("Replace", &self.replacement, SearchField::Replace));
+        spans.extend([
+            SearchBar::option_span(".*", self.options.regex),
+            Span::raw(" "),
+            SearchBar::option_span("Aa", self.options.case_sensitive),
+            Span::raw(" "),
+            SearchBar::option_span("\\b", self.options.whole_word),
+        ]);
+        Paragraph::new(Line::from(spans))
+            .block(
+                Block::default()
+                    .title("Search")
+                    .title_bottom(Line::from(status).alignment(Alignment::Right))
+                    .borders(Borders::ALL),
+            )
+            .render(area, buf);
+    }
+}
+
+/// Finds all non-empty matches of `regex` on each line.
+fn find_matches(lines: &[String], regex: &Regex) -> Vec<SearchMatch> {
+    lines
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            regex
+                .find_iter(line)
+                .filter(|m| !m.is_empty())
+                .map(move |m| SearchMatch {
+                    row,
+                    start: line[..m.start()].chars().count(),
+                    end: line[..m.end()].chars().count(),
+                })
+        })
+        .collect()
+}
+
+/// Converts a character column to a byte index in `line`.
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map_or(line.len(), |(index, _)| index)
+}
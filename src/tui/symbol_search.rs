@@ -0,0 +1,172 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::symbol_index::SymbolIndex;
+use crate::symbols::Symbol;
+use crate::tui::component::{Action, Component};
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use ratatui::widgets::{StatefulWidget, Widget};
+use std::path::PathBuf;
+
+/// A popup for finding a symbol anywhere in the project by name.
+#[derive(Default)]
+pub struct SymbolSearch {
+    visible: bool,
+    query: String,
+    results: Vec<(PathBuf, Symbol)>,
+    list_state: ListState,
+    /// The query and number of indexed symbols the results were found with, so the results are
+    /// only found again when either one changes.
+    searched: Option<(String, usize)>,
+    /// The areas drawn in the last frame, used for mouse input.
+    area: Rect,
+    list_area: Rect,
+}
+
+impl SymbolSearch {
+    pub const ID: &str = "SymbolSearch";
+
+    pub const HELP: &str = concat!(
+        "ESC: Close | Type to search symbols in the project | (↑/↓): Select symbol |",
+        " ENTER: Open symbol in editor"
+    );
+
+    /// The most results listed at once.
+    const MAX_RESULTS: usize = 100;
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn open(&mut self) {
+        trace!(target:Self::ID, "Opening {}", Self::ID);
+        self.visible = true;
+        self.query.clear();
+        self.searched = None;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.results.clear();
+    }
+
+    /// Searches the index again if the query changed or more symbols were indexed.
+    fn update(&mut self, index: &SymbolIndex) {
+        let searched = (self.query.clone(), index.symbol_count());
+        if self.searched.as_ref() == Some(&searched) {
+            return;
+        }
+        self.results = index.search(&self.query, Self::MAX_RESULTS);
+        self.list_state
+            .select((!self.results.is_empty()).then_some(0));
+        self.searched = Some(searched);
+    }
+
+    fn open_selected(&mut self) -> Action {
+        let Some((path, symbol)) = self.list_state.selected().and_then(|i| self.results.get(i))
+        else {
+            return Action::Handled;
+        };
+        let action = Action::OpenFileAt(path.clone(), symbol.row, symbol.col);
+        self.close();
+        action
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, index: &SymbolIndex) {
+        self.update(index);
+        self.area = area;
+        Clear.render(area, buf);
+        let title = match index.is_building() {
+            true => format!(
+                " Go to symbol (indexing {} symbols…) ",
+                index.symbol_count()
+            ),
+            false => " Go to symbol ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::LightYellow));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let [query_area, list_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+        Paragraph::new(format!("> {}█", self.query)).render(query_area, buf);
+
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|(path, symbol)| {
+                let path = path.strip_prefix(index.root()).unwrap_or(path);
+                ListItem::new(Line::from(vec![
+                    Span::styled(symbol.kind.label(), Style::default().fg(Color::Magenta)),
+                    Span::raw(" "),
+                    Span::raw(symbol.name.as_str()),
+                    Span::styled(
+                        format!("  {}:{}", path.display(), symbol.row + 1),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+        self.list_area = list_area;
+        StatefulWidget::render(
+            List::new(items).highlight_style(
+                Style::new()
+                    .fg(Color::Black)
+                    .bg(Color::Rgb(57, 59, 64))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            list_area,
+            buf,
+            &mut self.list_state,
+        );
+    }
+}
+
+impl Component for SymbolSearch {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Esc => self.close(),
+            KeyCode::Enter => return Ok(self.open_selected()),
+            KeyCode::Up => self.list_state.select_previous(),
+            KeyCode::Down => self.list_state.select_next(),
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.push(c)
+            }
+            _ => {}
+        }
+        // The popup takes all input while it is open.
+        Ok(Action::Handled)
+    }
+
+    /// Clicking a symbol opens it, and clicking outside of the popup closes it.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.list_state.scroll_down_by(1),
+            MouseEventKind::ScrollUp => self.list_state.scroll_up_by(1),
+            MouseEventKind::Down(_) if !self.area.contains(position) => self.close(),
+            MouseEventKind::Down(_) if self.list_area.contains(position) => {
+                let row = (mouse.row - self.list_area.y) as usize + self.list_state.offset();
+                if row < self.results.len() {
+                    self.list_state.select(Some(row));
+                    return Ok(self.open_selected());
+                }
+            }
+            _ => {}
+        }
+        Ok(Action::Handled)
+    }
+}
@@ -0,0 +1,175 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+// Editing helpers for buffer text stored as one string for each line, or as the Lines of an
+// EditorState.
+// Positions are character columns, and ranges exclude their end position.
+
+use edtui::{Index2, Lines, RowIndex};
+
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map_or(line.len(), |(index, _)| index)
+}
+
+fn clamp(lines: &[String], at: Index2) -> Index2 {
+    let row = at.row.min(lines.len().saturating_sub(1));
+    let col = lines
+        .get(row)
+        .map_or(0, |line| at.col.min(line.chars().count()));
+    Index2::new(row, col)
+}
+
+fn clamp_lines(lines: &Lines, at: Index2) -> Index2 {
+    let row = at.row.min(lines.len().saturating_sub(1));
+    Index2::new(row, at.col.min(lines.len_col(row).unwrap_or(0)))
+}
+
+/// Orders two positions so the first is never after the second.
+pub fn ordered(a: Index2, b: Index2) -> (Index2, Index2) {
+    match (a.row, a.col) <= (b.row, b.col) {
+        true => (a, b),
+        false => (b, a),
+    }
+}
+
+/// The text between two positions, with lines joined by newlines.
+pub fn text_in_range(lines: &[String], start: Index2, end: Index2) -> String {
+    let (start, end) = ordered(clamp(lines, start), clamp(lines, end));
+    if start.row == end.row {
+        let line = &lines[start.row];
+        return line[byte_index(line, start.col)..byte_index(line, end.col)].to_string();
+    }
+    let first = &lines[start.row];
+    let last = &lines[end.row];
+    let mut text = first[byte_index(first, start.col)..].to_string();
+    for line in &lines[start.row + 1..end.row] {
+        text.push('\n');
+        text.push_str(line);
+    }
+    text.push('\n');
+    text.push_str(&last[..byte_index(last, end.col)]);
+    text
+}
+
+/// Replaces the text between two positions with text that may contain newlines, returning the
+/// position following the inserted text. Only the rows in the range are changed.
+pub fn replace_range(lines: &mut Lines, start: Index2, end: Index2, text: &str) -> Index2 {
+    if lines.is_empty() {
+        lines.push(Vec::<char>::new());
+    }
+    let (start, end) = ordered(clamp_lines(lines, start), clamp_lines(lines, end));
+    let mut removed = lines.split_off(Index2::new(start.row + 1, 0));
+    let mut after = removed.split_off(Index2::new(end.row - start.row, 0));
+    let Some(first) = lines.get_mut(RowIndex::new(start.row)) else {
+        return start;
+    };
+    // The text after the range on its last row follows the inserted text.
+    let tail: Vec<char> = match end.row.checked_sub(start.row + 1) {
+        Some(last) => removed
+            .get(RowIndex::new(last))
+            .map_or(Vec::new(), |row| row[end.col..].to_vec()),
+        None => first[end.col..].to_vec(),
+    };
+    first.truncate(start.col);
+    let mut inserted = text.split('\n');
+    // split always yields at least one item, even for empty text.
+    first.extend(inserted.next().unwrap_or_default().chars());
+    let mut end = Index2::new(start.row, first.len());
+    for line in inserted {
+        let line: Vec<char> = line.chars().collect();
+        end = Index2::new(end.row + 1, line.len());
+        lines.push(line);
+    }
+    if let Some(last) = lines.get_mut(RowIndex::new(end.row)) {
+        last.extend(tail);
+    }
+    lines.append(&mut after);
+    end
+}
+
+/// The characters of a buffer, with lines separated by newlines.
+pub fn chars(lines: &Lines) -> impl DoubleEndedIterator<Item = char> + Clone + '_ {
+    lines.iter_row().enumerate().flat_map(|(row, line)| {
+        (row > 0)
+            .then_some('\n')
+            .into_iter()
+            .chain(line.iter().copied())
+    })
+}
+
+/// The leading whitespace of a line.
+pub fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// The closing bracket for an opening bracket, or None if `c` is not an opening bracket.
+pub fn closing_bracket(c: char) -> Option<char> {
+    match c {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+fn opening_bracket(c: char) -> Option<char> {
+    match c {
+        ')' => Some('('),
+        ']' => Some('['),
+        '}' => Some('{'),
+        _ => None,
+    }
+}
+
+/// Finds the bracket matching the one at a position, or the one before it, as the cursor is after
+/// the bracket just typed in insert mode. Returns the positions of both brackets.
+pub fn matching_bracket(lines: &[String], at: Index2) -> Option<(Index2, Index2)> {
+    // The number of rows searched in each direction, so large files stay responsive.
+    const MAX_ROWS: usize = 5000;
+    let line: Vec<char> = lines.get(at.row)?.chars().collect();
+    let is_bracket = |c: &char| closing_bracket(*c).or(opening_bracket(*c)).is_some();
+    let col = match line.get(at.col).filter(|c| is_bracket(c)) {
+        Some(_) => at.col,
+        None => at
+            .col
+            .checked_sub(1)
+            .filter(|col| line.get(*col).is_some_and(is_bracket))?,
+    };
+    let bracket = line[col];
+    let from = Index2::new(at.row, col);
+    let mut depth = 0;
+    if let Some(close) = closing_bracket(bracket) {
+        for (row, text) in lines.iter().enumerate().skip(at.row).take(MAX_ROWS) {
+            let start = if row == at.row { col } else { 0 };
+            for (n, c) in text.chars().enumerate().skip(start) {
+                if c == bracket {
+                    depth += 1;
+                } else if c == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some((from, Index2::new(row, n)));
+                    }
+                }
+            }
+        }
+    } else if let Some(open) = opening_bracket(bracket) {
+        for row in (at.row.saturating_sub(MAX_ROWS)..=at.row).rev() {
+            let chars: Vec<char> = lines[row].chars().collect();
+            let end = if row == at.row { col + 1 } else { chars.len() };
+            for n in (0..end).rev() {
+                if chars[n] == bracket {
+                    depth += 1;
+                } else if chars[n] == open {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some((from, Index2::new(row, n)));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::tui::history::History;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The result of input to the UndoTree that the Editor needs to act on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UndoTreeEvent {
+    /// Restore the buffer to the state of the node at this index in the History.
+    Jump(usize),
+    Close,
+    Handled,
+}
+
+/// A panel listing every state in an editor's undo tree, newest first.
+/// Each branch of the tree is drawn in its own column, similar to vim's undotree plugin.
+#[derive(Debug, Default)]
+pub struct UndoTree {
+    visible: bool,
+    list_state: ListState,
+    /// Node indices in the order they are listed.
+    rows: Vec<usize>,
+}
+
+impl UndoTree {
+    pub const HELP: &str =
+        "ESC: Close undo tree | (↑/k): Newer state | (↓/j): Older state | ENTER: Restore state";
+
+    pub const WIDTH: u16 = 32;
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Opens the panel with the current state selected.
+    pub fn open(&mut self, history: &History) {
+        self.visible = true;
+        self.rows = Self::rows(history);
+        self.list_state
+            .select(self.rows.iter().position(|i| *i == history.current()));
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+    }
+
+    pub fn handle_key_events(&mut self, key: KeyEvent) -> UndoTreeEvent {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => UndoTreeEvent::Close,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list_state.select_previous();
+                UndoTreeEvent::Handled
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list_state.select_next();
+                UndoTreeEvent::Handled
+            }
+            KeyCode::Enter => match self.list_state.selected().and_then(|i| self.rows.get(i)) {
+                Some(index) => UndoTreeEvent::Jump(*index),
+                None => UndoTreeEvent::Handled,
+            },
+            _ => UndoTreeEvent::Handled,
+        }
+    }
+
+    /// Node indices sorted from the newest change to the oldest.
+    fn rows(history: &History) -> Vec<usize> {
+        let mut rows: Vec<usize> = (0..history.nodes().len()).collect();
+        rows.sort_by_key(|i| std::cmp::Reverse(history.nodes()[*i].seq));
+        rows
+    }
+
+    /// Assigns each node a column. The first child continues its parent's column, and any other
+    /// children start a new branch in the next free column.
+    fn columns(history: &History) -> Vec<usize> {
+        let nodes = history.nodes();
+        let mut columns = vec![0; nodes.len()];
+        let mut next_column = 1;
+        let mut stack: Vec<usize> = (0..nodes.len())
+            .filter(|i| nodes[*i].parent.is_none())
+            .collect();
+        while let Some(index) = stack.pop() {
+            for (n, child) in nodes[index].children.iter().enumerate() {
+                columns[*child] = match n {
+                    0 => columns[index],
+                    _ => {
+                        next_column += 1;
+                        next_column - 1
+                    }
+                };
+                stack.push(*child);
+            }
+        }
+        columns
+    }
+
+    fn age(time: u64) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let seconds = now.saturating_sub(time);
+        match seconds {
+            0..60 => format!("{seconds}s ago"),
+            60..3600 => format!("{}m ago", seconds / 60),
+            3600..86400 => format!("{}h ago", seconds / 3600),
+            _ => format!("{}d ago", seconds / 86400),
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, history: &History) {
+        // The history changes as the buffer is edited, so the rows are rebuilt for each frame.
+        self.rows = Self::rows(history);
+        let nodes = history.nodes();
+        let columns = Self::columns(history);
+        let width = columns.iter().max().map_or(1, |c| c + 1);
+        // The range of changes each column spans, used to draw a line for each open branch.
+        let mut spans = vec![(usize::MAX, 0); width];
+        for (index, node) in nodes.iter().enumerate() {
+            let start = node.parent.map_or(node.seq, |p| nodes[p].seq);
+            let span = &mut spans[columns[index]];
+            *span = (span.0.min(start), span.1.max(node.seq));
+        }
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|index| {
+                let node = &nodes[*index];
+                let current = *index == history.current();
+                let graph: String = (0..width)
+                    .map(|column| match column {
+                        c if c == columns[*index] && current => "@ ",
+                        c if c == columns[*index] => "o ",
+                        c if spans[c].0 < node.seq && node.seq < spans[c].1 => "│ ",
+                        _ => "  ",
+                    })
+                    .collect();
+                let style = match current {
+                    true => Style::default().add_modifier(Modifier::BOLD),
+                    false => Style::default(),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(graph, Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("{:<4} ", node.seq), style),
+                    Span::styled(Self::age(node.time), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+
+        StatefulWidget::render(
+            List::new(items)
+                .block(Block::default().title("Undo tree").borders(Borders::ALL))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+            area,
+            buf,
+            &mut self.list_state,
+        );
+    }
+}
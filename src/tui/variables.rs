@@ -0,0 +1,154 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::debugger::{DebugCommand, Debugger};
+use crate::tui::component::{Action, Component, ComponentState, Focus};
+use crate::tui::debug_panel::DebugPanel;
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+
+/// The scopes of the selected frame and their variables, as a tree that expands as children are
+/// fetched from the debug adapter.
+pub struct Variables {
+    list_state: ListState,
+    /// Whether each variable drawn in the last frame is expanded, or None if it has no children.
+    expanded: Vec<Option<bool>>,
+    area: Rect,
+    pub(crate) component_state: ComponentState,
+}
+
+impl Variables {
+    pub const ID: &str = "Variables";
+
+    pub const HELP: &str = concat!(
+        "(↑/k)/(↓/j): Select variable | (Enter/Space): Expand / collapse |",
+        " (←/h)/(→/l): Collapse / expand"
+    );
+
+    pub fn new() -> Self {
+        trace!(target:Self::ID, "Building {}", Self::ID);
+        Self {
+            list_state: ListState::default(),
+            expanded: Vec::new(),
+            area: Rect::default(),
+            component_state: ComponentState::default(),
+        }
+    }
+
+    /// Expands or collapses the selected variable, if it has children and isn't already in the
+    /// requested state.
+    fn toggle(&self, expand: Option<bool>) -> Action {
+        let Some(index) = self.list_state.selected() else {
+            return Action::Handled;
+        };
+        match self.expanded.get(index).copied().flatten() {
+            Some(expanded) if expand != Some(expanded) => {
+                Action::Debug(DebugCommand::ToggleVariable(index))
+            }
+            _ => Action::Handled,
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, debugger: &Debugger) {
+        self.area = area;
+        let variables = debugger.variables();
+        self.expanded = variables
+            .iter()
+            .map(|v| v.has_children().then_some(v.expanded))
+            .collect();
+        match variables.len() {
+            0 => self.list_state.select(None),
+            len => self
+                .list_state
+                .select(Some(self.list_state.selected().unwrap_or(0).min(len - 1))),
+        }
+        let items: Vec<ListItem> = variables
+            .iter()
+            .map(|variable| {
+                let marker = match (variable.has_children(), variable.expanded) {
+                    (false, _) => "  ",
+                    (true, false) => "▸ ",
+                    (true, true) => "▾ ",
+                };
+                let mut spans = vec![Span::raw("  ".repeat(variable.depth)), Span::raw(marker)];
+                // Scopes, such as locals and registers, have no value.
+                if variable.depth == 0 {
+                    spans.push(Span::styled(
+                        variable.name.as_str(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ));
+                } else {
+                    spans.push(Span::styled(
+                        variable.name.as_str(),
+                        Style::default().fg(Color::Cyan),
+                    ));
+                    spans.push(Span::raw(" = "));
+                    spans.push(Span::raw(variable.value.as_str()));
+                    if !variable.type_name.is_empty() {
+                        spans.push(Span::styled(
+                            format!("  {}", variable.type_name),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        StatefulWidget::render(
+            List::new(items)
+                .block(DebugPanel::pane_block(
+                    "Variables".to_string(),
+                    &self.component_state,
+                ))
+                .highlight_style(DebugPanel::HIGHLIGHT_STYLE),
+            area,
+            buf,
+            &mut self.list_state,
+        );
+    }
+}
+
+impl Component for Variables {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.list_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.list_state.select_next(),
+            KeyCode::Home => self.list_state.select_first(),
+            KeyCode::End => self.list_state.select_last(),
+            KeyCode::Enter | KeyCode::Char(' ') => return Ok(self.toggle(None)),
+            KeyCode::Left | KeyCode::Char('h') => return Ok(self.toggle(Some(false))),
+            KeyCode::Right | KeyCode::Char('l') => return Ok(self.toggle(Some(true))),
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    /// Clicking a variable selects it, and expands or collapses it.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.list_state.scroll_down_by(1),
+            MouseEventKind::ScrollUp => self.list_state.scroll_up_by(1),
+            MouseEventKind::Down(_) => {
+                if let Some(row) = DebugPanel::list_row_at(self.area, &self.list_state, mouse)
+                    && row < self.expanded.len()
+                {
+                    self.list_state.select(Some(row));
+                    return Ok(self.toggle(None));
+                }
+            }
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    fn is_active(&self) -> bool {
+        self.component_state.focus == Focus::Active
+    }
+}
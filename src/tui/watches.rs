@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2026, Shaun Reed <shaunrd0@gmail.com>
+//
+// SPDX-License-Identifier: GNU General Public License v3.0 or later
+
+use crate::debugger::{DebugCommand, Debugger};
+use crate::tui::component::{Action, Component, ComponentState, Focus};
+use crate::tui::debug_panel::DebugPanel;
+use anyhow::Result;
+use log::trace;
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, StatefulWidget};
+
+/// Expressions evaluated in the selected frame each time the program stops.
+pub struct Watches {
+    list_state: ListState,
+    /// The expression being typed, while adding a watch.
+    input: Option<String>,
+    /// The number of watches drawn in the last frame.
+    count: usize,
+    area: Rect,
+    pub(crate) component_state: ComponentState,
+}
+
+impl Watches {
+    pub const ID: &str = "Watches";
+
+    pub const HELP: &str =
+        "(↑/k)/(↓/j): Select watch | (a/Insert): Add watch | (d/Delete): Remove watch";
+
+    const INPUT_HELP: &str = "Enter: Add watch | ESC: Cancel";
+
+    pub fn new() -> Self {
+        trace!(target:Self::ID, "Building {}", Self::ID);
+        Self {
+            list_state: ListState::default(),
+            input: None,
+            count: 0,
+            area: Rect::default(),
+            component_state: ComponentState::default(),
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, debugger: &Debugger) {
+        self.area = area;
+        let watches = debugger.watches();
+        self.count = watches.len();
+        if let Some(selected) = self.list_state.selected() {
+            self.list_state
+                .select(self.count.checked_sub(1).map(|last| selected.min(last)));
+        }
+        let mut items: Vec<ListItem> = watches
+            .iter()
+            .map(|watch| {
+                let value = match (&watch.value, watch.error) {
+                    (None, _) => {
+                        Span::styled("not available", Style::default().fg(Color::DarkGray))
+                    }
+                    (Some(value), true) => {
+                        Span::styled(value.as_str(), Style::default().fg(Color::Red))
+                    }
+                    (Some(value), false) => Span::raw(value.as_str()),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(watch.expression.as_str(), Style::default().fg(Color::Cyan)),
+                    Span::raw(" = "),
+                    value,
+                ]))
+            })
+            .collect();
+        if let Some(input) = &self.input {
+            items.push(ListItem::new(format!("+ {input}█")));
+        }
+        let title = match self.input {
+            Some(_) => format!("Watch ({})", Self::INPUT_HELP),
+            None => "Watch".to_string(),
+        };
+        StatefulWidget::render(
+            List::new(items)
+                .block(DebugPanel::pane_block(title, &self.component_state))
+                .highlight_style(DebugPanel::HIGHLIGHT_STYLE),
+            area,
+            buf,
+            &mut self.list_state,
+        );
+    }
+
+    fn handle_input_key(&mut self, key: KeyEvent) -> Action {
+        let Some(input) = self.input.as_mut() else {
+            return Action::Noop;
+        };
+        match key.code {
+            KeyCode::Esc => self.input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                let expression = input.trim().to_string();
+                self.input = None;
+                if !expression.is_empty() {
+                    return Action::Debug(DebugCommand::AddWatch(expression));
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+            _ => {}
+        }
+        Action::Handled
+    }
+}
+
+impl Component for Watches {
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Action> {
+        if self.input.is_some() {
+            return Ok(self.handle_input_key(key));
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.list_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.list_state.select_next(),
+            KeyCode::Char('a') | KeyCode::Insert => self.input = Some(String::new()),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                return Ok(match self.list_state.selected() {
+                    Some(index) if index < self.count => {
+                        Action::Debug(DebugCommand::RemoveWatch(index))
+                    }
+                    _ => Action::Handled,
+                });
+            }
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    /// Clicking a watch selects it, and clicking below the watches starts adding one.
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Action> {
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.list_state.scroll_down_by(1),
+            MouseEventKind::ScrollUp => self.list_state.scroll_up_by(1),
+            MouseEventKind::Down(_) => {
+                match DebugPanel::list_row_at(self.area, &self.list_state, mouse) {
+                    Some(row) if row < self.count => self.list_state.select(Some(row)),
+                    Some(_) if self.input.is_none() => self.input = Some(String::new()),
+                    _ => {}
+                }
+            }
+            _ => return Ok(Action::Noop),
+        }
+        Ok(Action::Handled)
+    }
+
+    fn is_active(&self) -> bool {
+        self.component_state.focus == Focus::Active
+    }
+}